@@ -0,0 +1,72 @@
+use std::hint::black_box;
+
+use algods::graph::{
+    CsrGraph, Directed, Graph, NodeIndex,
+    algo::{bfs, dijkstra},
+};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+const NODES: usize = 100_000;
+const DEGREE: usize = 8;
+
+/// 确定性的伪随机稀疏图（线性同余生成器）
+fn build_graph() -> Graph<(), u32, Directed> {
+    let mut g = Graph::new();
+    let nodes: Vec<_> = (0..NODES).map(|_| g.add_node(())).collect();
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as usize
+    };
+    for u in 0..NODES {
+        // 保证连通的链边 + 随机边
+        g.add_edge(nodes[u], nodes[(u + 1) % NODES], 1);
+        for _ in 1..DEGREE {
+            let v = next() % NODES;
+            let w = (next() % 100) as u32 + 1;
+            g.add_edge(nodes[u], nodes[v], w);
+        }
+    }
+    g
+}
+
+fn bench_bfs(c: &mut Criterion) {
+    let g = build_graph();
+    let csr = CsrGraph::from(&g);
+    let start = NodeIndex(0);
+
+    let mut group = c.benchmark_group("bfs 100K x 8");
+
+    group.bench_function("Graph", |b| {
+        b.iter(|| black_box(bfs(&g, black_box(start)).len()));
+    });
+
+    group.bench_function("CsrGraph", |b| {
+        b.iter(|| black_box(bfs(&csr, black_box(start)).len()));
+    });
+
+    group.finish();
+}
+
+fn bench_dijkstra(c: &mut Criterion) {
+    let g = build_graph();
+    let csr = CsrGraph::from(&g);
+    let (from, to) = (NodeIndex(0), NodeIndex(NODES / 2));
+
+    let mut group = c.benchmark_group("dijkstra 100K x 8");
+
+    group.bench_function("Graph", |b| {
+        b.iter(|| black_box(dijkstra(&g, black_box(from), black_box(to))));
+    });
+
+    group.bench_function("CsrGraph", |b| {
+        b.iter(|| black_box(dijkstra(&csr, black_box(from), black_box(to))));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_bfs, bench_dijkstra);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use std::hint::black_box;
 
-use algods::hierarchy::{hierarchy::Hierarchy, tree::Tree, vec_tree::VecTree};
+use algods::hierarchy::{hierarchy::Hierarchy, lcrs_tree::LcrsTree, tree::Tree, vec_tree::VecTree};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 fn build_large_tree(n: usize) -> VecTree<u32> {
@@ -55,12 +55,129 @@ fn bench_parent_children(c: &mut Criterion) {
             let mut count = 0usize;
             for n in tree.dfs_iter(root).unwrap() {
                 let _ = tree.parent(n).unwrap();
-                count += tree.children(n).unwrap().len();
+                count += tree.children(n).unwrap().count();
             }
             black_box(count);
         });
     });
 }
 
-criterion_group!(benches, bench_dfs, bench_bfs, bench_parent_children);
+/// 宽树：根节点下挂 n - 1 个叶子
+fn build_wide<T: Tree<Value = u32>>(mut tree: T, n: usize) -> T {
+    let root = tree.root();
+    for i in 1..n {
+        tree.add_child(root, i as u32).unwrap();
+    }
+    tree
+}
+
+/// 深树：长度为 n 的链
+fn build_deep<T: Tree<Value = u32>>(mut tree: T, n: usize) -> T {
+    let mut cur = tree.root();
+    for i in 1..n {
+        cur = tree.add_child(cur, i as u32).unwrap();
+    }
+    tree
+}
+
+fn dfs_sum<T: Tree<Value = u32>>(tree: &T) -> u64 {
+    tree.dfs_iter(tree.root())
+        .unwrap()
+        .map(|n| *tree.value(n).unwrap() as u64)
+        .sum()
+}
+
+fn add_and_peek<T: Tree<Value = u32>>(mut tree: T, n: usize) -> u32 {
+    let root = tree.root();
+    let mut sum = 0;
+    for i in 1..n {
+        tree.add_child(root, i as u32).unwrap();
+        let last = tree.children(root).unwrap().next_back().unwrap();
+        sum += *tree.value(last).unwrap();
+    }
+    sum
+}
+
+fn bench_layouts(c: &mut Criterion) {
+    const N: usize = 100_000;
+
+    let mut group = c.benchmark_group("layout build");
+    group.bench_function("VecTree wide", |b| {
+        b.iter(|| black_box(build_wide(VecTree::with_root(0), N)))
+    });
+    group.bench_function("LcrsTree wide", |b| {
+        b.iter(|| black_box(build_wide(LcrsTree::with_root(0), N)))
+    });
+    group.bench_function("VecTree deep", |b| {
+        b.iter(|| black_box(build_deep(VecTree::with_root(0), N)))
+    });
+    group.bench_function("LcrsTree deep", |b| {
+        b.iter(|| black_box(build_deep(LcrsTree::with_root(0), N)))
+    });
+    group.finish();
+
+    let vec_wide = build_wide(VecTree::with_root(0), N);
+    let lcrs_wide = build_wide(LcrsTree::with_root(0), N);
+    let vec_deep = build_deep(VecTree::with_root(0), N);
+    let lcrs_deep = build_deep(LcrsTree::with_root(0), N);
+
+    let mut group = c.benchmark_group("layout dfs");
+    group.bench_function("VecTree wide", |b| b.iter(|| black_box(dfs_sum(&vec_wide))));
+    group.bench_function("LcrsTree wide", |b| {
+        b.iter(|| black_box(dfs_sum(&lcrs_wide)))
+    });
+    group.bench_function("VecTree deep", |b| b.iter(|| black_box(dfs_sum(&vec_deep))));
+    group.bench_function("LcrsTree deep", |b| {
+        b.iter(|| black_box(dfs_sum(&lcrs_deep)))
+    });
+    group.finish();
+
+    // 宽节点上交替添加子节点与访问最新的子节点
+    let mut group = c.benchmark_group("layout add+children");
+    group.sample_size(10);
+    group.bench_function("VecTree wide", |b| {
+        b.iter(|| black_box(add_and_peek(VecTree::with_root(0), N / 10)))
+    });
+    group.bench_function("LcrsTree wide", |b| {
+        b.iter(|| black_box(add_and_peek(LcrsTree::with_root(0), N / 10)))
+    });
+    group.finish();
+
+    // 删除宽树中的叶子：VecTree 需在兄弟数组中线性查找，LcrsTree 只需摘链
+    let mut group = c.benchmark_group("layout remove leaves");
+    group.sample_size(10);
+    group.bench_function("VecTree wide", |b| {
+        b.iter_with_setup(
+            || build_wide(VecTree::with_root(0), N / 10),
+            |mut t| {
+                let leaves: Vec<_> = t.children(t.root()).unwrap().collect();
+                for n in leaves {
+                    t.remove_subtree(n).unwrap();
+                }
+                black_box(t.size())
+            },
+        )
+    });
+    group.bench_function("LcrsTree wide", |b| {
+        b.iter_with_setup(
+            || build_wide(LcrsTree::with_root(0), N / 10),
+            |mut t| {
+                let leaves: Vec<_> = t.children(t.root()).unwrap().collect();
+                for n in leaves {
+                    t.remove_subtree(n).unwrap();
+                }
+                black_box(t.size())
+            },
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_dfs,
+    bench_bfs,
+    bench_parent_children,
+    bench_layouts
+);
 criterion_main!(benches);
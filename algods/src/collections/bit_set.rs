@@ -91,6 +91,14 @@ impl BitSet {
         }
     }
 
+    /// 原地并集：self = self ∪ other
+    pub fn union_with(&mut self, other: &BitSet) {
+        assert_eq!(self.size, other.size);
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a |= b;
+        }
+    }
+
     /// 计算交集
     pub fn intersection(&self, other: &BitSet) -> BitSet {
         assert_eq!(self.size, other.size);
@@ -106,6 +114,14 @@ impl BitSet {
         }
     }
 
+    /// 原地交集：self = self ∩ other
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        assert_eq!(self.size, other.size);
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a &= b;
+        }
+    }
+
     /// 计算差集
     pub fn difference(&self, other: &BitSet) -> BitSet {
         assert_eq!(self.size, other.size);
@@ -121,6 +137,14 @@ impl BitSet {
         }
     }
 
+    /// 原地差集：self = self \ other
+    pub fn difference_with(&mut self, other: &BitSet) {
+        assert_eq!(self.size, other.size);
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a &= !b;
+        }
+    }
+
     /// 计算对称差集
     pub fn symmetric_difference(&self, other: &BitSet) -> BitSet {
         assert_eq!(self.size, other.size);
@@ -145,6 +169,16 @@ impl BitSet {
             .all(|(a, b)| (a & b) == *a)
     }
 
+    /// 返回交集的元素个数，不分配新集合
+    pub fn intersection_len(&self, other: &BitSet) -> usize {
+        assert_eq!(self.size, other.size);
+        self.bits
+            .iter()
+            .zip(&other.bits)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
     /// 判断两个集合是否有交集
     pub fn intersects(&self, other: &BitSet) -> bool {
         assert_eq!(self.size, other.size);
@@ -266,6 +300,38 @@ mod tests {
         assert_eq!(c.len(), 3);
     }
 
+    #[test]
+    fn test_union_with() {
+        let mut a = BitSet::new(70);
+        let mut b = BitSet::new(70);
+        a.insert(1);
+        b.insert(1);
+        b.insert(65);
+
+        a.union_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![1, 65]);
+    }
+
+    #[test]
+    fn test_in_place_ops() {
+        let mut a = BitSet::new(130);
+        let mut b = BitSet::new(130);
+        for i in [1, 64, 129] {
+            a.insert(i);
+        }
+        for i in [64, 100, 129] {
+            b.insert(i);
+        }
+        assert_eq!(a.intersection_len(&b), 2);
+
+        let mut c = a.clone();
+        c.intersect_with(&b);
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![64, 129]);
+
+        a.difference_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![1]);
+    }
+
     #[test]
     fn test_intersection() {
         let mut a = BitSet::new(10);
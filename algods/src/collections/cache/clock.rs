@@ -0,0 +1,62 @@
+//! 缓存过期判断使用的时钟
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// 单调时钟
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// 系统单调时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// 手动推进的时钟，克隆出的句柄共享同一时间，便于在测试中控制过期
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    base: Instant,
+    /// 相对 base 的偏移（纳秒）
+    offset: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            offset: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 将时间向前推进 d，累计偏移在 `u64::MAX` 纳秒（约 584 年）处饱和
+    pub fn advance(&self, d: Duration) {
+        let nanos = u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        let _ = self
+            .offset
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |t| {
+                Some(t.saturating_add(nanos))
+            });
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.base + Duration::from_nanos(self.offset.load(Ordering::Relaxed))
+    }
+}
@@ -0,0 +1,133 @@
+//! 淘汰策略共用的下标链表与幽灵队列
+use std::collections::{HashMap, VecDeque};
+
+pub(crate) const NIL: usize = usize::MAX;
+
+/// 以槽位下标为节点的双向链表头，头部为最新，尾部为最旧
+#[derive(Clone, Copy)]
+pub(crate) struct List {
+    head: usize,
+    tail: usize,
+    pub(crate) len: usize,
+}
+
+impl List {
+    pub(crate) const EMPTY: Self = Self {
+        head: NIL,
+        tail: NIL,
+        len: 0,
+    };
+
+    pub(crate) fn front(&self) -> Option<usize> {
+        (self.head != NIL).then_some(self.head)
+    }
+
+    pub(crate) fn back(&self) -> Option<usize> {
+        (self.tail != NIL).then_some(self.tail)
+    }
+}
+
+impl Default for List {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// 多条链表共享的前后指针，每个槽位同一时刻至多属于一条链表
+#[derive(Default)]
+pub(crate) struct Links {
+    prev: Vec<usize>,
+    next: Vec<usize>,
+}
+
+impl Links {
+    pub(crate) fn push_front(&mut self, list: &mut List, slot: usize) {
+        if slot >= self.prev.len() {
+            self.prev.resize(slot + 1, NIL);
+            self.next.resize(slot + 1, NIL);
+        }
+        self.prev[slot] = NIL;
+        self.next[slot] = list.head;
+        if list.head == NIL {
+            list.tail = slot;
+        } else {
+            self.prev[list.head] = slot;
+        }
+        list.head = slot;
+        list.len += 1;
+    }
+
+    pub(crate) fn unlink(&mut self, list: &mut List, slot: usize) {
+        let (prev, next) = (self.prev[slot], self.next[slot]);
+        if prev == NIL {
+            list.head = next;
+        } else {
+            self.next[prev] = next;
+        }
+        if next == NIL {
+            list.tail = prev;
+        } else {
+            self.prev[next] = prev;
+        }
+        list.len -= 1;
+    }
+
+    pub(crate) fn pop_back(&mut self, list: &mut List) -> Option<usize> {
+        let slot = list.back()?;
+        self.unlink(list, slot);
+        Some(slot)
+    }
+
+    /// 移到链表头部
+    pub(crate) fn touch(&mut self, list: &mut List, slot: usize) {
+        if list.head != slot {
+            self.unlink(list, slot);
+            self.push_front(list, slot);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.prev.clear();
+        self.next.clear();
+    }
+}
+
+/// 只记录键哈希的先进先出幽灵队列，用于识别最近被淘汰后又再次写入的键
+///
+/// 中途删除采用惰性标记：队列中序号与 `live` 不一致的记录视为已删除
+#[derive(Default)]
+pub(crate) struct Ghost {
+    order: VecDeque<(u64, u64)>,
+    live: HashMap<u64, u64>,
+    seq: u64,
+}
+
+impl Ghost {
+    pub(crate) fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    /// 记录 hash，并把队列裁剪到不超过 cap 条
+    pub(crate) fn insert(&mut self, hash: u64, cap: usize) {
+        self.seq += 1;
+        self.live.insert(hash, self.seq);
+        self.order.push_back((hash, self.seq));
+        while self.live.len() > cap {
+            let Some((h, s)) = self.order.pop_front() else {
+                break;
+            };
+            if self.live.get(&h) == Some(&s) {
+                self.live.remove(&h);
+            }
+        }
+        if self.order.len() > 2 * self.live.len() + 16 {
+            let live = &self.live;
+            self.order.retain(|(h, s)| live.get(h) == Some(s));
+        }
+    }
+
+    /// 删除 hash，返回它是否在队列中
+    pub(crate) fn remove(&mut self, hash: u64) -> bool {
+        self.live.remove(&hash).is_some()
+    }
+}
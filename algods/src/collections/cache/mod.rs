@@ -0,0 +1,674 @@
+//! 可插拔淘汰策略的缓存
+//!
+//! [`Cache`] 负责存储、权重与过期，淘汰顺序交给 [`EvictionPolicy`]：
+//! [`LruPolicy`]、[`LfuPolicy`]、[`ArcPolicy`]、[`S3FifoPolicy`] 与 [`WTinyLfuPolicy`]。
+//! 只需要按条目数淘汰的纯 LRU 场景可以直接使用更轻量的 [`LruCache`](super::LruCache)
+//!
+//! ```
+//! use std::time::Duration;
+//! use algods::collections::cache::{Cache, ManualClock, S3FifoPolicy};
+//!
+//! let clock = ManualClock::new();
+//! // 按响应体字节数计容量，默认 30 秒过期
+//! let mut responses = Cache::builder(1024)
+//!     .policy(S3FifoPolicy::new())
+//!     .weigher(|_path: &String, body: &Vec<u8>| body.len())
+//!     .time_to_live(Duration::from_secs(30))
+//!     .clock(clock.clone())
+//!     .build();
+//!
+//! responses.insert("/index.html".to_string(), vec![0; 600]);
+//! responses.insert("/app.js".to_string(), vec![0; 600]); // 超出 1024 字节，淘汰较早的条目
+//! assert_eq!(responses.len(), 1);
+//! assert_eq!(responses.weighted_size(), 600);
+//!
+//! clock.advance(Duration::from_secs(31));
+//! assert_eq!(responses.get("/app.js"), None);
+//! ```
+pub mod clock;
+mod list;
+pub mod policy;
+
+pub use clock::*;
+pub use policy::*;
+
+use std::{
+    borrow::Borrow,
+    collections::{BTreeSet, HashMap},
+    hash::{BuildHasher, Hash, RandomState},
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+type Weigher<K, V> = Box<dyn Fn(&K, &V) -> usize + Send + Sync>;
+type Listener<K, V> = Box<dyn FnMut(K, V, RemovalCause) + Send>;
+
+/// 条目被缓存自行移除的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemovalCause {
+    /// 超出容量被淘汰策略选中
+    Evicted,
+    /// 存活时间已到
+    Expired,
+    /// 单个条目的权重超过了缓存容量，无法存放
+    Rejected,
+}
+
+struct Entry<K, V> {
+    key: K,
+    val: V,
+    weight: usize,
+    expires: Option<Instant>,
+}
+
+/// 带淘汰策略、权重与过期时间的缓存
+///
+/// 容量以权重计，未设置权重函数时每个条目权重为 1。
+/// 条目可以带存活时间，过期条目在访问时或写入前被清理，
+/// 被缓存自行移除的条目（淘汰、过期、权重过大）会交给淘汰监听器
+///
+/// ## Notes
+/// 时间复杂度: 查找与写入均摊 O(1)（带过期时间的条目额外 O(log n)），
+/// 具体取决于淘汰策略
+pub struct Cache<K, V, P = LruPolicy, S = RandomState> {
+    map: HashMap<K, usize, S>,
+    entries: Vec<Option<Entry<K, V>>>,
+    /// 空闲槽位
+    free: Vec<usize>,
+    policy: P,
+    capacity: usize,
+    /// 当前总权重
+    weight: usize,
+    weigher: Option<Weigher<K, V>>,
+    /// 默认存活时间
+    ttl: Option<Duration>,
+    /// 按过期时间排序的槽位
+    expiry: BTreeSet<(Instant, usize)>,
+    clock: Arc<dyn Clock>,
+    listener: Option<Listener<K, V>>,
+}
+
+/// [`Cache`] 构建器
+pub struct CacheBuilder<K, V, P = LruPolicy, S = RandomState> {
+    capacity: usize,
+    policy: P,
+    hasher: S,
+    weigher: Option<Weigher<K, V>>,
+    ttl: Option<Duration>,
+    clock: Arc<dyn Clock>,
+    listener: Option<Listener<K, V>>,
+}
+
+impl<K, V> Cache<K, V> {
+    /// 创建按条目数计容量的 LRU 缓存
+    pub fn new(capacity: usize) -> Self {
+        Self::builder(capacity).build()
+    }
+
+    /// 以容量（总权重上限）开始构建
+    pub fn builder(capacity: usize) -> CacheBuilder<K, V> {
+        CacheBuilder {
+            capacity,
+            policy: LruPolicy::new(),
+            hasher: RandomState::new(),
+            weigher: None,
+            ttl: None,
+            clock: Arc::new(SystemClock),
+            listener: None,
+        }
+    }
+}
+
+impl<K, V, P, S> CacheBuilder<K, V, P, S> {
+    /// 设置淘汰策略，默认 LRU
+    pub fn policy<P2: EvictionPolicy>(self, policy: P2) -> CacheBuilder<K, V, P2, S> {
+        CacheBuilder {
+            capacity: self.capacity,
+            policy,
+            hasher: self.hasher,
+            weigher: self.weigher,
+            ttl: self.ttl,
+            clock: self.clock,
+            listener: self.listener,
+        }
+    }
+
+    /// 设置哈希器
+    pub fn hasher<S2: BuildHasher>(self, hasher: S2) -> CacheBuilder<K, V, P, S2> {
+        CacheBuilder {
+            capacity: self.capacity,
+            policy: self.policy,
+            hasher,
+            weigher: self.weigher,
+            ttl: self.ttl,
+            clock: self.clock,
+            listener: self.listener,
+        }
+    }
+
+    /// 设置权重函数，例如按字节数计容量；写入时计算一次
+    pub fn weigher<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&K, &V) -> usize + Send + Sync + 'static,
+    {
+        self.weigher = Some(Box::new(f));
+        self
+    }
+
+    /// 设置默认存活时间，[`Cache::insert_with_ttl`] 可以逐条覆盖；`Duration::MAX` 等过大的值视为永不过期
+    pub fn time_to_live(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// 设置判断过期使用的时钟，默认系统单调时钟
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// 设置淘汰监听器，接收被缓存自行移除的条目及原因
+    pub fn eviction_listener<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(K, V, RemovalCause) + Send + 'static,
+    {
+        self.listener = Some(Box::new(f));
+        self
+    }
+
+    pub fn build(self) -> Cache<K, V, P, S> {
+        Cache {
+            map: HashMap::with_hasher(self.hasher),
+            entries: Vec::new(),
+            free: Vec::new(),
+            policy: self.policy,
+            capacity: self.capacity,
+            weight: 0,
+            weigher: self.weigher,
+            ttl: self.ttl,
+            expiry: BTreeSet::new(),
+            clock: self.clock,
+            listener: self.listener,
+        }
+    }
+}
+
+impl<K, V, P, S> Cache<K, V, P, S> {
+    /// 条目数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// 总权重上限
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 当前总权重
+    pub fn weighted_size(&self) -> usize {
+        self.weight
+    }
+
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+
+    fn is_expired(&self, slot: usize) -> bool {
+        self.entries[slot]
+            .as_ref()
+            .and_then(|e| e.expires)
+            .is_some_and(|t| t <= self.clock.now())
+    }
+
+    fn notify(&mut self, key: K, val: V, cause: RemovalCause) {
+        if let Some(listener) = &mut self.listener {
+            listener(key, val, cause);
+        }
+    }
+}
+
+impl<K, V, P, S> Cache<K, V, P, S>
+where
+    K: Eq + Hash + Clone,
+    P: EvictionPolicy,
+    S: BuildHasher,
+{
+    /// 访问 key 并记录命中，过期的条目被移除并返回 None
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&slot) = self.map.get(key) else {
+            let hash = self.map.hasher().hash_one(key);
+            self.policy.on_miss(hash);
+            return None;
+        };
+        if self.is_expired(slot) {
+            self.expire(slot);
+            return None;
+        }
+        self.policy.on_access(slot);
+        self.entries[slot].as_ref().map(|e| &e.val)
+    }
+
+    /// 只读，不记录命中；过期的条目视为不存在
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &slot = self.map.get(key)?;
+        if self.is_expired(slot) {
+            return None;
+        }
+        self.entries[slot].as_ref().map(|e| &e.val)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    /// 写入键值对，使用默认存活时间；key 已存在时返回旧值
+    ///
+    /// 写入后超出容量的条目按淘汰策略移除并交给淘汰监听器
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        self.insert_inner(key, val, self.ttl)
+    }
+
+    /// 写入键值对，存活时间为 ttl（过大时视为永不过期）；key 已存在时返回旧值
+    pub fn insert_with_ttl(&mut self, key: K, val: V, ttl: Duration) -> Option<V> {
+        self.insert_inner(key, val, Some(ttl))
+    }
+
+    fn insert_inner(&mut self, key: K, val: V, ttl: Option<Duration>) -> Option<V> {
+        self.purge_expired();
+        let weight = self.weigher.as_ref().map_or(1, |f| f(&key, &val));
+        // 到期时刻超出 Instant 的表示范围时视为永不过期
+        let expires = ttl.and_then(|d| self.clock.now().checked_add(d));
+
+        if let Some(&slot) = self.map.get(&key) {
+            let e = self.entries[slot]
+                .as_mut()
+                .expect("mapped slot is occupied");
+            let old = mem::replace(&mut e.val, val);
+            let old_weight = mem::replace(&mut e.weight, weight);
+            if let Some(t) = mem::replace(&mut e.expires, expires) {
+                self.expiry.remove(&(t, slot));
+            }
+            if let Some(t) = expires {
+                self.expiry.insert((t, slot));
+            }
+            self.weight = self.weight - old_weight + weight;
+
+            if weight > self.capacity {
+                self.policy.on_remove(slot);
+                let e = self.take(slot);
+                self.notify(e.key, e.val, RemovalCause::Rejected);
+            } else {
+                self.policy.on_access(slot);
+                self.evict_to_capacity();
+            }
+            return Some(old);
+        }
+
+        if weight > self.capacity {
+            self.notify(key, val, RemovalCause::Rejected);
+            return None;
+        }
+
+        let hash = self.map.hasher().hash_one(&key);
+        let entry = Some(Entry {
+            key: key.clone(),
+            val,
+            weight,
+            expires,
+        });
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.entries[slot] = entry;
+                slot
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        self.map.insert(key, slot);
+        if let Some(t) = expires {
+            self.expiry.insert((t, slot));
+        }
+        self.weight += weight;
+        self.policy.on_insert(slot, hash);
+        self.evict_to_capacity();
+        None
+    }
+
+    /// 删除 key，返回其值（已过期但尚未清理的条目同样返回）
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &slot = self.map.get(key)?;
+        self.policy.on_remove(slot);
+        Some(self.take(slot).val)
+    }
+
+    /// 移除所有已过期的条目，返回移除的数量
+    pub fn purge_expired(&mut self) -> usize {
+        if self.expiry.is_empty() {
+            return 0;
+        }
+        let now = self.clock.now();
+        let mut count = 0;
+        while let Some(&(t, slot)) = self.expiry.first()
+            && t <= now
+        {
+            self.expire(slot);
+            count += 1;
+        }
+        count
+    }
+
+    /// 修改容量，超出部分立即按淘汰策略移除
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_to_capacity();
+    }
+
+    /// 清空，不通知淘汰监听器
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.entries.clear();
+        self.free.clear();
+        self.expiry.clear();
+        self.weight = 0;
+        self.policy.clear();
+    }
+
+    fn evict_to_capacity(&mut self) {
+        while self.weight > self.capacity {
+            let Some(slot) = self.policy.evict() else {
+                break;
+            };
+            let e = self.take(slot);
+            self.notify(e.key, e.val, RemovalCause::Evicted);
+        }
+    }
+
+    fn expire(&mut self, slot: usize) {
+        self.policy.on_remove(slot);
+        let e = self.take(slot);
+        self.notify(e.key, e.val, RemovalCause::Expired);
+    }
+
+    /// 取出槽位中的条目并释放槽位，调用者负责通知淘汰策略
+    fn take(&mut self, slot: usize) -> Entry<K, V> {
+        let e = self.entries[slot].take().expect("slot is occupied");
+        self.map.remove(&e.key);
+        if let Some(t) = e.expires {
+            self.expiry.remove(&(t, slot));
+        }
+        self.weight -= e.weight;
+        self.free.push(slot);
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    type Log = Arc<Mutex<Vec<(i32, RemovalCause)>>>;
+
+    fn logging<P: EvictionPolicy>(capacity: usize, policy: P) -> (Cache<i32, i32, P>, Log) {
+        let log = Log::default();
+        let sink = log.clone();
+        let cache = Cache::builder(capacity)
+            .policy(policy)
+            .eviction_listener(move |k, _v, cause| sink.lock().unwrap().push((k, cause)))
+            .build();
+        (cache, log)
+    }
+
+    /// 随机操作下各策略都不超出容量，且与存储保持一致
+    fn check_policy<P: EvictionPolicy>(policy: P) {
+        let (mut cache, log) = logging(16, policy);
+        let mut x = 7u64;
+        for _ in 0..5000 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let k = ((x >> 33) % 64) as i32;
+            match x % 5 {
+                0 => {
+                    cache.remove(&k);
+                }
+                1 | 2 => {
+                    cache.insert(k, k * 10);
+                }
+                _ => {
+                    if let Some(v) = cache.get(&k) {
+                        assert_eq!(*v, k * 10);
+                    }
+                }
+            }
+            assert!(cache.len() <= 16);
+            assert_eq!(cache.weighted_size(), cache.len());
+        }
+        let log = log.lock().unwrap();
+        assert!(!log.is_empty());
+        assert!(log.iter().all(|&(_, cause)| cause == RemovalCause::Evicted));
+        drop(log);
+
+        // 清空后重新装满，策略的状态应同样被清空
+        cache.clear();
+        for k in 0..20 {
+            cache.insert(k, k * 10);
+        }
+        assert_eq!(cache.len(), 16);
+        let resident = (0..20).filter(|k| cache.contains_key(k)).count();
+        assert_eq!(resident, 16);
+    }
+
+    #[test]
+    fn test_policies_respect_capacity() {
+        check_policy(LruPolicy::new());
+        check_policy(LfuPolicy::new());
+        check_policy(ArcPolicy::new());
+        check_policy(S3FifoPolicy::new());
+        check_policy(WTinyLfuPolicy::new(16));
+    }
+
+    #[test]
+    fn test_lru_matches_lru_cache() {
+        let (mut cache, log) = logging(2, LruPolicy::new());
+        cache.insert(1, 10);
+        cache.insert(2, 20);
+        cache.get(&1);
+        assert_eq!(cache.insert(3, 30), None);
+        assert_eq!(*log.lock().unwrap(), [(2, RemovalCause::Evicted)]);
+        assert_eq!(cache.insert(1, 100), Some(10));
+        assert_eq!(cache.peek(&1), Some(&100));
+        assert_eq!(cache.remove(&3), Some(30));
+        assert_eq!(cache.len(), 1);
+        // 显式删除不通知监听器
+        assert_eq!(log.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_lfu_keeps_frequent_keys() {
+        let mut cache = Cache::builder(3).policy(LfuPolicy::new()).build();
+        for k in 0..3 {
+            cache.insert(k, ());
+        }
+        for _ in 0..3 {
+            cache.get(&0);
+            cache.get(&1);
+        }
+        for k in 10..20 {
+            cache.insert(k, ());
+        }
+        assert!(cache.contains_key(&0) && cache.contains_key(&1));
+        assert!(cache.contains_key(&19));
+    }
+
+    #[test]
+    fn test_scan_resistance() {
+        // 冷键组成的长扫描中穿插少量热点访问，同一热键两次访问之间的冷键数超过容量
+        fn hot_hits<P: EvictionPolicy>(policy: P) -> usize {
+            let mut cache = Cache::builder(10).policy(policy).build();
+            let mut hits = 0;
+            for i in 0..3000 {
+                cache.insert(100 + i, 0);
+                if i % 3 == 0 {
+                    let k = (i / 3) % 5;
+                    match cache.get(&k) {
+                        Some(_) => hits += 1,
+                        None => {
+                            cache.insert(k, 0);
+                        }
+                    }
+                }
+            }
+            hits
+        }
+        // LRU 与 LFU 中热点总在下一次访问前被冷键挤出，其余策略能识别出热点
+        assert_eq!(hot_hits(LruPolicy::new()), 0);
+        assert_eq!(hot_hits(LfuPolicy::new()), 0);
+        assert!(hot_hits(ArcPolicy::new()) > 950);
+        assert!(hot_hits(S3FifoPolicy::new()) > 950);
+        assert!(hot_hits(WTinyLfuPolicy::new(10)) > 950);
+    }
+
+    #[test]
+    fn test_ttl_with_manual_clock() {
+        let clock = ManualClock::new();
+        let log = Log::default();
+        let sink = log.clone();
+        let mut cache = Cache::builder(10)
+            .time_to_live(Duration::from_secs(10))
+            .clock(clock.clone())
+            .eviction_listener(move |k, _v, cause| sink.lock().unwrap().push((k, cause)))
+            .build();
+
+        cache.insert(1, "a");
+        cache.insert_with_ttl(2, "b", Duration::from_secs(60));
+        clock.advance(Duration::from_secs(5));
+        cache.insert(3, "c");
+        assert_eq!(cache.get(&1), Some(&"a"));
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(cache.peek(&1), None);
+        assert!(!cache.contains_key(&1));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.len(), 2);
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.get(&2), Some(&"b"));
+        // 覆盖写入会重置为默认存活时间
+        cache.insert(2, "B");
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(cache.get(&2), Some(&"B"));
+        clock.advance(Duration::from_secs(1));
+        cache.insert(4, "d");
+        assert!(!cache.contains_key(&2));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                (1, RemovalCause::Expired),
+                (3, RemovalCause::Expired),
+                (2, RemovalCause::Expired)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unbounded_ttl_never_expires() {
+        let clock = ManualClock::new();
+        let mut cache = Cache::builder(10)
+            .time_to_live(Duration::MAX)
+            .clock(clock.clone())
+            .build();
+
+        cache.insert(1, "a");
+        cache.insert_with_ttl(2, "b", Duration::from_secs(10));
+        cache.insert_with_ttl(3, "c", Duration::MAX);
+        // 覆盖为永不过期后，原来的到期时刻不再生效
+        cache.insert_with_ttl(2, "B", Duration::MAX);
+
+        clock.advance(Duration::from_secs(365 * 24 * 3600));
+        assert_eq!(cache.purge_expired(), 0);
+        // 推进时间同样不会溢出，而是停在可表示的最远时刻
+        let before = clock.now();
+        clock.advance(Duration::MAX);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            clock.now() - before,
+            Duration::from_nanos(u64::MAX) - Duration::from_secs(365 * 24 * 3600)
+        );
+        assert_eq!(cache.get(&1), Some(&"a"));
+        assert_eq!(cache.get(&2), Some(&"B"));
+        assert_eq!(cache.get(&3), Some(&"c"));
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_weighted_capacity() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = log.clone();
+        let mut cache = Cache::builder(100)
+            .weigher(|_k: &&str, v: &Vec<u8>| v.len())
+            .eviction_listener(move |k, v: Vec<u8>, cause| {
+                sink.lock().unwrap().push((k, v.len(), cause))
+            })
+            .build();
+
+        cache.insert("a", vec![0; 40]);
+        cache.insert("b", vec![0; 40]);
+        assert_eq!(cache.weighted_size(), 80);
+        // 放入 30 字节需要淘汰最久未使用的 a
+        cache.insert("c", vec![0; 30]);
+        assert_eq!(cache.weighted_size(), 70);
+        // 单个条目超过容量时直接拒绝
+        cache.insert("huge", vec![0; 101]);
+        // 覆盖写入变大，同样可能触发淘汰
+        cache.insert("c", vec![0; 70]);
+        assert_eq!(cache.weighted_size(), 70);
+        assert_eq!(cache.len(), 1);
+
+        cache.set_capacity(50);
+        assert!(cache.is_empty());
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                ("a", 40, RemovalCause::Evicted),
+                ("huge", 101, RemovalCause::Rejected),
+                ("b", 40, RemovalCause::Evicted),
+                ("c", 70, RemovalCause::Evicted),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_borrowed_lookup() {
+        let mut cache: Cache<String, usize> = Cache::new(2);
+        cache.insert("one".to_string(), 1);
+        assert_eq!(cache.get("one"), Some(&1));
+        assert_eq!(cache.remove("one"), Some(1));
+        assert_eq!(cache.get("one"), None);
+    }
+}
@@ -0,0 +1,760 @@
+//! 缓存淘汰策略
+//!
+//! 策略只维护槽位下标的顺序与元数据，键值的存储、权重与过期由 [`Cache`](super::Cache) 负责
+use crate::collections::cache::list::{Ghost, Links, List, NIL};
+
+/// 淘汰策略
+///
+/// 缓存为每个驻留条目分配一个槽位下标，并在条目的生命周期内按下列约定回调：
+/// 写入新键时调用 `on_insert`，命中时调用 `on_access`，
+/// 条目被显式删除或过期时调用 `on_remove`；超出容量时反复调用 `evict`，
+/// 由策略选出并摘除一个槽位。被 `evict` 返回或 `on_remove` 的槽位之后可能被复用
+pub trait EvictionPolicy {
+    /// 新条目写入 slot，hash 为其键的哈希
+    fn on_insert(&mut self, slot: usize, hash: u64);
+
+    /// slot 处的条目被命中
+    fn on_access(&mut self, slot: usize);
+
+    /// 查找未命中，只有需要估计访问频率的策略关心
+    fn on_miss(&mut self, _hash: u64) {}
+
+    /// slot 处的条目被显式删除或过期
+    fn on_remove(&mut self, slot: usize);
+
+    /// 选出并摘除一个淘汰对象，没有驻留条目时返回 None
+    fn evict(&mut self) -> Option<usize>;
+
+    /// 丢弃所有状态
+    fn clear(&mut self);
+}
+
+fn ensure<T: Clone>(v: &mut Vec<T>, slot: usize, fill: T) {
+    if slot >= v.len() {
+        v.resize(slot + 1, fill);
+    }
+}
+
+/// 最近最少使用
+#[derive(Default)]
+pub struct LruPolicy {
+    links: Links,
+    list: List,
+}
+
+impl LruPolicy {
+    pub fn new() -> Self {
+        Self {
+            links: Links::default(),
+            list: List::EMPTY,
+        }
+    }
+}
+
+impl EvictionPolicy for LruPolicy {
+    fn on_insert(&mut self, slot: usize, _hash: u64) {
+        self.links.push_front(&mut self.list, slot);
+    }
+
+    fn on_access(&mut self, slot: usize) {
+        self.links.touch(&mut self.list, slot);
+    }
+
+    fn on_remove(&mut self, slot: usize) {
+        self.links.unlink(&mut self.list, slot);
+    }
+
+    fn evict(&mut self) -> Option<usize> {
+        self.links.pop_back(&mut self.list)
+    }
+
+    fn clear(&mut self) {
+        self.links.clear();
+        self.list = List::EMPTY;
+    }
+}
+
+/// 最不经常使用，频率相同时淘汰最久未使用的
+///
+/// ## Notes
+/// 按频率分桶，桶之间按频率升序串成链表，所有操作均为 O(1)
+pub struct LfuPolicy {
+    links: Links,
+    buckets: Vec<Bucket>,
+    free_buckets: Vec<usize>,
+    /// 每个槽位所在的桶
+    bucket_of: Vec<usize>,
+    /// 频率最低的桶
+    head: usize,
+}
+
+struct Bucket {
+    freq: u64,
+    list: List,
+    prev: usize,
+    next: usize,
+}
+
+impl LfuPolicy {
+    pub fn new() -> Self {
+        Self {
+            links: Links::default(),
+            buckets: Vec::new(),
+            free_buckets: Vec::new(),
+            bucket_of: Vec::new(),
+            head: NIL,
+        }
+    }
+
+    /// 在 prev 之后新建一个频率为 freq 的桶，prev 为 NIL 时放在最前
+    fn new_bucket(&mut self, freq: u64, prev: usize) -> usize {
+        let next = if prev == NIL {
+            self.head
+        } else {
+            self.buckets[prev].next
+        };
+        let bucket = Bucket {
+            freq,
+            list: List::EMPTY,
+            prev,
+            next,
+        };
+        let b = match self.free_buckets.pop() {
+            Some(b) => {
+                self.buckets[b] = bucket;
+                b
+            }
+            None => {
+                self.buckets.push(bucket);
+                self.buckets.len() - 1
+            }
+        };
+        if prev == NIL {
+            self.head = b;
+        } else {
+            self.buckets[prev].next = b;
+        }
+        if next != NIL {
+            self.buckets[next].prev = b;
+        }
+        b
+    }
+
+    /// 从桶中摘下 slot，桶空时回收
+    fn detach(&mut self, slot: usize) {
+        let b = self.bucket_of[slot];
+        self.links.unlink(&mut self.buckets[b].list, slot);
+        if self.buckets[b].list.len == 0 {
+            let Bucket { prev, next, .. } = self.buckets[b];
+            if prev == NIL {
+                self.head = next;
+            } else {
+                self.buckets[prev].next = next;
+            }
+            if next != NIL {
+                self.buckets[next].prev = prev;
+            }
+            self.free_buckets.push(b);
+        }
+    }
+
+    /// slot 的访问频率
+    pub fn frequency(&self, slot: usize) -> u64 {
+        self.buckets[self.bucket_of[slot]].freq
+    }
+}
+
+impl Default for LfuPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EvictionPolicy for LfuPolicy {
+    fn on_insert(&mut self, slot: usize, _hash: u64) {
+        let b = if self.head != NIL && self.buckets[self.head].freq == 1 {
+            self.head
+        } else {
+            self.new_bucket(1, NIL)
+        };
+        ensure(&mut self.bucket_of, slot, NIL);
+        self.bucket_of[slot] = b;
+        self.links.push_front(&mut self.buckets[b].list, slot);
+    }
+
+    fn on_access(&mut self, slot: usize) {
+        let b = self.bucket_of[slot];
+        let freq = self.buckets[b].freq + 1;
+        let next = self.buckets[b].next;
+        let nb = if next != NIL && self.buckets[next].freq == freq {
+            next
+        } else {
+            self.new_bucket(freq, b)
+        };
+        // 先在旧桶之后建好新桶再摘旧桶，旧桶被回收时新桶会接到它的前驱之后
+        self.detach(slot);
+        self.bucket_of[slot] = nb;
+        self.links.push_front(&mut self.buckets[nb].list, slot);
+    }
+
+    fn on_remove(&mut self, slot: usize) {
+        self.detach(slot);
+    }
+
+    fn evict(&mut self) -> Option<usize> {
+        if self.head == NIL {
+            return None;
+        }
+        let slot = self.buckets[self.head].list.back()?;
+        self.detach(slot);
+        Some(slot)
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// 自适应替换缓存（ARC）
+///
+/// 驻留条目分为只访问过一次的 T1 与访问过多次的 T2，
+/// 两者各自带一个记录最近淘汰键的幽灵队列 B1/B2。
+/// 命中 B1 说明 T1 过小，命中 B2 说明 T2 过小，据此调整 T1 的目标大小
+#[derive(Default)]
+pub struct ArcPolicy {
+    links: Links,
+    t1: List,
+    t2: List,
+    b1: Ghost,
+    b2: Ghost,
+    in_t2: Vec<bool>,
+    hashes: Vec<u64>,
+    /// T1 的目标大小
+    target: usize,
+    /// 驻留条目数的历史最大值，作为幽灵队列的容量
+    high_water: usize,
+}
+
+impl ArcPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// T1 的当前目标大小
+    pub fn target(&self) -> usize {
+        self.target
+    }
+}
+
+impl EvictionPolicy for ArcPolicy {
+    fn on_insert(&mut self, slot: usize, hash: u64) {
+        ensure(&mut self.in_t2, slot, false);
+        ensure(&mut self.hashes, slot, 0);
+        self.hashes[slot] = hash;
+        let resident = self.t1.len + self.t2.len + 1;
+        self.high_water = self.high_water.max(resident);
+
+        let (b1, b2) = (self.b1.len().max(1), self.b2.len().max(1));
+        let ghost_hit = if self.b1.remove(hash) {
+            self.target = (self.target + (b2 / b1).max(1)).min(self.high_water);
+            true
+        } else if self.b2.remove(hash) {
+            self.target = self.target.saturating_sub((b1 / b2).max(1));
+            true
+        } else {
+            false
+        };
+        self.in_t2[slot] = ghost_hit;
+        let list = if ghost_hit {
+            &mut self.t2
+        } else {
+            &mut self.t1
+        };
+        self.links.push_front(list, slot);
+    }
+
+    fn on_access(&mut self, slot: usize) {
+        if self.in_t2[slot] {
+            self.links.touch(&mut self.t2, slot);
+        } else {
+            self.links.unlink(&mut self.t1, slot);
+            self.links.push_front(&mut self.t2, slot);
+            self.in_t2[slot] = true;
+        }
+    }
+
+    fn on_remove(&mut self, slot: usize) {
+        let list = if self.in_t2[slot] {
+            &mut self.t2
+        } else {
+            &mut self.t1
+        };
+        self.links.unlink(list, slot);
+    }
+
+    fn evict(&mut self) -> Option<usize> {
+        let cap = self.high_water;
+        if self.t1.len > 0 && (self.t1.len > self.target || self.t2.len == 0) {
+            let slot = self.links.pop_back(&mut self.t1)?;
+            self.b1.insert(self.hashes[slot], cap);
+            Some(slot)
+        } else {
+            let slot = self.links.pop_back(&mut self.t2)?;
+            self.b2.insert(self.hashes[slot], cap);
+            Some(slot)
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// S3-FIFO
+///
+/// 新键进入小队列 S（约占 10%），在 S 中再次被访问过的才晋升到主队列 M，
+/// 其余被快速淘汰并记入幽灵队列（容量与驻留条目数相同）；幽灵命中的键直接进入 M。
+/// M 是带最多 3 次重插机会的 FIFO（CLOCK），命中只增加计数、不移动节点
+#[derive(Default)]
+pub struct S3FifoPolicy {
+    links: Links,
+    small: List,
+    main: List,
+    ghost: Ghost,
+    in_main: Vec<bool>,
+    freq: Vec<u8>,
+    hashes: Vec<u64>,
+}
+
+impl S3FifoPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EvictionPolicy for S3FifoPolicy {
+    fn on_insert(&mut self, slot: usize, hash: u64) {
+        ensure(&mut self.in_main, slot, false);
+        ensure(&mut self.freq, slot, 0);
+        ensure(&mut self.hashes, slot, 0);
+        self.hashes[slot] = hash;
+        self.freq[slot] = 0;
+        let to_main = self.ghost.remove(hash);
+        self.in_main[slot] = to_main;
+        let list = if to_main {
+            &mut self.main
+        } else {
+            &mut self.small
+        };
+        self.links.push_front(list, slot);
+    }
+
+    fn on_access(&mut self, slot: usize) {
+        self.freq[slot] = (self.freq[slot] + 1).min(3);
+    }
+
+    fn on_remove(&mut self, slot: usize) {
+        let list = if self.in_main[slot] {
+            &mut self.main
+        } else {
+            &mut self.small
+        };
+        self.links.unlink(list, slot);
+    }
+
+    fn evict(&mut self) -> Option<usize> {
+        loop {
+            let resident = self.small.len + self.main.len;
+            if resident == 0 {
+                return None;
+            }
+            if self.small.len > 0 && (self.small.len * 10 >= resident || self.main.len == 0) {
+                let slot = self.links.pop_back(&mut self.small)?;
+                if self.freq[slot] > 0 {
+                    self.freq[slot] = 0;
+                    self.in_main[slot] = true;
+                    self.links.push_front(&mut self.main, slot);
+                } else {
+                    self.ghost.insert(self.hashes[slot], resident);
+                    return Some(slot);
+                }
+            } else {
+                let slot = self.links.pop_back(&mut self.main)?;
+                if self.freq[slot] > 0 {
+                    self.freq[slot] -= 1;
+                    self.links.push_front(&mut self.main, slot);
+                } else {
+                    return Some(slot);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// W-TinyLFU
+///
+/// 新键先进入约占驻留条目 1% 的 LRU 窗口，溢出的条目进入主区（分段 LRU）的候选队列。
+/// 淘汰时，最近从窗口溢出的候选者与试用段尾部的淘汰对象比较 Count-Min 频率估计，
+/// 频率更高者留下（胜出的候选者转入试用段头部），因此一次性扫描不会冲掉热点。
+/// 候选者或试用段中再次命中的条目晋升到保护段（约占主区 80%），保护段溢出的条目降级到试用段头部
+pub struct WTinyLfuPolicy {
+    links: Links,
+    window: List,
+    /// 从窗口溢出、尚未经过准入比较的条目
+    candidates: List,
+    probation: List,
+    protected: List,
+    region: Vec<Region>,
+    hashes: Vec<u64>,
+    sketch: FrequencySketch,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Window,
+    Candidate,
+    Probation,
+    Protected,
+}
+
+impl WTinyLfuPolicy {
+    /// `expected_entries` 为预计驻留条目数，用于确定频率草图的宽度
+    pub fn new(expected_entries: usize) -> Self {
+        Self {
+            links: Links::default(),
+            window: List::EMPTY,
+            candidates: List::EMPTY,
+            probation: List::EMPTY,
+            protected: List::EMPTY,
+            region: Vec::new(),
+            hashes: Vec::new(),
+            sketch: FrequencySketch::new(expected_entries),
+        }
+    }
+
+    /// 键哈希的频率估计
+    pub fn frequency(&self, hash: u64) -> u8 {
+        self.sketch.estimate(hash)
+    }
+
+    fn list(&mut self, region: Region) -> &mut List {
+        match region {
+            Region::Window => &mut self.window,
+            Region::Candidate => &mut self.candidates,
+            Region::Probation => &mut self.probation,
+            Region::Protected => &mut self.protected,
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let region = self.region[slot];
+        let mut list = *self.list(region);
+        self.links.unlink(&mut list, slot);
+        *self.list(region) = list;
+    }
+
+    /// 主区（候选队列、试用段与保护段）的条目数
+    fn main_len(&self) -> usize {
+        self.candidates.len + self.probation.len + self.protected.len
+    }
+
+    fn push(&mut self, region: Region, slot: usize) {
+        let mut list = *self.list(region);
+        self.links.push_front(&mut list, slot);
+        *self.list(region) = list;
+        self.region[slot] = region;
+    }
+}
+
+impl Default for WTinyLfuPolicy {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl EvictionPolicy for WTinyLfuPolicy {
+    fn on_insert(&mut self, slot: usize, hash: u64) {
+        ensure(&mut self.region, slot, Region::Window);
+        ensure(&mut self.hashes, slot, 0);
+        self.hashes[slot] = hash;
+        self.sketch.increment(hash);
+        self.push(Region::Window, slot);
+        let resident = self.window.len + self.main_len();
+        if self.window.len > (resident / 100).max(1)
+            && let Some(overflow) = self.window.back()
+        {
+            self.unlink(overflow);
+            self.push(Region::Candidate, overflow);
+        }
+    }
+
+    fn on_access(&mut self, slot: usize) {
+        self.sketch.increment(self.hashes[slot]);
+        match self.region[slot] {
+            Region::Window => self.links.touch(&mut self.window, slot),
+            Region::Protected => self.links.touch(&mut self.protected, slot),
+            Region::Candidate | Region::Probation => {
+                self.unlink(slot);
+                self.push(Region::Protected, slot);
+                let protected_cap = (self.main_len() * 4 / 5).max(1);
+                if self.protected.len > protected_cap
+                    && let Some(demoted) = self.links.pop_back(&mut self.protected)
+                {
+                    self.push(Region::Probation, demoted);
+                }
+            }
+        }
+    }
+
+    fn on_miss(&mut self, hash: u64) {
+        self.sketch.increment(hash);
+    }
+
+    fn on_remove(&mut self, slot: usize) {
+        self.unlink(slot);
+    }
+
+    fn evict(&mut self) -> Option<usize> {
+        let freq = |slot: usize| self.sketch.estimate(self.hashes[slot]);
+        // 试用段为空时，最早的候选者充当淘汰对象
+        let tail = self.probation.back().or(self.candidates.back());
+        let victim = match (self.candidates.front(), tail) {
+            (Some(candidate), Some(victim)) if candidate != victim => {
+                if freq(candidate) > freq(victim) {
+                    // 候选者胜出，准入主区
+                    self.unlink(candidate);
+                    self.push(Region::Probation, candidate);
+                    victim
+                } else {
+                    candidate
+                }
+            }
+            (_, Some(victim)) => victim,
+            // 主区只剩保护段时，在窗口尾部与保护段尾部之间同样按频率取舍
+            _ => match (self.window.back(), self.protected.back()) {
+                (Some(candidate), Some(victim)) => {
+                    if freq(candidate) > freq(victim) {
+                        victim
+                    } else {
+                        candidate
+                    }
+                }
+                (candidate, victim) => candidate.or(victim)?,
+            },
+        };
+        self.unlink(victim);
+        Some(victim)
+    }
+
+    fn clear(&mut self) {
+        self.links.clear();
+        self.window = List::EMPTY;
+        self.candidates = List::EMPTY;
+        self.probation = List::EMPTY;
+        self.protected = List::EMPTY;
+        self.region.clear();
+        self.hashes.clear();
+        self.sketch.clear();
+    }
+}
+
+/// 4 行 Count-Min 草图，计数器上限 15
+///
+/// 每行约为预计条目数的 8 倍宽以减少碰撞；累计增加次数达到预计条目数的 10 倍时
+/// 所有计数减半，使频率随时间衰减
+struct FrequencySketch {
+    table: Vec<u8>,
+    width: usize,
+    additions: usize,
+    sample_size: usize,
+}
+
+impl FrequencySketch {
+    const SEEDS: [u64; 4] = [
+        0x9E37_79B9_7F4A_7C15,
+        0xC2B2_AE3D_27D4_EB4F,
+        0x1656_67B1_9E37_79F9,
+        0x85EB_CA77_C2B2_AE63,
+    ];
+
+    fn new(expected_entries: usize) -> Self {
+        let expected = expected_entries.max(16);
+        let width = (8 * expected).next_power_of_two();
+        Self {
+            table: vec![0; 4 * width],
+            width,
+            additions: 0,
+            sample_size: 10 * expected,
+        }
+    }
+
+    fn index(&self, hash: u64, row: usize) -> usize {
+        let h = (hash ^ Self::SEEDS[row]).wrapping_mul(Self::SEEDS[(row + 1) % 4]);
+        row * self.width + ((h >> 32) as usize & (self.width - 1))
+    }
+
+    fn increment(&mut self, hash: u64) {
+        for row in 0..4 {
+            let i = self.index(hash, row);
+            self.table[i] = (self.table[i] + 1).min(15);
+        }
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            self.table.iter_mut().for_each(|c| *c /= 2);
+            self.additions /= 2;
+        }
+    }
+
+    fn estimate(&self, hash: u64) -> u8 {
+        (0..4)
+            .map(|row| self.table[self.index(hash, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn clear(&mut self) {
+        self.table.fill(0);
+        self.additions = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(p: &mut impl EvictionPolicy) -> Vec<usize> {
+        std::iter::from_fn(|| p.evict()).collect()
+    }
+
+    #[test]
+    fn test_lru_order() {
+        let mut p = LruPolicy::new();
+        for s in 0..4 {
+            p.on_insert(s, s as u64);
+        }
+        p.on_access(0);
+        p.on_remove(2);
+        assert_eq!(drain(&mut p), [1, 3, 0]);
+    }
+
+    #[test]
+    fn test_lfu_frequency_buckets() {
+        let mut p = LfuPolicy::new();
+        for s in 0..4 {
+            p.on_insert(s, s as u64);
+        }
+        // 0: 3 次，1: 2 次，2、3: 1 次（2 更旧）
+        p.on_access(0);
+        p.on_access(0);
+        p.on_access(1);
+        assert_eq!((p.frequency(0), p.frequency(1), p.frequency(3)), (3, 2, 1));
+        assert_eq!(p.evict(), Some(2));
+
+        p.on_remove(1);
+        p.on_insert(1, 1);
+        p.on_access(1);
+        p.on_access(1);
+        p.on_access(1);
+        assert_eq!(drain(&mut p), [3, 0, 1]);
+        assert_eq!(p.evict(), None);
+    }
+
+    #[test]
+    fn test_arc_adapts_to_ghost_hits() {
+        let mut p = ArcPolicy::new();
+        for s in 0..4 {
+            p.on_insert(s, 100 + s as u64);
+        }
+        p.on_access(3);
+        // T1 = [2, 1, 0]，T2 = [3]
+        assert_eq!(p.evict(), Some(0));
+        assert_eq!(p.target(), 0);
+        // 刚被淘汰的键再次写入：命中 B1，T1 目标增大，新条目直接进入 T2
+        p.on_insert(0, 100);
+        assert_eq!(p.target(), 1);
+        assert_eq!(drain(&mut p), [1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_s3fifo_quick_demotion() {
+        let mut p = S3FifoPolicy::new();
+        for s in 0..10 {
+            p.on_insert(s, s as u64);
+        }
+        p.on_access(0);
+        p.on_access(5);
+        // 只访问过一次的键直接从小队列淘汰，0 与 5 晋升到主队列
+        assert_eq!(p.evict(), Some(1));
+        // 幽灵命中的键直接进入主队列
+        p.on_insert(1, 1);
+        assert!(p.in_main[1]);
+        let order = drain(&mut p);
+        assert_eq!(order, [2, 3, 4, 6, 7, 8, 9, 0, 1, 5]);
+    }
+
+    #[test]
+    fn test_wtinylfu_rejects_cold_candidates() {
+        let mut p = WTinyLfuPolicy::new(64);
+        for s in 0..4 {
+            p.on_insert(s, s as u64);
+            for _ in 0..5 {
+                p.on_access(s);
+            }
+        }
+        for s in 10..13 {
+            p.on_insert(s, s as u64);
+        }
+        // 候选队列为 [11, 10, 3, 2, 1, 0]：冷门候选者频率不高于热点，被自身淘汰
+        assert_eq!(p.evict(), Some(11));
+        assert_eq!(p.evict(), Some(10));
+        assert!(p.frequency(0) >= 5);
+
+        // 冷键被频繁访问后反超尾部
+        p.on_insert(20, 20);
+        p.on_insert(21, 21);
+        for _ in 0..10 {
+            p.on_miss(20);
+        }
+        assert_eq!(p.candidates.front(), Some(20));
+        assert_eq!(p.evict(), Some(0));
+        assert_eq!(p.probation.front(), Some(20));
+    }
+
+    #[test]
+    fn test_wtinylfu_demotion_does_not_replace_candidate() {
+        let mut p = WTinyLfuPolicy::new(64);
+        let touch = |p: &mut WTinyLfuPolicy, s: usize, times: usize| {
+            for _ in 0..times {
+                p.on_access(s);
+            }
+        };
+        // 0 为高频条目，1..=9 为中频条目，各自在窗口中被访问
+        p.on_insert(0, 0);
+        touch(&mut p, 0, 12);
+        for s in 1..=9 {
+            p.on_insert(s, s as u64);
+            touch(&mut p, s, 2);
+        }
+        p.on_insert(10, 10);
+        // 1..=8 从候选队列晋升到保护段，1 位于保护段尾部
+        for s in 1..=8 {
+            p.on_access(s);
+        }
+        assert_eq!(p.protected.back(), Some(1));
+
+        // 10 从窗口溢出成为候选者，随后 9 晋升使 1 降级到试用段头部
+        p.on_insert(11, 11);
+        assert_eq!(p.candidates.front(), Some(10));
+        p.on_access(9);
+        assert_eq!(p.probation.front(), Some(1));
+
+        // 准入比较的仍是冷门候选者 10，而不是刚降级的 1
+        assert_eq!(p.evict(), Some(10));
+        assert!(p.region[1] == Region::Probation);
+        assert_eq!(p.candidates.front(), Some(0));
+    }
+}
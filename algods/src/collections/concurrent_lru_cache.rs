@@ -0,0 +1,810 @@
+//! 线程安全的分段 LRU 缓存
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::{BuildHasher, Hash, RandomState},
+    sync::{
+        Arc, Condvar, Mutex, PoisonError,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+use crossbeam_utils::CachePadded;
+
+/// 淘汰时随机抽样的条目数
+const SAMPLES: usize = 5;
+
+/// 每段桶数的上限
+const MAX_BUCKETS: usize = 1 << 20;
+
+/// 缓存快照统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// 命中数
+    pub hits: usize,
+    /// 未命中数
+    pub misses: usize,
+    /// 新写入的条目数（不含覆盖写入）
+    pub insertions: usize,
+    /// 因容量不足被淘汰的条目数
+    pub evictions: usize,
+    /// `get_or_insert_with` 实际执行加载函数的次数
+    pub loads: usize,
+    /// `get_or_insert_with` 等待其他线程加载同一个键的次数
+    pub load_waits: usize,
+}
+
+impl CacheStats {
+    /// 命中率，没有查询时为 0
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// 单个段的计数器（原子操作），放在 `CachePadded` 中，不同段的命中不会争用同一缓存行
+#[derive(Default)]
+struct ShardStats {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    insertions: AtomicUsize,
+    evictions: AtomicUsize,
+    loads: AtomicUsize,
+    load_waits: AtomicUsize,
+}
+
+impl ShardStats {
+    fn bump(counter: &AtomicUsize) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 累加到 `total`（Relaxed 顺序，各计数器间可能不一致）
+    fn add_to(&self, total: &mut CacheStats) {
+        total.hits += self.hits.load(Ordering::Relaxed);
+        total.misses += self.misses.load(Ordering::Relaxed);
+        total.insertions += self.insertions.load(Ordering::Relaxed);
+        total.evictions += self.evictions.load(Ordering::Relaxed);
+        total.loads += self.loads.load(Ordering::Relaxed);
+        total.load_waits += self.load_waits.load(Ordering::Relaxed);
+    }
+}
+
+/// 桶头或节点的 next 指针
+type Link<K, V> = Atomic<Node<K, V>>;
+
+/// 查找结果：指向节点的链接与节点本身
+type Found<'g, K, V> = (&'g Link<K, V>, Shared<'g, Node<K, V>>);
+
+/// 桶链上的节点，发布后除 `stamp`、`slot`、`next` 外不再修改
+struct Node<K, V> {
+    hash: u64,
+    key: K,
+    val: V,
+    /// 最近一次访问时的段时钟
+    stamp: AtomicU64,
+    /// 在 `Writer::nodes` 中的下标，只在写锁下读写
+    slot: AtomicUsize,
+    next: Link<K, V>,
+}
+
+/// 只在持有段写锁时访问的状态
+struct Writer<K, V> {
+    /// 段内全部节点，供抽样淘汰；删除时与末尾交换
+    nodes: Vec<*const Node<K, V>>,
+    /// 抽样用的 xorshift 状态
+    rng: u64,
+}
+
+// 裸指针只在持有写锁时解引用，节点本身的跨线程访问由 K、V 的 Send + Sync 保证
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for Writer<K, V> {}
+
+struct Shard<K, V, S> {
+    /// 读者无锁遍历的桶数组，按哈希低位索引
+    buckets: Box<[Link<K, V>]>,
+    /// 串行化段内的写入、删除与淘汰
+    writer: Mutex<Writer<K, V>>,
+    /// 段时钟，只在写入时推进，命中只读取
+    clock: AtomicU64,
+    /// 正在加载的键
+    loading: Mutex<HashMap<K, Arc<Load<V>>, S>>,
+    stats: CachePadded<ShardStats>,
+}
+
+enum LoadState<V> {
+    Pending,
+    Done(V),
+    /// 加载函数 panic，等待者需要自行重试
+    Failed,
+}
+
+struct Load<V> {
+    state: Mutex<LoadState<V>>,
+    cvar: Condvar,
+}
+
+/// 线程安全的分段近似 LRU 缓存
+///
+/// 按键的哈希分成若干段，每段是一张链式哈希表。读取（`get`、`peek`、`contains_key`）不加锁，
+/// 只沿桶链做原子读取；写入、删除与淘汰由段内的互斥锁串行化，被替换或移除的节点通过 epoch
+/// 回收延迟到所有可能看到它的读者离开后再释放。
+///
+/// 访问时间戳取自段时钟：时钟只在写入时推进，命中只读取它，且与条目已有的时间戳相同时不再写入，
+/// 因此反复命中同一条目不会产生写争用，代价是两次写入之间的访问视为同一时刻。
+/// 淘汰时从段内随机抽样若干条目，移除其中时间戳最小的一个（段内条目不多于抽样数时即上述粒度下的精确 LRU）。
+///
+/// 读取返回值的克隆，较大的值可以用 `Arc` 包装。各项计数按段存放在独立的缓存行中，`stats` 时求和
+///
+/// ## Notes
+/// - 每段容量为 `ceil(capacity / shards)`，总条目数可能略超过 `capacity`
+/// - 旧节点可能仍被并发读者访问，覆盖写入与 `remove` 返回的是旧值的克隆
+/// - 节点可能在其他线程上延迟释放，因此要求 `K`、`V` 为 `Send + 'static`
+pub struct ConcurrentLruCache<K, V, S = RandomState> {
+    shards: Box<[Shard<K, V, S>]>,
+    hasher: S,
+    shard_capacity: usize,
+}
+
+impl<K, V> ConcurrentLruCache<K, V, RandomState>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// 按可用并行度的 4 倍（取 2 的幂）分段
+    pub fn new(capacity: usize) -> Self {
+        let parallelism = thread::available_parallelism().map_or(4, |n| n.get());
+        Self::with_shards(capacity, (parallelism * 4).next_power_of_two())
+    }
+
+    pub fn with_shards(capacity: usize, shards: usize) -> Self {
+        Self::with_shards_and_hasher(capacity, shards, RandomState::new())
+    }
+}
+
+impl<K, V, S> ConcurrentLruCache<K, V, S>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+    S: BuildHasher + Clone,
+{
+    /// 段数不超过容量，且至少为 1；每段的桶数为段容量向上取 2 的幂（不超过 2^20）
+    pub fn with_shards_and_hasher(capacity: usize, shards: usize, hasher: S) -> Self {
+        let n = shards.clamp(1, capacity.max(1));
+        let shard_capacity = capacity.div_ceil(n);
+        let buckets = shard_capacity.clamp(1, MAX_BUCKETS).next_power_of_two();
+        let shards = (0..n)
+            .map(|i| Shard {
+                buckets: (0..buckets).map(|_| Atomic::null()).collect(),
+                writer: Mutex::new(Writer {
+                    nodes: Vec::new(),
+                    rng: 0x9E37_79B9_7F4A_7C15 ^ (i as u64 + 1),
+                }),
+                clock: AtomicU64::new(0),
+                loading: Mutex::new(HashMap::with_hasher(hasher.clone())),
+                stats: CachePadded::new(ShardStats::default()),
+            })
+            .collect();
+        Self {
+            shards,
+            hasher,
+            shard_capacity,
+        }
+    }
+
+    fn shard(&self, hash: u64) -> &Shard<K, V, S> {
+        // 段内按低位分桶，分段取高 32 位避免相关
+        &self.shards[(hash >> 32) as usize % self.shards.len()]
+    }
+
+    /// 访问 key 并刷新其最近访问时间，不加锁
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let shard = self.shard(hash);
+        let found = shard.lookup(hash, key, true);
+        ShardStats::bump(if found.is_some() {
+            &shard.stats.hits
+        } else {
+            &shard.stats.misses
+        });
+        found
+    }
+
+    /// 只读，不刷新访问时间，也不计入命中统计
+    pub fn peek<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        self.shard(hash).lookup(hash, key, false)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let guard = epoch::pin();
+        self.shard(hash).find(hash, key, &guard).is_some()
+    }
+
+    /// 写入键值对，key 已存在时返回旧值；段满时淘汰抽样中最久未访问的条目
+    pub fn insert(&self, key: K, val: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        let shard = self.shard(hash);
+        let guard = epoch::pin();
+        let mut writer = shard.writer.lock().unwrap();
+        // 写入的条目取推进前的时间，之后的命中都晚于它
+        let stamp = shard.clock.fetch_add(1, Ordering::Relaxed);
+
+        if let Some((link, old)) = shard.find(hash, &key, &guard) {
+            // 节点发布后不可变，覆盖写入换成新节点
+            let old_node = unsafe { old.deref() };
+            let slot = old_node.slot.load(Ordering::Relaxed);
+            let node = Owned::new(Node {
+                hash,
+                key,
+                val,
+                stamp: AtomicU64::new(stamp),
+                slot: AtomicUsize::new(slot),
+                next: Atomic::from(old_node.next.load(Ordering::Relaxed, &guard)),
+            })
+            .into_shared(&guard);
+            link.store(node, Ordering::Release);
+            writer.nodes[slot] = node.as_raw();
+            let prev = old_node.val.clone();
+            unsafe { guard.defer_destroy(old) };
+            return Some(prev);
+        }
+
+        if self.shard_capacity == 0 {
+            return None;
+        }
+        if writer.nodes.len() >= self.shard_capacity {
+            shard.evict_sampled(&mut writer, &guard);
+            ShardStats::bump(&shard.stats.evictions);
+        }
+        let bucket = shard.bucket(hash);
+        let node = Owned::new(Node {
+            hash,
+            key,
+            val,
+            stamp: AtomicU64::new(stamp),
+            slot: AtomicUsize::new(writer.nodes.len()),
+            next: Atomic::from(bucket.load(Ordering::Relaxed, &guard)),
+        })
+        .into_shared(&guard);
+        bucket.store(node, Ordering::Release);
+        writer.nodes.push(node.as_raw());
+        ShardStats::bump(&shard.stats.insertions);
+        None
+    }
+
+    /// 返回 key 对应的值，不存在时用 `f` 加载并写入
+    ///
+    /// 多个线程同时加载同一个键时只有一个线程执行 `f`，其余线程阻塞等待其结果；
+    /// 若 `f` panic，等待者中的一个会接替加载
+    pub fn get_or_insert_with<F>(&self, key: K, f: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(v) = self.get(&key) {
+            return v;
+        }
+        let hash = self.hasher.hash_one(&key);
+        let shard = self.shard(hash);
+        loop {
+            let load = {
+                let mut loading = shard.loading.lock().unwrap();
+                // 拿到加载锁后再查一次，避免与刚完成的加载重复
+                if let Some(v) = shard.lookup(hash, &key, true) {
+                    return v;
+                }
+                match loading.get(&key) {
+                    Some(load) => Some(load.clone()),
+                    None => {
+                        loading.insert(
+                            key.clone(),
+                            Arc::new(Load {
+                                state: Mutex::new(LoadState::Pending),
+                                cvar: Condvar::new(),
+                            }),
+                        );
+                        None
+                    }
+                }
+            };
+
+            match load {
+                Some(load) => {
+                    ShardStats::bump(&shard.stats.load_waits);
+                    let mut state = load.state.lock().unwrap();
+                    while let LoadState::Pending = *state {
+                        state = load.cvar.wait(state).unwrap();
+                    }
+                    if let LoadState::Done(v) = &*state {
+                        return v.clone();
+                    }
+                    // 加载者 panic，重新竞争加载权
+                }
+                None => {
+                    let mut guard = LoadGuard {
+                        shard,
+                        key: &key,
+                        result: None,
+                    };
+                    ShardStats::bump(&shard.stats.loads);
+                    let val = f();
+                    self.insert(key.clone(), val.clone());
+                    guard.result = Some(val.clone());
+                    return val;
+                }
+            }
+        }
+    }
+
+    /// 删除 key，返回其值
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let shard = self.shard(hash);
+        let guard = epoch::pin();
+        let mut writer = shard.writer.lock().unwrap();
+        let (link, node) = shard.find(hash, key, &guard)?;
+        let val = unsafe { node.deref() }.val.clone();
+        unsafe { shard.unlink(&mut writer, link, node, &guard) };
+        Some(val)
+    }
+
+    /// 各段条目数之和（逐段加写锁，并发修改时只是近似值）
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|s| s.writer.lock().unwrap().nodes.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 总容量上限（各段容量之和）
+    pub fn capacity(&self) -> usize {
+        self.shard_capacity * self.shards.len()
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn clear(&self) {
+        let guard = epoch::pin();
+        for shard in self.shards.iter() {
+            let mut writer = shard.writer.lock().unwrap();
+            for bucket in shard.buckets.iter() {
+                bucket.store(Shared::null(), Ordering::Release);
+            }
+            for node in writer.nodes.drain(..) {
+                unsafe { guard.defer_destroy(Shared::from(node)) };
+            }
+        }
+    }
+
+    /// 统计快照，各段计数之和
+    pub fn stats(&self) -> CacheStats {
+        let mut total = CacheStats::default();
+        for shard in self.shards.iter() {
+            shard.stats.add_to(&mut total);
+        }
+        total
+    }
+}
+
+impl<K, V, S> Shard<K, V, S>
+where
+    K: Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn bucket(&self, hash: u64) -> &Link<K, V> {
+        &self.buckets[hash as usize & (self.buckets.len() - 1)]
+    }
+
+    /// 沿桶链查找 key，返回指向它的链接与节点本身
+    fn find<'g, Q>(&'g self, hash: u64, key: &Q, guard: &'g Guard) -> Option<Found<'g, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut link = self.bucket(hash);
+        loop {
+            let cur = link.load(Ordering::Acquire, guard);
+            // 节点只通过 defer_destroy 释放，guard 存活期间可以安全访问
+            let node = unsafe { cur.as_ref() }?;
+            if node.hash == hash && node.key.borrow() == key {
+                return Some((link, cur));
+            }
+            link = &node.next;
+        }
+    }
+
+    /// 无锁查找，`touch` 时把条目的时间戳刷新为当前段时钟
+    fn lookup<Q>(&self, hash: u64, key: &Q, touch: bool) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let guard = epoch::pin();
+        let (_, node) = self.find(hash, key, &guard)?;
+        let node = unsafe { node.deref() };
+        if touch {
+            let now = self.clock.load(Ordering::Relaxed);
+            if node.stamp.load(Ordering::Relaxed) != now {
+                node.stamp.store(now, Ordering::Relaxed);
+            }
+        }
+        Some(node.val.clone())
+    }
+
+    /// 抽样淘汰：条目不多于抽样数时遍历全部，否则随机抽取 SAMPLES 个
+    fn evict_sampled(&self, writer: &mut Writer<K, V>, guard: &Guard) {
+        let n = writer.nodes.len();
+        if n == 0 {
+            return;
+        }
+        let mut candidates = [0; SAMPLES];
+        let len = n.min(SAMPLES);
+        for (j, c) in candidates[..len].iter_mut().enumerate() {
+            *c = if n <= SAMPLES {
+                j
+            } else {
+                writer.next_random() as usize % n
+            };
+        }
+        let Some(victim) = candidates[..len]
+            .iter()
+            .map(|&i| writer.nodes[i])
+            .min_by_key(|&node| unsafe { &*node }.stamp.load(Ordering::Relaxed))
+        else {
+            return;
+        };
+
+        // 沿桶链找到指向 victim 的链接
+        let mut link = self.bucket(unsafe { &*victim }.hash);
+        loop {
+            let cur = link.load(Ordering::Relaxed, guard);
+            if cur.as_raw() == victim {
+                unsafe { self.unlink(writer, link, cur, guard) };
+                return;
+            }
+            match unsafe { cur.as_ref() } {
+                Some(node) => link = &node.next,
+                None => return,
+            }
+        }
+    }
+
+    /// 把 `node` 从桶链与 `writer.nodes` 中摘除，并延迟释放
+    ///
+    /// ## Safety
+    /// 必须持有段写锁，且 `link` 当前指向 `node`
+    unsafe fn unlink(
+        &self,
+        writer: &mut Writer<K, V>,
+        link: &Link<K, V>,
+        node: Shared<'_, Node<K, V>>,
+        guard: &Guard,
+    ) {
+        unsafe {
+            let n = node.deref();
+            link.store(n.next.load(Ordering::Relaxed, guard), Ordering::Release);
+            let i = n.slot.load(Ordering::Relaxed);
+            writer.nodes.swap_remove(i);
+            if let Some(&moved) = writer.nodes.get(i) {
+                (*moved).slot.store(i, Ordering::Relaxed);
+            }
+            guard.defer_destroy(node);
+        }
+    }
+}
+
+impl<K, V> Writer<K, V> {
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+}
+
+impl<K, V, S> Drop for Shard<K, V, S> {
+    fn drop(&mut self) {
+        // 独占访问，仍在表中的节点直接释放；已摘除的节点由 epoch 回收
+        let writer = self
+            .writer
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        for &node in &writer.nodes {
+            drop(unsafe { Shared::from(node).into_owned() });
+        }
+    }
+}
+
+/// 加载结束（含 panic）时发布结果并唤醒等待者
+struct LoadGuard<'a, K: Eq + Hash, V, S: BuildHasher> {
+    shard: &'a Shard<K, V, S>,
+    key: &'a K,
+    result: Option<V>,
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> Drop for LoadGuard<'_, K, V, S> {
+    fn drop(&mut self) {
+        let load = self.shard.loading.lock().unwrap().remove(self.key);
+        if let Some(load) = load {
+            *load.state.lock().unwrap() = match self.result.take() {
+                Some(v) => LoadState::Done(v),
+                None => LoadState::Failed,
+            };
+            load.cvar.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use concurrency::thread_pool::ThreadPool;
+    use std::{
+        hash::{BuildHasherDefault, DefaultHasher},
+        panic,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_single_shard_is_exact_lru() {
+        let cache = ConcurrentLruCache::with_shards(4, 1);
+        for k in 1..=4 {
+            cache.insert(k, k * 10);
+        }
+        cache.get(&1);
+        cache.get(&2);
+        cache.get(&3);
+        assert_eq!(cache.insert(5, 50), None);
+        assert!(!cache.contains_key(&4));
+        assert_eq!(cache.peek(&1), Some(10));
+        assert_eq!(cache.insert(1, 11), Some(10));
+        assert_eq!(cache.remove(&1), Some(11));
+        assert_eq!(cache.len(), 3);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (3, 0));
+        assert_eq!((stats.insertions, stats.evictions), (5, 1));
+    }
+
+    #[test]
+    fn test_sharded_capacity_and_hit_rate() {
+        // 固定哈希器，使分段结果可复现
+        let hasher = BuildHasherDefault::<DefaultHasher>::default();
+        let cache = ConcurrentLruCache::with_shards_and_hasher(64, 8, hasher);
+        assert_eq!(cache.shard_count(), 8);
+        assert_eq!(cache.capacity(), 64);
+        for k in 0..1000 {
+            cache.insert(k, k);
+        }
+        assert!(cache.len() <= 64);
+        let stats = cache.stats();
+        assert_eq!(stats.insertions, 1000);
+        assert_eq!(stats.evictions, 1000 - cache.len());
+
+        // 热点反复访问后，在随后的写入中大多能存活
+        for k in 2000..2008 {
+            cache.insert(k, k);
+        }
+        for _ in 0..10 {
+            for k in 2000..2008 {
+                cache.get(&k);
+            }
+        }
+        for k in 3000..3032 {
+            cache.insert(k, k);
+        }
+        let survivors = (2000..2008).filter(|k| cache.contains_key(k)).count();
+        assert!(survivors >= 6, "only {survivors} hot keys survived");
+        assert!(cache.stats().hit_rate() > 0.9);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(
+            ConcurrentLruCache::<i32, i32>::with_shards(3, 8).shard_count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_hits_between_writes_share_a_stamp() {
+        let cache = ConcurrentLruCache::with_shards(3, 1);
+        for k in 1..=3 {
+            cache.insert(k, k);
+        }
+        // 两次写入之间的命中视为同一时刻，但都晚于此前的写入
+        cache.get(&2);
+        cache.get(&1);
+        cache.insert(4, 4);
+        assert!(!cache.contains_key(&3));
+        cache.get(&4);
+        cache.insert(5, 5);
+        assert!(cache.contains_key(&4));
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_stats_are_summed_across_shards() {
+        let cache = ConcurrentLruCache::with_shards(64, 8);
+        for k in 0..64 {
+            cache.insert(k, k);
+        }
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for k in 0..80 {
+                        cache.get(&k);
+                    }
+                });
+            }
+        });
+        let stats = cache.stats();
+        assert_eq!(stats.insertions, 64);
+        // 每段容量 8，哈希不均匀时个别段会有淘汰
+        assert_eq!(stats.evictions, 64 - cache.len());
+        assert_eq!(stats.hits + stats.misses, 320);
+        assert_eq!(stats.hits, 4 * cache.len());
+        assert!(cache.shards.iter().all(|s| {
+            let hits = s.stats.hits.load(Ordering::Relaxed);
+            hits == 4 * s.writer.lock().unwrap().nodes.len()
+        }));
+    }
+
+    #[test]
+    fn test_readers_race_with_overwrites_and_clear() {
+        let cache = ConcurrentLruCache::<usize, String>::with_shards(32, 4);
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for i in 0..20_000 {
+                        let k = i % 48;
+                        if let Some(v) = cache.get(&k) {
+                            assert!(v.starts_with(&format!("{k}:")));
+                        }
+                    }
+                });
+            }
+            s.spawn(|| {
+                for round in 0..200 {
+                    for k in 0..48 {
+                        cache.insert(k, format!("{k}:{round}"));
+                    }
+                    if round % 10 == 0 {
+                        cache.clear();
+                    }
+                }
+            });
+        });
+        assert!(cache.len() <= cache.capacity());
+    }
+
+    #[test]
+    fn test_get_or_insert_with_deduplicates_loads() {
+        let cache = Arc::new(ConcurrentLruCache::new(100));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(8);
+        let handles: Vec<_> = (0..16)
+            .map(|_| {
+                let (cache, calls) = (cache.clone(), calls.clone());
+                pool.spawn(move || {
+                    cache.get_or_insert_with("config".to_string(), || {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(50));
+                        Arc::new(vec![1, 2, 3])
+                    })
+                })
+                .unwrap()
+            })
+            .collect();
+        for h in handles {
+            assert_eq!(*h.join().unwrap(), [1, 2, 3]);
+        }
+        pool.shutdown();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let stats = cache.stats();
+        assert_eq!(stats.loads, 1);
+        assert_eq!(stats.insertions, 1);
+    }
+
+    #[test]
+    fn test_failed_load_is_retried() {
+        let cache = ConcurrentLruCache::with_shards(10, 2);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            cache.get_or_insert_with(1, || panic!("backend unavailable"))
+        }));
+        assert!(result.is_err());
+        assert!(!cache.contains_key(&1));
+
+        // 加载者 panic 时，正在等待的线程接替加载
+        let calls = AtomicUsize::new(0);
+        let values: Vec<_> = thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    s.spawn(|| {
+                        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                            cache.get_or_insert_with(2, || {
+                                let n = calls.fetch_add(1, Ordering::SeqCst);
+                                thread::sleep(Duration::from_millis(20));
+                                if n == 0 {
+                                    panic!("first load fails");
+                                }
+                                n * 100
+                            })
+                        }))
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let loaded: Vec<_> = values.into_iter().filter_map(Result::ok).collect();
+        assert!(!loaded.is_empty());
+        assert!(loaded.iter().all(|&v| v == loaded[0] && v >= 100));
+        assert_eq!(cache.peek(&2), Some(loaded[0]));
+    }
+
+    #[test]
+    fn test_concurrent_mixed_workload() {
+        let cache = ConcurrentLruCache::with_shards(256, 16);
+        thread::scope(|s| {
+            for t in 0..8u64 {
+                let cache = &cache;
+                s.spawn(move || {
+                    let mut x = t + 1;
+                    for _ in 0..5000 {
+                        x ^= x << 13;
+                        x ^= x >> 7;
+                        x ^= x << 17;
+                        let k = x % 512;
+                        match x % 4 {
+                            0 => {
+                                cache.insert(k, k * 2);
+                            }
+                            1 => {
+                                cache.remove(&k);
+                            }
+                            _ => {
+                                if let Some(v) = cache.get(&k) {
+                                    assert_eq!(v, k * 2);
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        assert!(cache.len() <= cache.capacity());
+        for k in 0..512 {
+            if let Some(v) = cache.peek(&k) {
+                assert_eq!(v, k * 2);
+            }
+        }
+    }
+}
@@ -0,0 +1,685 @@
+//! 基于 arena 的双向链表，元素通过带世代号的句柄定位
+//!
+//! ```
+//! use algods::collections::IndexList;
+//!
+//! let mut list = IndexList::new();
+//! let a = list.push_back("a");
+//! let b = list.push_back("b");
+//! list.insert_after(a, "c");
+//! list.move_to_front(b);
+//!
+//! assert_eq!(list.iter().copied().collect::<Vec<_>>(), ["b", "a", "c"]);
+//! assert_eq!(list.remove(a), Some("a"));
+//! // 删除后旧句柄失效，即使槽位被复用也不会误指向新元素
+//! assert_eq!(list.get(a), None);
+//! ```
+use std::{fmt, marker::PhantomData};
+
+const NIL: usize = usize::MAX;
+
+/// 元素句柄
+///
+/// 元素被删除后句柄失效，所有以失效句柄为参数的操作都返回 None / false
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    /// arena 下标
+    index: usize,
+    /// 防止槽位复用后旧句柄误指向新元素
+    generation: u32,
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}, gen={})", self.index, self.generation)
+    }
+}
+
+#[derive(Clone)]
+struct Slot<T> {
+    /// 空闲槽位为 None
+    value: Option<T>,
+    generation: u32,
+    prev: usize,
+    /// 空闲槽位复用 next 串成空闲链表
+    next: usize,
+}
+
+/// 基于 arena 的双向链表
+///
+/// 所有节点存放在同一个 Vec 中，删除后的槽位会被复用。与 [`LinkedList`](crate::collections::LinkedList)
+/// 不同，`push_*` / `insert_*` 返回的 [`Handle`] 可以在 O(1) 内直接定位、删除或移动元素，
+/// 无需借助游标遍历。
+#[derive(Clone)]
+pub struct IndexList<T> {
+    slots: Vec<Slot<T>>,
+    head: usize,
+    tail: usize,
+    /// 空闲链表头
+    free: usize,
+    len: usize,
+}
+
+impl<T> IndexList<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// 预分配 `capacity` 个槽位
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            free: NIL,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 检查句柄是否仍然有效
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slot_of(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let index = self.slot_of(handle)?;
+        self.slots[index].value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let index = self.slot_of(handle)?;
+        self.slots[index].value.as_mut()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.front_handle().and_then(|h| self.get(h))
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.back_handle().and_then(|h| self.get(h))
+    }
+
+    pub fn front_handle(&self) -> Option<Handle> {
+        self.handle_at(self.head)
+    }
+
+    pub fn back_handle(&self) -> Option<Handle> {
+        self.handle_at(self.tail)
+    }
+
+    /// 返回后继元素的句柄，`handle` 为尾元素或已失效时返回 None
+    pub fn next(&self, handle: Handle) -> Option<Handle> {
+        let index = self.slot_of(handle)?;
+        self.handle_at(self.slots[index].next)
+    }
+
+    /// 返回前驱元素的句柄，`handle` 为首元素或已失效时返回 None
+    pub fn prev(&self, handle: Handle) -> Option<Handle> {
+        let index = self.slot_of(handle)?;
+        self.handle_at(self.slots[index].prev)
+    }
+
+    /// 插入到头部
+    ///
+    /// 时间复杂度: O(1)（均摊）
+    pub fn push_front(&mut self, value: T) -> Handle {
+        let index = self.alloc(value);
+        self.link(index, NIL, self.head);
+        self.handle_at(index).unwrap()
+    }
+
+    /// 插入到尾部
+    ///
+    /// 时间复杂度: O(1)（均摊）
+    pub fn push_back(&mut self, value: T) -> Handle {
+        let index = self.alloc(value);
+        self.link(index, self.tail, NIL);
+        self.handle_at(index).unwrap()
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let handle = self.front_handle()?;
+        self.remove(handle)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let handle = self.back_handle()?;
+        self.remove(handle)
+    }
+
+    /// 在 `handle` 之后插入
+    ///
+    /// 时间复杂度: O(1)（均摊）
+    ///
+    /// ## 返回
+    /// 新元素的句柄；`handle` 已失效时返回 None，`value` 被丢弃
+    pub fn insert_after(&mut self, handle: Handle, value: T) -> Option<Handle> {
+        let at = self.slot_of(handle)?;
+        let index = self.alloc(value);
+        self.link(index, at, self.slots[at].next);
+        self.handle_at(index)
+    }
+
+    /// 在 `handle` 之前插入
+    ///
+    /// 时间复杂度: O(1)（均摊）
+    ///
+    /// ## 返回
+    /// 新元素的句柄；`handle` 已失效时返回 None，`value` 被丢弃
+    pub fn insert_before(&mut self, handle: Handle, value: T) -> Option<Handle> {
+        let at = self.slot_of(handle)?;
+        let index = self.alloc(value);
+        self.link(index, self.slots[at].prev, at);
+        self.handle_at(index)
+    }
+
+    /// 删除 `handle` 指向的元素，之后该句柄失效
+    ///
+    /// 时间复杂度: O(1)
+    ///
+    /// ## 返回
+    /// 被删除的值；`handle` 已失效时返回 None
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = self.slot_of(handle)?;
+        self.unlink(index);
+        let slot = &mut self.slots[index];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.next = self.free;
+        self.free = index;
+        slot.value.take()
+    }
+
+    /// 将元素移到头部，返回 `handle` 是否有效
+    ///
+    /// 时间复杂度: O(1)
+    pub fn move_to_front(&mut self, handle: Handle) -> bool {
+        let Some(index) = self.slot_of(handle) else {
+            return false;
+        };
+        if index != self.head {
+            self.unlink(index);
+            self.link(index, NIL, self.head);
+        }
+        true
+    }
+
+    /// 将元素移到尾部，返回 `handle` 是否有效
+    ///
+    /// 时间复杂度: O(1)
+    pub fn move_to_back(&mut self, handle: Handle) -> bool {
+        let Some(index) = self.slot_of(handle) else {
+            return false;
+        };
+        if index != self.tail {
+            self.unlink(index);
+            self.link(index, self.tail, NIL);
+        }
+        true
+    }
+
+    /// 删除所有元素，之前的句柄全部失效
+    ///
+    /// 槽位保留在空闲链表中以便复用，世代号不会回退
+    pub fn clear(&mut self) {
+        self.free = NIL;
+        for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            slot.prev = NIL;
+            slot.next = self.free;
+            self.free = index;
+        }
+        self.head = NIL;
+        self.tail = NIL;
+        self.len = 0;
+    }
+
+    /// 从头到尾的迭代器
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// 从头到尾产出各元素句柄的迭代器
+    pub fn handles(&self) -> Handles<'_, T> {
+        Handles {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    fn slot_of(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.index)?;
+        (slot.generation == handle.generation && slot.value.is_some()).then_some(handle.index)
+    }
+
+    fn handle_at(&self, index: usize) -> Option<Handle> {
+        (index != NIL).then(|| Handle {
+            index,
+            generation: self.slots[index].generation,
+        })
+    }
+
+    /// 取一个空闲槽位（优先复用）存放 `value`，此时尚未挂入链表
+    fn alloc(&mut self, value: T) -> usize {
+        if self.free != NIL {
+            let index = self.free;
+            let slot = &mut self.slots[index];
+            self.free = slot.next;
+            slot.value = Some(value);
+            index
+        } else {
+            self.slots.push(Slot {
+                value: Some(value),
+                generation: 0,
+                prev: NIL,
+                next: NIL,
+            });
+            self.slots.len() - 1
+        }
+    }
+
+    /// 将 `index` 挂到 `prev` 与 `next` 之间（两者必须相邻，NIL 表示链表端点）
+    fn link(&mut self, index: usize, prev: usize, next: usize) {
+        self.slots[index].prev = prev;
+        self.slots[index].next = next;
+        match prev {
+            NIL => self.head = index,
+            p => self.slots[p].next = index,
+        }
+        match next {
+            NIL => self.tail = index,
+            n => self.slots[n].prev = index,
+        }
+        self.len += 1;
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let Slot { prev, next, .. } = self.slots[index];
+        match prev {
+            NIL => self.head = next,
+            p => self.slots[p].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            n => self.slots[n].prev = prev,
+        }
+        self.slots[index].prev = NIL;
+        self.slots[index].next = NIL;
+        self.len -= 1;
+    }
+}
+
+impl<T> Default for IndexList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IndexList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Extend<T> for IndexList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for IndexList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IndexList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut IndexList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct Iter<'a, T> {
+    list: &'a IndexList<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = &self.list.slots[self.front];
+        self.front = slot.next;
+        slot.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = &self.list.slots[self.back];
+        self.back = slot.prev;
+        slot.value.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+}
+
+pub struct IterMut<'a, T> {
+    /// 迭代期间 arena 不会重新分配，每个槽位至多被产出一次
+    slots: *mut Slot<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let slot = &mut *self.slots.add(self.front);
+            self.front = slot.next;
+            slot.value.as_mut()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let slot = &mut *self.slots.add(self.back);
+            self.back = slot.prev;
+            slot.value.as_mut()
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+pub struct Handles<'a, T> {
+    list: &'a IndexList<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<T> Iterator for Handles<'_, T> {
+    type Item = Handle;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let handle = self.list.handle_at(self.front);
+        self.front = self.list.slots[self.front].next;
+        handle
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Handles<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let handle = self.list.handle_at(self.back);
+        self.back = self.list.slots[self.back].prev;
+        handle
+    }
+}
+
+impl<T> ExactSizeIterator for Handles<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<T: Copy>(list: &IndexList<T>) -> Vec<T> {
+        list.iter().copied().collect()
+    }
+
+    /// 正反两个方向的链接一致
+    fn check_links<T: Copy + PartialEq + fmt::Debug>(list: &IndexList<T>) {
+        let mut backward: Vec<_> = list.iter().rev().copied().collect();
+        backward.reverse();
+        assert_eq!(collect(list), backward);
+        assert_eq!(list.iter().len(), list.len());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut list = IndexList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        check_links(&list);
+        assert_eq!(collect(&list), [1, 2, 3]);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.front_handle(), None);
+    }
+
+    #[test]
+    fn test_handles_remove_and_insert() {
+        let mut list = IndexList::new();
+        let a = list.push_back('a');
+        let b = list.push_back('b');
+        let c = list.push_back('c');
+
+        assert_eq!(list.remove(b), Some('b'));
+        assert_eq!(list.remove(b), None);
+        assert!(!list.contains(b));
+        assert_eq!(list.next(a), Some(c));
+        assert_eq!(list.prev(c), Some(a));
+
+        let d = list.insert_after(a, 'd').unwrap();
+        let e = list.insert_before(a, 'e').unwrap();
+        assert_eq!(list.insert_after(b, 'x'), None);
+        check_links(&list);
+        assert_eq!(collect(&list), ['e', 'a', 'd', 'c']);
+        assert_eq!(list.front_handle(), Some(e));
+        assert_eq!(list.prev(e), None);
+        assert_eq!(list.next(d), Some(c));
+
+        *list.get_mut(d).unwrap() = 'D';
+        assert_eq!(list.get(d), Some(&'D'));
+        assert_eq!(list.insert_after(c, 'z').and_then(|z| list.next(z)), None);
+        assert_eq!(list.back(), Some(&'z'));
+    }
+
+    #[test]
+    fn test_stale_handle_after_slot_reuse() {
+        let mut list = IndexList::new();
+        let a = list.push_back(1);
+        list.remove(a);
+        let b = list.push_back(2);
+        // 槽位被复用，但世代号不同
+        assert_ne!(a, b);
+        assert_eq!(list.get(a), None);
+        assert_eq!(list.get(b), Some(&2));
+        assert!(!list.move_to_front(a));
+        assert_eq!(list.remove(a), None);
+        assert_eq!(list.len(), 1);
+        assert_eq!(format!("{:?}", a), "Handle(0, gen=0)");
+        assert_eq!(format!("{:?}", b), "Handle(0, gen=1)");
+
+        list.clear();
+        assert_eq!(list.get(b), None);
+        assert!(list.is_empty());
+        // clear 之后复用槽位的新元素也不能被旧句柄访问
+        let c = list.push_back(3);
+        assert_eq!(list.get(b), None);
+        assert_eq!(list.get(c), Some(&3));
+    }
+
+    #[test]
+    fn test_move_to_front_and_back() {
+        let mut list: IndexList<i32> = IndexList::new();
+        let handles: Vec<_> = (0..5).map(|i| list.push_back(i)).collect();
+
+        assert!(list.move_to_front(handles[3]));
+        assert!(list.move_to_front(handles[3]));
+        assert!(list.move_to_back(handles[0]));
+        assert!(list.move_to_back(handles[0]));
+        check_links(&list);
+        assert_eq!(collect(&list), [3, 1, 2, 4, 0]);
+
+        // 单元素链表
+        let mut single = IndexList::new();
+        let h = single.push_back(7);
+        assert!(single.move_to_front(h));
+        assert!(single.move_to_back(h));
+        assert_eq!(collect(&single), [7]);
+    }
+
+    #[test]
+    fn test_lru_usage() {
+        // 用句柄实现一个简单的 LRU：命中时移到头部，满时淘汰尾部
+        let mut list = IndexList::new();
+        let mut index = std::collections::HashMap::new();
+        let capacity = 3;
+        for key in [1, 2, 3, 1, 4, 2, 5] {
+            if let Some(&h) = index.get(&key) {
+                list.move_to_front(h);
+                continue;
+            }
+            if list.len() == capacity {
+                let evicted = list.pop_back().unwrap();
+                index.remove(&evicted);
+            }
+            index.insert(key, list.push_front(key));
+        }
+        assert_eq!(collect(&list), [5, 2, 4]);
+        assert!(index.values().all(|&h| list.contains(h)));
+    }
+
+    #[test]
+    fn test_iterators_and_traits() {
+        let mut list: IndexList<i32> = (1..=4).collect();
+        for v in list.iter_mut() {
+            *v *= 10;
+        }
+        for v in &mut list {
+            *v += 1;
+        }
+        assert_eq!(collect(&list), [11, 21, 31, 41]);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            [41, 31, 21, 11]
+        );
+        assert_eq!(format!("{:?}", list), "[11, 21, 31, 41]");
+
+        let handles: Vec<_> = list.handles().collect();
+        assert_eq!(handles.len(), 4);
+        assert_eq!(list.get(handles[2]), Some(&31));
+        assert_eq!(list.handles().next_back(), list.back_handle());
+
+        let copy = list.clone();
+        list.remove(handles[0]);
+        assert_eq!(copy.get(handles[0]), Some(&11));
+        assert_eq!(collect(&list), [21, 31, 41]);
+
+        let mut it = list.iter();
+        assert_eq!(it.next(), Some(&21));
+        assert_eq!(it.next_back(), Some(&41));
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.next(), Some(&31));
+        assert_eq!(it.next(), None);
+    }
+}
@@ -49,6 +49,21 @@ pub struct CursorMut<'a, T> {
     index: Option<usize>,
 }
 
+/// 只读游标，位于 ghost（`curr` 为 None）时表示链表首尾之间的空位置
+pub struct Cursor<'a, T> {
+    curr: Link<T>,
+    list: &'a LinkedList<T>,
+    index: Option<usize>,
+}
+
+/// [`LinkedList::extract_if`] 返回的惰性迭代器
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut LinkedList<T>,
+    /// 下一个待检查的节点
+    next: Link<T>,
+    pred: F,
+}
+
 impl<T> LinkedList<T> {
     pub fn new() -> Self {
         Self {
@@ -187,6 +202,225 @@ impl<T> LinkedList<T> {
             index: None,
         }
     }
+
+    /// 位于 ghost 的只读游标
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            curr: None,
+            index: None,
+        }
+    }
+
+    /// 指向首元素的只读游标，链表为空时位于 ghost
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            curr: self.head,
+            index: self.head.map(|_| 0),
+        }
+    }
+
+    /// 指向尾元素的只读游标，链表为空时位于 ghost
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            curr: self.tail,
+            index: self.tail.map(|_| self.len - 1),
+        }
+    }
+
+    /// 指向首元素的可变游标，链表为空时位于 ghost
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            curr: self.head,
+            index: self.head.map(|_| 0),
+            list: self,
+        }
+    }
+
+    /// 指向尾元素的可变游标，链表为空时位于 ghost
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            curr: self.tail,
+            index: self.tail.map(|_| self.len - 1),
+            list: self,
+        }
+    }
+
+    /// 在下标 `at` 处将链表一分为二，返回 `[at, len)` 部分，自身保留 `[0, at)`
+    ///
+    /// 时间复杂度: O(min(at, len - at))
+    ///
+    /// ## Panics
+    /// `at > len` 时 panic
+    pub fn split_off(&mut self, at: usize) -> LinkedList<T> {
+        let len = self.len;
+        assert!(
+            at <= len,
+            "split_off index (is {at}) should be <= len (is {len})"
+        );
+        if at == 0 {
+            return std::mem::take(self);
+        }
+        if at == len {
+            return Self::new();
+        }
+
+        // 从距离较近的一端走到切分点
+        if at - 1 < len - at {
+            let mut cursor = self.cursor_front_mut();
+            for _ in 0..at - 1 {
+                cursor.move_next();
+            }
+            cursor.split_after()
+        } else {
+            let mut cursor = self.cursor_back_mut();
+            for _ in 0..len - 1 - at {
+                cursor.move_prev();
+            }
+            let front = cursor.split_before();
+            std::mem::replace(self, front)
+        }
+    }
+
+    /// 将 `other` 的全部元素移动到自身末尾，`other` 变为空
+    ///
+    /// 时间复杂度: O(1)
+    pub fn append(&mut self, other: &mut Self) {
+        self.cursor_back_mut().splice_after(std::mem::take(other));
+    }
+
+    /// 只保留使 `f` 返回 true 的元素，保持相对顺序
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.extract_if(|elem| !f(elem)).for_each(drop);
+    }
+
+    /// 从前向后惰性地移除并返回使 `pred` 返回 true 的元素
+    ///
+    /// 迭代器提前丢弃时，尚未访问的元素保持不变
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            next: self.head,
+            list: self,
+            pred,
+        }
+    }
+
+    /// 稳定排序
+    ///
+    /// 时间复杂度: O(nlogn)
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// 按 `compare` 稳定排序，只重排节点链接，不移动元素
+    ///
+    /// 时间复杂度: O(nlogn)
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let Some(head) = self.head else {
+            return;
+        };
+        unsafe {
+            // 排序时只维护 next 链接，最后统一修复 prev 与尾指针
+            let head = merge_sort(head, self.len, &mut compare);
+            let mut prev = None;
+            let mut curr = Some(head);
+            while let Some(node) = curr {
+                (*node.as_ptr()).prev = prev;
+                prev = curr;
+                curr = (*node.as_ptr()).next;
+            }
+            self.head = Some(head);
+            self.tail = prev;
+        }
+    }
+
+    /// 将节点从链表中摘下（不释放），并清空其前后链接
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) {
+        unsafe {
+            let prev = (*node.as_ptr()).prev.take();
+            let next = (*node.as_ptr()).next.take();
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = next,
+                None => self.head = next,
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = prev,
+                None => self.tail = prev,
+            }
+            self.len -= 1;
+        }
+    }
+}
+
+/// 对以 `head` 开头的 `len` 个节点做自顶向下的归并排序，返回新的头节点
+///
+/// 只修改 next 链接，结果链表的尾节点 next 为 None
+unsafe fn merge_sort<T, F>(head: NonNull<Node<T>>, len: usize, compare: &mut F) -> NonNull<Node<T>>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    unsafe {
+        if len == 1 {
+            (*head.as_ptr()).next = None;
+            return head;
+        }
+        // 先定位右半部分的头，再排序左半部分（会切断左半部分末尾的链接）
+        let mid = len / 2;
+        let mut right = head;
+        for _ in 0..mid {
+            right = (*right.as_ptr()).next.unwrap();
+        }
+        let left = merge_sort(head, mid, compare);
+        let right = merge_sort(right, len - mid, compare);
+        merge(left, right, compare)
+    }
+}
+
+/// 合并两条有序的 next 链，相等时左侧优先以保证稳定
+unsafe fn merge<T, F>(
+    left: NonNull<Node<T>>,
+    right: NonNull<Node<T>>,
+    compare: &mut F,
+) -> NonNull<Node<T>>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    unsafe {
+        let (mut a, mut b) = (Some(left), Some(right));
+        let mut head: Link<T> = None;
+        let mut tail: Link<T> = None;
+        while let (Some(x), Some(y)) = (a, b) {
+            let node = if compare(&(*x.as_ptr()).elem, &(*y.as_ptr()).elem) != Ordering::Greater {
+                a = (*x.as_ptr()).next;
+                x
+            } else {
+                b = (*y.as_ptr()).next;
+                y
+            };
+            match tail {
+                Some(tail) => (*tail.as_ptr()).next = Some(node),
+                None => head = Some(node),
+            }
+            tail = Some(node);
+        }
+        // 剩余部分已经有序，直接接上
+        (*tail.unwrap().as_ptr()).next = a.or(b);
+        head.unwrap()
+    }
 }
 
 impl<T> Drop for LinkedList<T> {
@@ -462,6 +696,59 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
+    /// 在当前元素之前插入，位于 ghost 时插入到链表末尾
+    pub fn insert_before(&mut self, elem: T) {
+        let mut input = LinkedList::new();
+        input.push_back(elem);
+        self.splice_before(input);
+    }
+
+    /// 在当前元素之后插入，位于 ghost 时插入到链表头部
+    pub fn insert_after(&mut self, elem: T) {
+        let mut input = LinkedList::new();
+        input.push_back(elem);
+        self.splice_after(input);
+    }
+
+    /// 移除并返回当前元素，游标移到下一个元素，位于 ghost 时返回 None
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.unlink_current()?;
+        unsafe { Some(Box::from_raw(node.as_ptr()).elem) }
+    }
+
+    /// 与 [`remove_current`](Self::remove_current) 相同，但复用节点，以单元素链表返回
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<T>> {
+        let node = self.unlink_current()?;
+        Some(LinkedList {
+            head: Some(node),
+            tail: Some(node),
+            len: 1,
+            _marker: PhantomData,
+        })
+    }
+
+    /// 借出一个位于相同位置的只读游标
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            curr: self.curr,
+            list: self.list,
+            index: self.index,
+        }
+    }
+
+    fn unlink_current(&mut self) -> Link<T> {
+        let curr = self.curr?;
+        unsafe {
+            // 下一个元素接替当前下标
+            self.curr = (*curr.as_ptr()).next;
+            if self.curr.is_none() {
+                self.index = None;
+            }
+            self.list.unlink_node(curr);
+        }
+        Some(curr)
+    }
+
     pub fn split_before(&mut self) -> LinkedList<T> {
         // We have this:
         //
@@ -710,6 +997,103 @@ impl<'a, T> CursorMut<'a, T> {
     }
 }
 
+impl<'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(curr) = self.curr {
+            unsafe {
+                self.curr = (*curr.as_ptr()).next;
+                if self.curr.is_some() {
+                    *self.index.as_mut().unwrap() += 1;
+                } else {
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            self.curr = self.list.head;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(curr) = self.curr {
+            unsafe {
+                self.curr = (*curr.as_ptr()).prev;
+                if self.curr.is_some() {
+                    *self.index.as_mut().unwrap() -= 1;
+                } else {
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            self.curr = self.list.tail;
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    /// 返回的引用与链表同寿命，不受游标后续移动影响
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.curr.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = match self.curr {
+                Some(curr) => (*curr.as_ptr()).next,
+                None => self.list.head,
+            };
+            next.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            let prev = match self.curr {
+                Some(curr) => (*curr.as_ptr()).prev,
+                None => self.list.tail,
+            };
+            prev.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            curr: self.curr,
+            list: self.list,
+            index: self.index,
+        }
+    }
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.next {
+            unsafe {
+                self.next = (*node.as_ptr()).next;
+                if (self.pred)(&mut (*node.as_ptr()).elem) {
+                    self.list.unlink_node(node);
+                    return Some(Box::from_raw(node.as_ptr()).elem);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.len))
+    }
+}
+
 unsafe impl<T: Send> Send for LinkedList<T> {}
 unsafe impl<T: Sync> Sync for LinkedList<T> {}
 
@@ -719,6 +1103,9 @@ unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
 unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
 unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
 
+unsafe impl<'a, T: Sync> Send for Cursor<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Cursor<'a, T> {}
+
 #[allow(dead_code)]
 fn assert_properties() {
     fn is_send<T: Send>() {}
@@ -1082,7 +1469,6 @@ mod test {
             &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]
         );
 
-        /* remove_current not impl'd
         let mut cursor = m.cursor_mut();
         cursor.move_next();
         cursor.move_prev();
@@ -1097,8 +1483,10 @@ mod test {
         cursor.move_next();
         assert_eq!(cursor.remove_current(), Some(10));
         check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 8, 2, 3, 4, 5, 6]);
-        */
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[1, 8, 2, 3, 4, 5, 6]
+        );
 
         let mut m: LinkedList<u32> = LinkedList::new();
         m.extend([1, 8, 2, 3, 4, 5, 6]);
@@ -1119,7 +1507,7 @@ mod test {
         cursor.move_next();
         cursor.move_prev();
         let tmp = cursor.split_before();
-        assert_eq!(m.into_iter().collect::<Vec<_>>(), &[]);
+        assert_eq!(m.into_iter().collect::<Vec<_>>(), &[] as &[u32]);
         m = tmp;
         let mut cursor = m.cursor_mut();
         cursor.move_next();
@@ -1141,6 +1529,181 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_cursor_immutable() {
+        let m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_front();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        let first = cursor.current();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(first, Some(&1));
+
+        let mut back = m.cursor_back();
+        assert_eq!(back.current(), Some(&3));
+        assert_eq!(back.index(), Some(2));
+        back.move_next();
+        assert_eq!(back.current(), None);
+        assert_eq!(back.index(), None);
+        assert_eq!(back.peek_next(), Some(&1));
+        assert_eq!(back.peek_prev(), Some(&3));
+
+        let mut ghost = m.cursor();
+        ghost.move_prev();
+        assert_eq!(ghost.current(), Some(&3));
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(empty.cursor_front().current(), None);
+        assert_eq!(empty.cursor_back().index(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert_remove_single() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut cursor = m.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before(10);
+        cursor.insert_after(20);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.as_cursor().peek_next(), Some(&20));
+
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 20));
+        assert_eq!(cursor.index(), Some(2));
+
+        let removed = cursor.remove_current_as_list().unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed.front(), Some(&20));
+        assert_eq!(cursor.current(), Some(&mut 3));
+
+        // 移除尾元素后游标落在 ghost
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_before(4);
+        cursor.insert_after(0);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[0, 1, 10, 4]);
+        assert_eq!(m.len(), 4);
+
+        let mut empty = LinkedList::new();
+        let mut cursor = empty.cursor_back_mut();
+        cursor.insert_after(1);
+        cursor.insert_before(2);
+        assert_eq!(empty.iter().copied().collect::<Vec<_>>(), &[1, 2]);
+    }
+
+    #[test]
+    fn test_split_off() {
+        for at in 0..=7 {
+            let mut m = generate_test();
+            let tail = m.split_off(at);
+            check_links(&m);
+            check_links(&tail);
+            assert_eq!(m.len(), at);
+            assert_eq!(tail.len(), 7 - at);
+            assert_eq!(
+                m.into_iter().chain(tail).collect::<Vec<_>>(),
+                &[0, 1, 2, 3, 4, 5, 6]
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        generate_test().split_off(8);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut a = list_from(&[1, 2]);
+        let mut b = list_from(&[3, 4]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        check_links(&a);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4]);
+
+        let mut empty = LinkedList::new();
+        empty.append(&mut a);
+        assert!(a.is_empty());
+        assert_eq!(empty.len(), 4);
+        empty.append(&mut a);
+        assert_eq!(empty.back(), Some(&4));
+    }
+
+    #[test]
+    fn test_retain_and_extract_if() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        m.retain(|x| x % 3 != 0);
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[1, 2, 4, 5, 7, 8]);
+
+        {
+            let mut it = m.extract_if(|x| {
+                *x *= 10;
+                *x > 30
+            });
+            assert_eq!(it.next(), Some(40));
+        }
+        // 提前丢弃时未访问的元素保持不变
+        check_links(&m);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[10, 20, 5, 7, 8]);
+
+        let evens: Vec<_> = m.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, &[10, 20, 8]);
+        assert_eq!(m.iter().copied().collect::<Vec<_>>(), &[5, 7]);
+        assert_eq!(m.back(), Some(&7));
+    }
+
+    #[test]
+    fn test_sort_by_stable() {
+        let mut m: LinkedList<(u32, usize)> = [3, 1, 2, 3, 1, 2, 0, 3]
+            .into_iter()
+            .enumerate()
+            .map(|(i, k)| (k, i))
+            .collect();
+        m.sort_by(|a, b| a.0.cmp(&b.0));
+        check_links(&m);
+        assert_eq!(
+            m.iter().copied().collect::<Vec<_>>(),
+            &[
+                (0, 6),
+                (1, 1),
+                (1, 4),
+                (2, 2),
+                (2, 5),
+                (3, 0),
+                (3, 3),
+                (3, 7)
+            ]
+        );
+
+        let mut state = 12345u64;
+        let mut v: Vec<u64> = (0..1000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                state >> 40
+            })
+            .collect();
+        let mut m: LinkedList<u64> = v.iter().copied().collect();
+        m.sort();
+        v.sort();
+        check_links(&m);
+        assert_eq!(m.len(), 1000);
+        assert_eq!(m.back(), v.last());
+        assert!(m.iter().eq(v.iter()));
+
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.sort();
+        m.push_back(1);
+        m.sort_by(|a, b| b.cmp(a));
+        assert_eq!(m.front(), Some(&1));
+    }
+
     fn check_links<T: Eq + std::fmt::Debug>(list: &LinkedList<T>) {
         let from_front: Vec<_> = list.iter().collect();
         let from_back: Vec<_> = list.iter().rev().collect();
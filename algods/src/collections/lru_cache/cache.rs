@@ -0,0 +1,958 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt,
+    hash::{BuildHasher, Hash, Hasher, RandomState},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use crate::collections::lru_cache::{
+    entry::{Entry, OccupiedEntry, VacantEntry},
+    iter::{IntoIter, Iter, IterMut},
+};
+
+/// 按条目数淘汰的 LRU 缓存
+///
+/// 需要其他淘汰策略、过期时间或按权重计容量时使用 [`Cache`](crate::collections::Cache)
+pub struct LruCache<K, V, S = RandomState> {
+    pub(crate) map: HashMap<KeyRef<K>, NonNull<Node<K, V>>, S>,
+    /// 最近使用的
+    pub(crate) head: Link<K, V>,
+    /// 最久未使用的
+    pub(crate) tail: Link<K, V>,
+    capacity: usize,
+    _marker: PhantomData<Box<Node<K, V>>>,
+}
+
+pub(crate) type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+pub(crate) struct Node<K, V> {
+    pub(crate) key: K,
+    pub(crate) val: V,
+    pub(crate) prev: Link<K, V>,
+    pub(crate) next: Link<K, V>,
+}
+
+/// 指向节点内键的指针，使哈希表无需另存一份键
+pub(crate) struct KeyRef<K>(*const K);
+
+impl<K: Hash> Hash for KeyRef<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { (*self.0).hash(state) }
+    }
+}
+
+impl<K: PartialEq> PartialEq for KeyRef<K> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { *self.0 == *other.0 }
+    }
+}
+
+impl<K: Eq> Eq for KeyRef<K> {}
+
+/// 借用形式的查找键，用于以 `&Q` 查找 `KeyRef<K>`
+#[repr(transparent)]
+pub(crate) struct KeyWrapper<Q: ?Sized>(Q);
+
+impl<Q: ?Sized> KeyWrapper<Q> {
+    pub(crate) fn from_ref(key: &Q) -> &Self {
+        // repr(transparent) 保证布局相同
+        unsafe { &*(key as *const Q as *const KeyWrapper<Q>) }
+    }
+}
+
+impl<Q: Hash + ?Sized> Hash for KeyWrapper<Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Q: PartialEq + ?Sized> PartialEq for KeyWrapper<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Q: Eq + ?Sized> Eq for KeyWrapper<Q> {}
+
+impl<K, Q> Borrow<KeyWrapper<Q>> for KeyRef<K>
+where
+    K: Borrow<Q>,
+    Q: ?Sized,
+{
+    fn borrow(&self) -> &KeyWrapper<Q> {
+        KeyWrapper::from_ref(unsafe { (*self.0).borrow() })
+    }
+}
+
+// 节点只由缓存独占，与 Box<Node<K, V>> 的线程安全性相同
+unsafe impl<K: Send, V: Send, S: Send> Send for LruCache<K, V, S> {}
+unsafe impl<K: Sync, V: Sync, S: Sync> Sync for LruCache<K, V, S> {}
+
+impl<K, V> LruCache<K, V, RandomState>
+where
+    K: Eq + Hash,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> LruCache<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    pub fn with_hasher(capacity: usize, hasher_builder: S) -> Self {
+        Self {
+            map: HashMap::with_hasher(hasher_builder),
+            head: None,
+            tail: None,
+            capacity,
+            _marker: PhantomData,
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Link<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(KeyWrapper::from_ref(key)).copied()
+    }
+
+    /// 访问并将节点移到头部
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ptr = self.find(key)?;
+
+        unsafe {
+            self.move_to_front(ptr);
+
+            Some(&mut (*ptr.as_ptr()).val)
+        }
+    }
+
+    /// 只读不更新顺序
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ptr = self.find(key)?;
+        unsafe { Some(&(*ptr.as_ptr()).val) }
+    }
+
+    /// 插入键值对
+    /// 若 key 已存在，则更新值并移动到头部
+    /// 若插入后超过容量，则淘汰 LRU 节点并返回被淘汰的值
+    pub fn put(&mut self, key: K, val: V) -> Option<V> {
+        if self.capacity == 0 {
+            return Some(val);
+        }
+
+        // 已存在
+        if let Some(ptr) = self.find(&key) {
+            unsafe {
+                (*ptr.as_ptr()).val = val;
+
+                self.move_to_front(ptr);
+            }
+
+            return None;
+        }
+
+        self.push_front(key, val);
+
+        if self.map.len() > self.capacity {
+            return self.pop_lru().map(|(_, val)| val);
+        }
+
+        None
+    }
+
+    /// 返回 key 对应的值并移到头部，不存在时用 `f` 计算并插入（必要时淘汰 LRU 节点）
+    ///
+    /// ## Panics
+    /// 容量为 0 时 panic
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        self.entry(key).or_insert_with(f)
+    }
+
+    /// 获取 key 的一次性访问视图，key 存在时视为一次访问并移到头部
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        match self.find(&key) {
+            Some(node) => {
+                unsafe { self.move_to_front(node) };
+                Entry::Occupied(OccupiedEntry { cache: self, node })
+            }
+            None => Entry::Vacant(VacantEntry { cache: self, key }),
+        }
+    }
+
+    /// 删除
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, val)| val)
+    }
+
+    /// 删除并返回键值对
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let ptr = self.map.remove(KeyWrapper::from_ref(key))?;
+        unsafe { Some(self.free_node(ptr)) }
+    }
+
+    /// 存在性检查
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// 淘汰并返回最久未使用的键值对
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let tail = self.tail?;
+        unsafe { Some(self.remove_node(tail)) }
+    }
+
+    /// 移出最近使用的键值对
+    pub(crate) fn pop_mru(&mut self) -> Option<(K, V)> {
+        let head = self.head?;
+        unsafe { Some(self.remove_node(head)) }
+    }
+
+    /// 从哈希表与链表中移除节点并释放
+    pub(crate) unsafe fn remove_node(&mut self, node: NonNull<Node<K, V>>) -> (K, V) {
+        unsafe {
+            self.map.remove(&KeyRef(&(*node.as_ptr()).key));
+            self.free_node(node)
+        }
+    }
+
+    /// 修改容量，超出部分按 LRU 顺序淘汰
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.map.len() > capacity {
+            self.pop_lru();
+        }
+    }
+
+    /// 将 key 标记为最近使用，返回 key 是否存在
+    pub fn promote<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(ptr) = self.find(key) else {
+            return false;
+        };
+        unsafe { self.move_to_front(ptr) };
+        true
+    }
+
+    /// 将 key 标记为最久未使用（下一个被淘汰），返回 key 是否存在
+    pub fn demote<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(ptr) = self.find(key) else {
+            return false;
+        };
+        unsafe {
+            self.detach(ptr);
+            self.attach_back(ptr);
+        }
+        true
+    }
+
+    /// 新建节点挂到头部并登记到哈希表，不检查容量
+    pub(crate) fn push_front(&mut self, key: K, val: V) -> NonNull<Node<K, V>> {
+        let node = Box::new(Node {
+            key,
+            val,
+            prev: None,
+            next: None,
+        });
+
+        let ptr = NonNull::from(Box::leak(node));
+
+        unsafe {
+            self.attach_front(ptr);
+            self.map.insert(KeyRef(&(*ptr.as_ptr()).key), ptr);
+        }
+
+        ptr
+    }
+}
+
+impl<K, V, S> LruCache<K, V, S> {
+    /// 清空
+    pub fn clear(&mut self) {
+        // 先清空哈希表，其中的 KeyRef 指向即将释放的节点
+        self.map.clear();
+
+        let mut cur = self.head;
+
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).next;
+                drop(Box::from_raw(node.as_ptr()));
+            }
+        }
+
+        self.head = None;
+        self.tail = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 最久未使用的键值对，不更新顺序
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.tail
+            .map(|tail| unsafe { (&(*tail.as_ptr()).key, &(*tail.as_ptr()).val) })
+    }
+
+    /// 按最近使用到最久未使用的顺序迭代，不更新顺序
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.head, self.tail, self.len())
+    }
+
+    /// 按最近使用到最久未使用的顺序可变迭代，不更新顺序
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.head, self.tail, self.len())
+    }
+
+    /// 摘下节点并释放，调用者负责先从哈希表中移除
+    unsafe fn free_node(&mut self, node: NonNull<Node<K, V>>) -> (K, V) {
+        unsafe {
+            self.detach(node);
+            let Node { key, val, .. } = *Box::from_raw(node.as_ptr());
+            (key, val)
+        }
+    }
+
+    /// 摘下链表节点
+    unsafe fn detach(&mut self, node: NonNull<Node<K, V>>) {
+        let node_ref = unsafe { node.as_ref() };
+
+        match node_ref.prev {
+            Some(mut prev) => unsafe {
+                prev.as_mut().next = node_ref.next;
+            },
+            None => self.head = node_ref.next,
+        }
+
+        match node_ref.next {
+            Some(mut next) => unsafe {
+                next.as_mut().prev = node_ref.prev;
+            },
+            None => self.tail = node_ref.prev,
+        }
+    }
+
+    /// 头插
+    unsafe fn attach_front(&mut self, mut node: NonNull<Node<K, V>>) {
+        unsafe {
+            let node_ref = node.as_mut();
+            node_ref.prev = None;
+            node_ref.next = self.head;
+        }
+
+        match self.head {
+            Some(mut head) => unsafe {
+                head.as_mut().prev = Some(node);
+            },
+            None => {
+                self.tail = Some(node);
+            }
+        }
+
+        self.head = Some(node);
+    }
+
+    /// 尾插
+    unsafe fn attach_back(&mut self, mut node: NonNull<Node<K, V>>) {
+        unsafe {
+            let node_ref = node.as_mut();
+            node_ref.next = None;
+            node_ref.prev = self.tail;
+        }
+
+        match self.tail {
+            Some(mut tail) => unsafe {
+                tail.as_mut().next = Some(node);
+            },
+            None => {
+                self.head = Some(node);
+            }
+        }
+
+        self.tail = Some(node);
+    }
+
+    /// 将结点移动到链表头
+    pub(crate) unsafe fn move_to_front(&mut self, node: NonNull<Node<K, V>>) {
+        if self.head == Some(node) {
+            return;
+        }
+
+        unsafe {
+            self.detach(node);
+            self.attach_front(node);
+        }
+    }
+}
+
+impl<K, V, S> Drop for LruCache<K, V, S> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, S> Clone for LruCache<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    /// 复制内容与使用顺序
+    fn clone(&self) -> Self {
+        let mut cache = Self::with_hasher(self.capacity, self.map.hasher().clone());
+        for (key, val) in self.iter().rev() {
+            cache.push_front(key.clone(), val.clone());
+        }
+        cache
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for LruCache<K, V, S> {
+    /// 按最近使用到最久未使用的顺序输出
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for LruCache<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// 依次 `put`，后写入的更新
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.put(key, val);
+        }
+    }
+}
+
+impl<K, V, S> IntoIterator for LruCache<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, S>;
+
+    fn into_iter(self) -> IntoIter<K, V, S> {
+        IntoIter { cache: self }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a LruCache<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut LruCache<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::RandomState;
+
+    #[test]
+    fn test_new_initial_state() {
+        let cache: LruCache<i32, i32> = LruCache::new(3);
+        assert_eq!(cache.capacity(), 3);
+        assert_eq!(cache.len(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_with_hasher_initial_state() {
+        let cache: LruCache<i32, i32> = LruCache::with_hasher(5, RandomState::new());
+        assert_eq!(cache.capacity(), 5);
+        assert_eq!(cache.len(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_zero_put_returns_value() {
+        let mut cache: LruCache<i32, i32> = LruCache::new(0);
+        assert_eq!(cache.put(1, 10), Some(10));
+        assert_eq!(cache.len(), 0);
+        assert!(cache.is_empty());
+        assert_eq!(cache.put(2, 20), Some(20));
+    }
+
+    #[test]
+    fn test_put_new_key() {
+        let mut cache = LruCache::new(3);
+        assert_eq!(cache.put(1, 10), None);
+        assert_eq!(cache.len(), 1);
+        assert!(!cache.is_empty());
+    }
+
+    #[test]
+    fn test_put_existing_key_updates_value() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.put(1, 100), None);
+        assert_eq!(cache.get(&1), Some(&mut 100));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_get_existing_key() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.get(&1), Some(&mut 10));
+    }
+
+    #[test]
+    fn test_get_nonexistent_key() {
+        let mut cache: LruCache<i32, i32> = LruCache::new(3);
+        assert_eq!(cache.get(&1), None);
+    }
+
+    #[test]
+    fn test_peek_existing_key() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.peek(&1), Some(&10));
+    }
+
+    #[test]
+    fn test_peek_nonexistent_key() {
+        let cache: LruCache<i32, i32> = LruCache::new(3);
+        assert_eq!(cache.peek(&1), None);
+    }
+
+    #[test]
+    fn test_peek_does_not_change_order() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.peek(&1);
+        // 1 is still LRU (at tail)
+        assert_eq!(cache.put(3, 30), Some(10));
+        assert_eq!(cache.get(&1), None);
+    }
+
+    #[test]
+    fn test_eviction_simple() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.put(3, 30), Some(10));
+        assert_eq!(cache.get(&1), None);
+    }
+
+    #[test]
+    fn test_eviction_order_after_get() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.get(&1);
+        assert_eq!(cache.put(3, 30), Some(20));
+        assert_eq!(cache.get(&2), None);
+    }
+
+    #[test]
+    fn test_eviction_after_get_of_non_head() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.get(&2);
+        assert_eq!(cache.put(4, 40), Some(10));
+    }
+
+    #[test]
+    fn test_remove_existing_key() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_nonexistent_key() {
+        let mut cache: LruCache<i32, i32> = LruCache::new(3);
+        assert_eq!(cache.remove(&1), None);
+    }
+
+    #[test]
+    fn test_remove_twice() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.remove(&1), None);
+    }
+
+    #[test]
+    fn test_remove_head_prev_none() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(&mut 20));
+        assert_eq!(cache.get(&3), Some(&mut 30));
+    }
+
+    #[test]
+    fn test_remove_tail_next_none() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        assert_eq!(cache.remove(&3), Some(30));
+        assert_eq!(cache.get(&3), None);
+        assert_eq!(cache.get(&2), Some(&mut 20));
+    }
+
+    #[test]
+    fn test_remove_middle_both_some() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        assert_eq!(cache.remove(&2), Some(20));
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some(&mut 10));
+        assert_eq!(cache.get(&3), Some(&mut 30));
+    }
+
+    #[test]
+    fn test_remove_single_element_both_none() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert!(cache.is_empty());
+        // attach_front on empty list
+        cache.put(2, 20);
+        assert_eq!(cache.get(&2), Some(&mut 20));
+    }
+
+    #[test]
+    fn test_contains_key() {
+        let mut cache = LruCache::new(3);
+        assert!(!cache.contains_key(&1));
+        cache.put(1, 10);
+        assert!(cache.contains_key(&1));
+    }
+
+    #[test]
+    fn test_clear_empty() {
+        let mut cache: LruCache<i32, i32> = LruCache::new(3);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_clear_nonempty() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+        assert!(cache.is_empty());
+        assert!(!cache.contains_key(&1));
+    }
+
+    #[test]
+    fn test_reuse_after_clear() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.len(), 0);
+        cache.put(3, 30);
+        cache.put(4, 40);
+        cache.put(5, 50);
+        assert_eq!(cache.len(), 3);
+        let evicted = cache.put(6, 60);
+        assert!(evicted.is_some());
+        assert_eq!(cache.len(), 3);
+        assert!(cache.contains_key(&6));
+    }
+
+    #[test]
+    fn test_get_head_node_early_return_in_move_to_front() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        assert_eq!(cache.get(&1), Some(&mut 10));
+        cache.put(2, 20);
+        assert_eq!(cache.get(&1), Some(&mut 10));
+    }
+
+    #[test]
+    fn test_get_moves_non_head_to_front() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.get(&1);
+        assert_eq!(cache.put(3, 30), Some(20));
+    }
+
+    #[test]
+    fn test_put_existing_moves_to_front() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(1, 100);
+        assert_eq!(cache.put(3, 30), Some(20));
+        assert_eq!(cache.get(&1), Some(&mut 100));
+        assert_eq!(cache.get(&3), Some(&mut 30));
+    }
+
+    #[test]
+    fn test_drop_nonempty_cache() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+    }
+
+    #[test]
+    fn test_iter_mru_to_lru() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.get(&1);
+
+        let keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![1, 3, 2]);
+        let rev: Vec<_> = cache.iter().rev().map(|(k, _)| *k).collect();
+        assert_eq!(rev, vec![2, 3, 1]);
+        assert_eq!(cache.iter().len(), 3);
+
+        let mut it = cache.iter();
+        assert_eq!(it.next(), Some((&1, &10)));
+        assert_eq!(it.next_back(), Some((&2, &20)));
+        assert_eq!(it.next(), Some((&3, &30)));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_mut_updates_values() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        for (_, v) in cache.iter_mut() {
+            *v += 1;
+        }
+        for (_, v) in &mut cache {
+            *v *= 2;
+        }
+        assert_eq!(cache.peek(&1), Some(&22));
+        assert_eq!(cache.peek(&2), Some(&42));
+    }
+
+    #[test]
+    fn test_pop_lru_and_peek_lru() {
+        let mut cache = LruCache::new(3);
+        assert_eq!(cache.peek_lru(), None);
+        assert_eq!(cache.pop_lru(), None);
+
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.peek_lru(), Some((&1, &10)));
+        assert_eq!(cache.pop_lru(), Some((1, 10)));
+        assert_eq!(cache.pop_lru(), Some((2, 20)));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_resize_evicts_lru() {
+        let mut cache = LruCache::new(4);
+        for i in 0..4 {
+            cache.put(i, i * 10);
+        }
+        cache.get(&0);
+        cache.resize(2);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_key(&0));
+        assert!(cache.contains_key(&3));
+
+        cache.resize(3);
+        cache.put(9, 90);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_promote_and_demote() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+
+        assert!(cache.promote(&1));
+        assert!(cache.demote(&3));
+        assert!(!cache.promote(&4));
+        assert!(!cache.demote(&4));
+
+        let keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![1, 2, 3]);
+
+        cache.put(4, 40);
+        assert!(!cache.contains_key(&3));
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(*cache.get_or_insert_with(1, || 10), 10);
+        assert_eq!(*cache.get_or_insert_with(1, || unreachable!()), 10);
+        cache.put(2, 20);
+        *cache.get_or_insert_with(1, || 0) += 1;
+        cache.get_or_insert_with(3, || 30);
+        assert!(!cache.contains_key(&2));
+        assert_eq!(cache.peek(&1), Some(&11));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_or_insert_with_zero_capacity_panics() {
+        let mut cache: LruCache<i32, i32> = LruCache::new(0);
+        cache.get_or_insert_with(1, || 10);
+    }
+
+    #[test]
+    fn test_entry_api() {
+        let mut cache = LruCache::new(2);
+        cache.entry("a").or_insert(1);
+        cache.entry("a").and_modify(|v| *v += 1).or_insert(100);
+        assert_eq!(cache.peek(&"a"), Some(&2));
+
+        match cache.entry("b") {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key, "b");
+                entry.insert(5);
+            }
+            Entry::Occupied(_) => unreachable!(),
+        }
+        match cache.entry("a") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(*entry.key(), "a");
+                assert_eq!(entry.insert(3), 2);
+                assert_eq!(entry.remove(), 3);
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(cache.len(), 1);
+        assert!(!cache.contains_key(&"a"));
+    }
+
+    #[test]
+    fn test_borrowed_key_lookup() {
+        let mut cache = LruCache::new(2);
+        cache.put(String::from("one"), 1);
+        cache.put(String::from("two"), 2);
+
+        assert_eq!(cache.get("one"), Some(&mut 1));
+        assert_eq!(cache.peek("two"), Some(&2));
+        assert!(cache.contains_key("one"));
+        assert_eq!(cache.remove_entry("two"), Some((String::from("two"), 2)));
+        assert_eq!(cache.remove("one"), Some(1));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_non_clone_keys() {
+        #[derive(PartialEq, Eq, Hash, Debug)]
+        struct Key(u32);
+
+        let mut cache = LruCache::new(1);
+        cache.put(Key(1), "a");
+        assert_eq!(cache.put(Key(2), "b"), Some("a"));
+        assert_eq!(cache.pop_lru(), Some((Key(2), "b")));
+    }
+
+    #[test]
+    fn test_debug_clone_extend() {
+        let mut cache = LruCache::new(3);
+        cache.extend([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+        assert_eq!(format!("{:?}", cache), "{4: 'd', 3: 'c', 2: 'b'}");
+
+        let mut copy = cache.clone();
+        assert_eq!(copy.capacity(), 3);
+        copy.put(5, 'e');
+        assert!(copy.contains_key(&3));
+        assert!(!copy.contains_key(&2));
+        assert!(cache.contains_key(&2));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+
+        let items: Vec<_> = (&cache).into_iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(items, vec![(3, 30), (2, 20), (1, 10)]);
+
+        let mut owned = cache.into_iter();
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned.next_back(), Some((1, 10)));
+        assert_eq!(owned.collect::<Vec<_>>(), vec![(3, 30), (2, 20)]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    hash::{BuildHasher, Hash},
+    ptr::NonNull,
+};
+
+use crate::collections::lru_cache::cache::{LruCache, Node};
+
+/// 对缓存中某个 key 的一次性访问视图
+pub enum Entry<'a, K, V, S> {
+    Vacant(VacantEntry<'a, K, V, S>),
+    Occupied(OccupiedEntry<'a, K, V, S>),
+}
+
+pub struct VacantEntry<'a, K, V, S> {
+    pub(crate) cache: &'a mut LruCache<K, V, S>,
+    pub(crate) key: K,
+}
+
+/// 已存在的条目，创建时已被移到最近使用的位置
+pub struct OccupiedEntry<'a, K, V, S> {
+    pub(crate) cache: &'a mut LruCache<K, V, S>,
+    pub(crate) node: NonNull<Node<K, V>>,
+}
+
+impl<'a, K: Eq + Hash, V, S: BuildHasher> Entry<'a, K, V, S> {
+    /// 确保值存在，通过插入默认值来处理 Vacant 情况
+    ///
+    /// ## Panics
+    /// Vacant 且容量为 0 时 panic
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// 确保值存在，通过闭包计算默认值来处理 Vacant 情况
+    ///
+    /// ## Panics
+    /// Vacant 且容量为 0 时 panic
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// 如果为 Occupied 则修改值
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => &entry.key,
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V, S: BuildHasher> VacantEntry<'a, K, V, S> {
+    /// 插入为最近使用的条目，缓存已满时先淘汰最久未使用的条目
+    ///
+    /// ## Panics
+    /// 容量为 0 时 panic
+    pub fn insert(self, val: V) -> &'a mut V {
+        assert!(
+            self.cache.capacity() > 0,
+            "cannot insert into a zero-capacity LruCache"
+        );
+        if self.cache.len() >= self.cache.capacity() {
+            self.cache.pop_lru();
+        }
+        let node = self.cache.push_front(self.key, val);
+        unsafe { &mut (*node.as_ptr()).val }
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<'a, K: Eq + Hash, V, S: BuildHasher> OccupiedEntry<'a, K, V, S> {
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.node.as_ptr()).val }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.node.as_ptr()).val }
+    }
+
+    pub fn insert(&mut self, val: V) -> V {
+        unsafe { std::mem::replace(&mut (*self.node.as_ptr()).val, val) }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.node.as_ptr()).val }
+    }
+
+    pub fn key(&self) -> &K {
+        unsafe { &(*self.node.as_ptr()).key }
+    }
+
+    pub fn remove(self) -> V {
+        unsafe { self.cache.remove_node(self.node).1 }
+    }
+}
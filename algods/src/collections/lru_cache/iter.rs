@@ -0,0 +1,148 @@
+use std::{
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::collections::lru_cache::cache::{Link, LruCache, Node};
+
+/// 按最近使用到最久未使用顺序的不可变迭代器，可从两端迭代
+pub struct Iter<'a, K, V> {
+    front: Link<K, V>,
+    back: Link<K, V>,
+    /// 尚未产出的节点数
+    len: usize,
+    _marker: PhantomData<&'a Node<K, V>>,
+}
+
+/// 按最近使用到最久未使用顺序的可变迭代器，可从两端迭代
+pub struct IterMut<'a, K, V> {
+    front: Link<K, V>,
+    back: Link<K, V>,
+    len: usize,
+    _marker: PhantomData<&'a mut Node<K, V>>,
+}
+
+/// 按最近使用到最久未使用顺序、拥有所有权的迭代器
+pub struct IntoIter<K, V, S> {
+    pub(crate) cache: LruCache<K, V, S>,
+}
+
+impl<K, V> Iter<'_, K, V> {
+    pub(crate) fn new(front: Link<K, V>, back: Link<K, V>, len: usize) -> Self {
+        Self {
+            front,
+            back,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.front?.as_ptr();
+            self.front = (*node).next;
+            Some((&(*node).key, &(*node).val))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.back?.as_ptr();
+            self.back = (*node).prev;
+            Some((&(*node).key, &(*node).val))
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self::new(self.front, self.back, self.len)
+    }
+}
+
+impl<K, V> IterMut<'_, K, V> {
+    pub(crate) fn new(front: Link<K, V>, back: Link<K, V>, len: usize) -> Self {
+        Self {
+            front,
+            back,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.front?.as_ptr();
+            self.front = (*node).next;
+            Some((&(*node).key, &mut (*node).val))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.back?.as_ptr();
+            self.back = (*node).prev;
+            Some((&(*node).key, &mut (*node).val))
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K: Eq + Hash, V, S: BuildHasher> Iterator for IntoIter<K, V, S> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cache.pop_mru()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cache.len(), Some(self.cache.len()))
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> DoubleEndedIterator for IntoIter<K, V, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cache.pop_lru()
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> ExactSizeIterator for IntoIter<K, V, S> {}
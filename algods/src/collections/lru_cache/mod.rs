@@ -0,0 +1,7 @@
+pub mod cache;
+pub mod entry;
+pub mod iter;
+
+pub use cache::*;
+pub use entry::*;
+pub use iter::*;
@@ -1,15 +1,23 @@
 pub mod bit_set;
+pub mod cache;
+pub mod concurrent_lru_cache;
 pub mod disjoint_set;
 pub mod fenwick_tree;
 pub mod fibonacci_heap;
+pub mod index_list;
 pub mod linked_list;
 pub mod lru_cache;
+pub mod persistent_map;
 pub mod rbtree;
 pub mod segment_tree;
 pub mod trie;
 
 pub use bit_set::{BitSet, BitSetIter};
+pub use cache::Cache;
+pub use concurrent_lru_cache::{CacheStats, ConcurrentLruCache};
 pub use disjoint_set::DisjointSet;
+pub use index_list::{Handle, IndexList};
 pub use linked_list::LinkedList;
 pub use lru_cache::LruCache;
-pub use rbtree::RBTreeMap;
+pub use persistent_map::PersistentMap;
+pub use rbtree::{Augment, IntervalMap, RBTreeMap, RBTreeSet};
@@ -0,0 +1,767 @@
+//! 持久化（不可变、结构共享）有序映射
+//!
+//! ```
+//! use algods::collections::PersistentMap;
+//!
+//! let v1: PersistentMap<&str, i32> = PersistentMap::new().insert("a", 1).insert("b", 2);
+//! let v2 = v1.insert("c", 3).remove("a");
+//!
+//! // 旧版本保持不变，两个版本共享未修改的子树
+//! assert_eq!(v1.iter().collect::<Vec<_>>(), [(&"a", &1), (&"b", &2)]);
+//! assert_eq!(v2.iter().collect::<Vec<_>>(), [(&"b", &2), (&"c", &3)]);
+//! ```
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// 平衡因子：一侧子树的大小不超过另一侧的 DELTA 倍
+const DELTA: usize = 3;
+/// 旋转时判断单旋或双旋的阈值
+const RATIO: usize = 2;
+
+type Link<K, V> = Option<Arc<Node<K, V>>>;
+
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    val: V,
+    /// 子树节点数
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+/// 持久化有序映射
+///
+/// 基于权重平衡树（Adams 树）的路径复制实现：每次修改只复制从根到修改位置的 O(log n) 个节点，
+/// 其余子树通过 `Arc` 在新旧版本之间共享。因此 `clone` 为 O(1)，
+/// 历史版本可以直接保存用于撤销/重做或快照隔离，并且可以跨线程共享
+///
+/// 迭代顺序与接口风格与 [`RBTreeMap`](crate::collections::RBTreeMap) 一致
+///
+/// ## Notes
+/// 时间复杂度: 查找、插入、删除、按序选取与排名均为 O(log n)
+pub struct PersistentMap<K, V> {
+    root: Link<K, V>,
+}
+
+/// 按键升序的迭代器，可从两端迭代
+pub struct Iter<'a, K, V> {
+    /// 前端的中序遍历栈，栈顶为下一个产出的节点
+    front: Vec<&'a Node<K, V>>,
+    /// 后端的逆中序遍历栈
+    back: Vec<&'a Node<K, V>>,
+    /// 尚未产出的节点数，两端相遇时归零
+    len: usize,
+}
+
+/// 范围迭代器，可从两端迭代
+pub struct Range<'a, K, V>(Iter<'a, K, V>);
+
+pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+pub struct Values<'a, K, V>(Iter<'a, K, V>);
+
+fn size<K, V>(t: &Link<K, V>) -> usize {
+    t.as_ref().map_or(0, |n| n.size)
+}
+
+fn node<K, V>(key: K, val: V, left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    Some(Arc::new(Node {
+        size: size(&left) + size(&right) + 1,
+        key,
+        val,
+        left,
+        right,
+    }))
+}
+
+/// 取出节点内容：唯一持有时直接移出，否则复制该节点（子树仍共享）
+fn unwrap_node<K: Clone, V: Clone>(t: Link<K, V>) -> Node<K, V> {
+    Arc::unwrap_or_clone(t.expect("non-empty subtree"))
+}
+
+/// 组合节点并在失衡时单旋或双旋
+///
+/// 左右子树各自平衡，且至多相差一次插入或删除
+fn balance<K: Clone, V: Clone>(key: K, val: V, left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    let (ls, rs) = (size(&left), size(&right));
+    if ls + rs <= 1 {
+        node(key, val, left, right)
+    } else if rs > DELTA * ls {
+        let r = unwrap_node(right);
+        if size(&r.left) < RATIO * size(&r.right) {
+            // 单左旋
+            node(r.key, r.val, node(key, val, left, r.left), r.right)
+        } else {
+            // 双旋：先右旋右子树再左旋
+            let rl = unwrap_node(r.left);
+            node(
+                rl.key,
+                rl.val,
+                node(key, val, left, rl.left),
+                node(r.key, r.val, rl.right, r.right),
+            )
+        }
+    } else if ls > DELTA * rs {
+        let l = unwrap_node(left);
+        if size(&l.right) < RATIO * size(&l.left) {
+            node(l.key, l.val, l.left, node(key, val, l.right, right))
+        } else {
+            let lr = unwrap_node(l.right);
+            node(
+                lr.key,
+                lr.val,
+                node(l.key, l.val, l.left, lr.left),
+                node(key, val, lr.right, right),
+            )
+        }
+    } else {
+        node(key, val, left, right)
+    }
+}
+
+fn insert<K: Ord + Clone, V: Clone>(t: &Link<K, V>, key: K, val: V) -> Link<K, V> {
+    let Some(n) = t else {
+        return node(key, val, None, None);
+    };
+    match key.cmp(&n.key) {
+        Ordering::Less => balance(
+            n.key.clone(),
+            n.val.clone(),
+            insert(&n.left, key, val),
+            n.right.clone(),
+        ),
+        Ordering::Greater => balance(
+            n.key.clone(),
+            n.val.clone(),
+            n.left.clone(),
+            insert(&n.right, key, val),
+        ),
+        Ordering::Equal => node(key, val, n.left.clone(), n.right.clone()),
+    }
+}
+
+/// 删除 key，key 不存在时返回 None（无需复制任何节点）
+fn remove<K, V, Q>(t: &Link<K, V>, key: &Q) -> Option<Link<K, V>>
+where
+    K: Borrow<Q> + Clone,
+    V: Clone,
+    Q: Ord + ?Sized,
+{
+    let n = t.as_ref()?;
+    Some(match key.cmp(n.key.borrow()) {
+        Ordering::Less => balance(
+            n.key.clone(),
+            n.val.clone(),
+            remove(&n.left, key)?,
+            n.right.clone(),
+        ),
+        Ordering::Greater => balance(
+            n.key.clone(),
+            n.val.clone(),
+            n.left.clone(),
+            remove(&n.right, key)?,
+        ),
+        Ordering::Equal => glue(n.left.clone(), n.right.clone()),
+    })
+}
+
+/// 合并被删除节点的左右子树：从较大的一侧取出最值作为新根
+fn glue<K: Clone, V: Clone>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    if left.is_none() {
+        return right;
+    }
+    if right.is_none() {
+        return left;
+    }
+    if size(&left) > size(&right) {
+        let (k, v, l) = pop_max(left);
+        balance(k, v, l, right)
+    } else {
+        let (k, v, r) = pop_min(right);
+        balance(k, v, left, r)
+    }
+}
+
+fn pop_min<K: Clone, V: Clone>(t: Link<K, V>) -> (K, V, Link<K, V>) {
+    let n = unwrap_node(t);
+    if n.left.is_none() {
+        return (n.key, n.val, n.right);
+    }
+    let (k, v, l) = pop_min(n.left);
+    (k, v, balance(n.key, n.val, l, n.right))
+}
+
+fn pop_max<K: Clone, V: Clone>(t: Link<K, V>) -> (K, V, Link<K, V>) {
+    let n = unwrap_node(t);
+    if n.right.is_none() {
+        return (n.key, n.val, n.left);
+    }
+    let (k, v, r) = pop_max(n.right);
+    (k, v, balance(n.key, n.val, n.left, r))
+}
+
+impl<K, V> PersistentMap<K, V> {
+    /// 创建空映射
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// 两个版本是否共享同一个根（为真时内容必然相同）
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut it = Iter {
+            front: Vec::new(),
+            back: Vec::new(),
+            len: self.len(),
+        };
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            it.front.push(n);
+            cur = n.left.as_deref();
+        }
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            it.back.push(n);
+            cur = n.right.as_deref();
+        }
+        it
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.iter())
+    }
+
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.iter().next_back()
+    }
+
+    /// 按键升序的第 k 个（从 0 开始）键值对
+    pub fn nth(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            let left = size(&n.left);
+            match k.cmp(&left) {
+                Ordering::Less => cur = n.left.as_deref(),
+                Ordering::Equal => return Some((&n.key, &n.val)),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    cur = n.right.as_deref();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<K: Ord, V> PersistentMap<K, V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            match key.cmp(n.key.borrow()) {
+                Ordering::Less => cur = n.left.as_deref(),
+                Ordering::Greater => cur = n.right.as_deref(),
+                Ordering::Equal => return Some(&n.val),
+            }
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// 严格小于 key 的键的数量
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_below(key, false)
+    }
+
+    /// 键小于 key（`inclusive` 为真时小于等于）的数量
+    fn count_below<Q>(&self, key: &Q, inclusive: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut count = 0;
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            let go_right = match key.cmp(n.key.borrow()) {
+                Ordering::Less => false,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => true,
+            };
+            if go_right {
+                count += size(&n.left) + 1;
+                cur = n.right.as_deref();
+            } else {
+                cur = n.left.as_deref();
+            }
+        }
+        count
+    }
+
+    /// 范围内的键值对，起点大于终点时为空
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let above_lo = |k: &Q| match range.start_bound() {
+            Bound::Included(lo) => k >= lo,
+            Bound::Excluded(lo) => k > lo,
+            Bound::Unbounded => true,
+        };
+        let below_hi = |k: &Q| match range.end_bound() {
+            Bound::Included(hi) => k <= hi,
+            Bound::Excluded(hi) => k < hi,
+            Bound::Unbounded => true,
+        };
+        let before_start = match range.start_bound() {
+            Bound::Included(k) => self.count_below(k, false),
+            Bound::Excluded(k) => self.count_below(k, true),
+            Bound::Unbounded => 0,
+        };
+        let before_end = match range.end_bound() {
+            Bound::Included(k) => self.count_below(k, true),
+            Bound::Excluded(k) => self.count_below(k, false),
+            Bound::Unbounded => self.len(),
+        };
+
+        // 前端栈定位到第一个 >= 下界的节点，后端栈定位到最后一个 <= 上界的节点
+        let mut it = Iter {
+            front: Vec::new(),
+            back: Vec::new(),
+            len: before_end.saturating_sub(before_start),
+        };
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            if above_lo(n.key.borrow()) {
+                it.front.push(n);
+                cur = n.left.as_deref();
+            } else {
+                cur = n.right.as_deref();
+            }
+        }
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            if below_hi(n.key.borrow()) {
+                it.back.push(n);
+                cur = n.right.as_deref();
+            } else {
+                cur = n.left.as_deref();
+            }
+        }
+        Range(it)
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> PersistentMap<K, V> {
+    /// 插入键值对，返回新版本；key 已存在时新版本中的值被替换
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)，只复制根到插入位置路径上的节点
+    #[must_use = "insert returns a new version and leaves self unchanged"]
+    pub fn insert(&self, key: K, val: V) -> Self {
+        Self {
+            root: insert(&self.root, key, val),
+        }
+    }
+
+    /// 删除 key，返回新版本；key 不存在时返回与自身共享的副本
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    #[must_use = "remove returns a new version and leaves self unchanged"]
+    pub fn remove<Q>(&self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match remove(&self.root, key) {
+            Some(root) => Self { root },
+            None => self.clone(),
+        }
+    }
+
+    /// 以 `f` 修改 key 对应的值，返回新版本；key 不存在时返回与自身共享的副本
+    #[must_use = "update returns a new version and leaves self unchanged"]
+    pub fn update<Q, F>(&self, key: &Q, f: F) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        F: FnOnce(&V) -> V,
+    {
+        let mut cur = self.root.as_deref();
+        while let Some(n) = cur {
+            match key.cmp(n.key.borrow()) {
+                Ordering::Less => cur = n.left.as_deref(),
+                Ordering::Greater => cur = n.right.as_deref(),
+                Ordering::Equal => return self.insert(n.key.clone(), f(&n.val)),
+            }
+        }
+        self.clone()
+    }
+}
+
+impl<K, V> Clone for PersistentMap<K, V> {
+    /// 只复制根指针，时间复杂度 O(1)
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for PersistentMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for PersistentMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |map, (k, v)| map.insert(k, v))
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for PersistentMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.len() == other.len() && self.iter().eq(other.iter()))
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for PersistentMap<K, V> {}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for PersistentMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a PersistentMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let n = self.front.pop()?;
+        let mut cur = n.right.as_deref();
+        while let Some(c) = cur {
+            self.front.push(c);
+            cur = c.left.as_deref();
+        }
+        Some((&n.key, &n.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let n = self.back.pop()?;
+        let mut cur = n.left.as_deref();
+        while let Some(c) = cur {
+            self.back.push(c);
+            cur = c.right.as_deref();
+        }
+        Some((&n.key, &n.val))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front.clone(),
+            back: self.back.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Range<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Range<'_, K, V> {}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::RBTreeMap;
+    use std::collections::BTreeMap;
+
+    /// 校验子树大小与权重平衡条件
+    fn check_balanced<K: Ord, V>(map: &PersistentMap<K, V>) {
+        fn go<K: Ord, V>(t: &Link<K, V>) -> usize {
+            let Some(n) = t else { return 0 };
+            let (ls, rs) = (go(&n.left), go(&n.right));
+            assert_eq!(n.size, ls + rs + 1, "size mismatch");
+            assert!(
+                ls + rs <= 1 || (ls <= DELTA * rs && rs <= DELTA * ls),
+                "unbalanced: {ls} vs {rs}"
+            );
+            if let Some(l) = &n.left {
+                assert!(l.key < n.key);
+            }
+            if let Some(r) = &n.right {
+                assert!(r.key > n.key);
+            }
+            n.size
+        }
+        assert_eq!(go(&map.root), map.len());
+    }
+
+    #[test]
+    fn test_versions_are_independent() {
+        let empty = PersistentMap::new();
+        let v1 = empty.insert(2, "two").insert(1, "one");
+        let v2 = v1.insert(3, "three");
+        let v3 = v2.remove(&1).insert(2, "TWO");
+
+        assert!(empty.is_empty());
+        assert_eq!(v1.iter().collect::<Vec<_>>(), [(&1, &"one"), (&2, &"two")]);
+        assert_eq!(v2.len(), 3);
+        assert_eq!(
+            v3.iter().collect::<Vec<_>>(),
+            [(&2, &"TWO"), (&3, &"three")]
+        );
+        assert_eq!(v2.get(&1), Some(&"one"));
+        assert_eq!(v3.get(&1), None);
+
+        // 删除不存在的键不复制任何节点
+        assert!(v3.remove(&42).ptr_eq(&v3));
+        assert!(v3.update(&42, |v| v).ptr_eq(&v3));
+        assert_eq!(
+            v3.update(&3, |v| if *v == "three" { "3" } else { v })
+                .get(&3),
+            Some(&"3")
+        );
+        assert_eq!(v3.get(&3), Some(&"three"));
+    }
+
+    #[test]
+    fn test_structural_sharing() {
+        let base: PersistentMap<i32, i32> = (0..1024).map(|k| (k, k)).collect();
+        let next = base.insert(5000, 0);
+
+        // 新版本只复制了一条路径，其余子树与旧版本共享
+        fn collect_nodes<K, V>(t: &Link<K, V>, out: &mut Vec<*const Node<K, V>>) {
+            if let Some(n) = t {
+                out.push(Arc::as_ptr(n));
+                collect_nodes(&n.left, out);
+                collect_nodes(&n.right, out);
+            }
+        }
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        collect_nodes(&base.root, &mut a);
+        collect_nodes(&next.root, &mut b);
+        let shared = b.iter().filter(|p| a.contains(p)).count();
+        assert!(
+            b.len() - shared <= 2 * 11,
+            "copied {} nodes",
+            b.len() - shared
+        );
+
+        let snapshot = next.clone();
+        assert!(snapshot.ptr_eq(&next));
+        assert_eq!(snapshot, next);
+        assert_ne!(base, next);
+    }
+
+    #[test]
+    fn test_matches_btreemap() {
+        let mut x = 2024u64;
+        let mut map = PersistentMap::new();
+        let mut model = BTreeMap::new();
+        let mut history = Vec::new();
+        for _ in 0..2000 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let k = (x >> 35) % 300;
+            if x.is_multiple_of(3) {
+                map = map.remove(&k);
+                model.remove(&k);
+            } else {
+                map = map.insert(k, x);
+                model.insert(k, x);
+            }
+            history.push((map.clone(), model.clone()));
+        }
+        check_balanced(&map);
+
+        // 每个历史版本都保持当时的内容
+        for (version, expected) in history.iter().step_by(97) {
+            check_balanced(version);
+            assert!(version.iter().eq(expected.iter()));
+            assert!(version.iter().rev().eq(expected.iter().rev()));
+        }
+
+        for (i, (k, v)) in model.iter().enumerate() {
+            assert_eq!(map.nth(i), Some((k, v)));
+            assert_eq!(map.rank(k), i);
+        }
+        for (lo, hi) in [(0, 300), (10, 20), (150, 151), (299, 400)] {
+            assert!(map.range(lo..hi).eq(model.range(lo..hi)));
+            assert!(map.range(lo..=hi).rev().eq(model.range(lo..=hi).rev()));
+            assert_eq!(map.range(lo..hi).len(), model.range(lo..hi).count());
+        }
+    }
+
+    #[test]
+    fn test_iteration_matches_rbtreemap() {
+        let pairs = [(5, 'e'), (1, 'a'), (3, 'c'), (4, 'd'), (2, 'b')];
+        let p: PersistentMap<_, _> = pairs.into_iter().collect();
+        let r: RBTreeMap<_, _> = pairs.into_iter().collect();
+
+        assert!(p.iter().eq(r.iter()));
+        assert!(p.keys().rev().eq(r.keys().rev()));
+        assert!(p.values().eq(r.values()));
+        assert!(p.range(2..4).eq(r.range(2..4)));
+        assert!(p.range(2..=4).rev().eq(r.range(2..=4).rev()));
+        let (lo, hi) = (4, 2);
+        assert_eq!(p.range(lo..hi).count(), 0);
+        assert_eq!(p.first_key_value(), r.first_key_value());
+        assert_eq!(p.last_key_value(), r.last_key_value());
+        assert_eq!(format!("{p:?}"), format!("{r:?}"));
+
+        let mut it = p.iter();
+        assert_eq!(
+            (it.next(), it.next_back()),
+            (Some((&1, &'a')), Some((&5, &'e')))
+        );
+        assert_eq!(it.len(), 3);
+    }
+
+    #[test]
+    fn test_snapshots_across_threads() {
+        let map: PersistentMap<String, usize> = (0..100).map(|i| (format!("k{i:03}"), i)).collect();
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let snapshot = map.clone();
+                std::thread::spawn(move || {
+                    let mine = snapshot.insert(format!("t{t}"), t);
+                    (snapshot.len(), mine.len(), mine.get("k050").copied())
+                })
+            })
+            .collect();
+        for h in handles {
+            assert_eq!(h.join().unwrap(), (100, 101, Some(50)));
+        }
+        assert_eq!(map.len(), 100);
+    }
+}
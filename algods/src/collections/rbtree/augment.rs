@@ -0,0 +1,342 @@
+//! 红黑树的节点增强：子树大小与用户自定义的摘要
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::collections::rbtree::tree::{Link, LinkExt, RBTree};
+
+/// 子树摘要（幺半群）
+///
+/// 每个节点保存其子树按中序合并后的摘要：
+/// `combine(combine(left, summarize(key, val)), right)`，nil 子树取 `identity`。
+/// 摘要在插入、删除和旋转时自动维护，因此同一棵红黑树可以充当区间树
+/// （摘要为子树内区间右端点的最大值）或区间求和表（摘要为值之和）
+///
+/// ## Notes
+/// `combine` 须满足结合律，`identity` 须是其单位元；不要求交换律，
+/// 合并总是按键的升序进行
+pub trait Augment<K, V> {
+    type Summary: Clone;
+
+    /// 单位元，即空子树的摘要
+    fn identity(&self) -> Self::Summary;
+
+    /// 单个节点的摘要
+    fn summarize(&self, key: &K, val: &V) -> Self::Summary;
+
+    /// 合并相邻两段（左段在前）的摘要
+    fn combine(&self, left: &Self::Summary, right: &Self::Summary) -> Self::Summary;
+}
+
+/// 不携带摘要，只维护子树大小
+impl<K, V> Augment<K, V> for () {
+    type Summary = ();
+
+    #[inline(always)]
+    fn identity(&self) {}
+
+    #[inline(always)]
+    fn summarize(&self, _key: &K, _val: &V) {}
+
+    #[inline(always)]
+    fn combine(&self, _left: &(), _right: &()) {}
+}
+
+impl<K: Ord, V, A: Augment<K, V>> RBTree<K, V, A> {
+    /// 中序第 k 个（从 0 开始）节点，越界时返回 nil
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn select(&self, mut k: usize) -> Link<K, V, A> {
+        if k >= self.len() {
+            return self.nil;
+        }
+        unsafe {
+            let mut curr = self.root;
+            loop {
+                let n = curr.ptr();
+                let left = (*(*n).lch.ptr()).size;
+                match k.cmp(&left) {
+                    Ordering::Less => curr = (*n).lch,
+                    Ordering::Equal => return curr,
+                    Ordering::Greater => {
+                        k -= left + 1;
+                        curr = (*n).rch;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 键小于 key（`inclusive` 为真时小于等于）的节点数
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn count_below<Q>(&self, key: &Q, inclusive: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        unsafe {
+            let mut count = 0;
+            let mut curr = self.root;
+            while curr != self.nil {
+                let n = curr.ptr();
+                let go_right = match key.cmp((*n).key.borrow()) {
+                    Ordering::Less => false,
+                    Ordering::Equal => inclusive,
+                    Ordering::Greater => true,
+                };
+                if go_right {
+                    count += (*(*n).lch.ptr()).size + 1;
+                    curr = (*n).rch;
+                } else {
+                    curr = (*n).lch;
+                }
+            }
+            count
+        }
+    }
+
+    /// 键落在范围内的节点数，起点大于终点时为 0
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let before_start = match range.start_bound() {
+            Bound::Included(k) => self.count_below(k, false),
+            Bound::Excluded(k) => self.count_below(k, true),
+            Bound::Unbounded => 0,
+        };
+        let before_end = match range.end_bound() {
+            Bound::Included(k) => self.count_below(k, true),
+            Bound::Excluded(k) => self.count_below(k, false),
+            Bound::Unbounded => self.len(),
+        };
+        before_end.saturating_sub(before_start)
+    }
+
+    /// 整棵树的摘要，空树为单位元
+    pub fn summary(&self) -> &A::Summary {
+        // nil 的摘要字段已初始化为单位元，可以直接借用
+        unsafe { &(*self.root.ptr()).summary }
+    }
+
+    /// 键落在范围内的所有节点按升序合并的摘要，起点大于终点时为单位元
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n) 次 `combine`
+    pub fn range_summary<Q, R>(&self, range: R) -> A::Summary
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        unsafe { self.fold_bounded(self.root, range.start_bound(), range.end_bound()) }
+    }
+
+    /// 合并子树 x 中位于 `[lo, hi]` 内的节点摘要
+    ///
+    /// 两条边界在某个节点处分叉后，每一侧只剩单边约束：
+    /// 沿途与边界同侧的完整子树直接取其摘要，因此总共只访问 O(log n) 个节点
+    unsafe fn fold_bounded<Q>(&self, x: Link<K, V, A>, lo: Bound<&Q>, hi: Bound<&Q>) -> A::Summary
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if x == self.nil {
+            return self.aug.identity();
+        }
+        unsafe {
+            let n = x.ptr();
+            if let (Bound::Unbounded, Bound::Unbounded) = (lo, hi) {
+                return (*n).summary.clone();
+            }
+            let key: &Q = (*n).key.borrow();
+            let above_lo = match lo {
+                Bound::Included(l) => key >= l,
+                Bound::Excluded(l) => key > l,
+                Bound::Unbounded => true,
+            };
+            if !above_lo {
+                return self.fold_bounded((*n).rch, lo, hi);
+            }
+            let below_hi = match hi {
+                Bound::Included(h) => key <= h,
+                Bound::Excluded(h) => key < h,
+                Bound::Unbounded => true,
+            };
+            if !below_hi {
+                return self.fold_bounded((*n).lch, lo, hi);
+            }
+
+            let left = self.fold_bounded((*n).lch, lo, Bound::Unbounded);
+            let right = self.fold_bounded((*n).rch, Bound::Unbounded, hi);
+            let own = self.aug.summarize(&(*n).key, &(*n).val);
+            self.aug.combine(&self.aug.combine(&left, &own), &right)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 值之和
+    struct SumValues;
+
+    impl Augment<i32, i64> for SumValues {
+        type Summary = i64;
+
+        fn identity(&self) -> i64 {
+            0
+        }
+
+        fn summarize(&self, _key: &i32, val: &i64) -> i64 {
+            *val
+        }
+
+        fn combine(&self, left: &i64, right: &i64) -> i64 {
+            left + right
+        }
+    }
+
+    /// 按中序拼接键，用于检验合并顺序
+    struct Concat;
+
+    impl Augment<i32, ()> for Concat {
+        type Summary = String;
+
+        fn identity(&self) -> String {
+            String::new()
+        }
+
+        fn summarize(&self, key: &i32, _val: &()) -> String {
+            format!("{key},")
+        }
+
+        fn combine(&self, left: &String, right: &String) -> String {
+            format!("{left}{right}")
+        }
+    }
+
+    fn keys_in_order(t: &RBTree<i32, i64, SumValues>) -> Vec<i32> {
+        (0..t.len())
+            .map(|i| unsafe { (*t.select(i).ptr()).key })
+            .collect()
+    }
+
+    /// 校验每个节点的子树大小与摘要
+    fn check_augment(t: &RBTree<i32, i64, SumValues>) {
+        fn dfs(t: &RBTree<i32, i64, SumValues>, x: Link<i32, i64, SumValues>) -> (usize, i64) {
+            if x == t.nil {
+                return (0, 0);
+            }
+            unsafe {
+                let n = x.ptr();
+                let (ls, lsum) = dfs(t, (*n).lch);
+                let (rs, rsum) = dfs(t, (*n).rch);
+                assert_eq!((*n).size, ls + rs + 1, "size mismatch at {}", (*n).key);
+                assert_eq!(
+                    (*n).summary,
+                    lsum + rsum + (*n).val,
+                    "sum mismatch at {}",
+                    (*n).key
+                );
+                ((*n).size, (*n).summary)
+            }
+        }
+        assert_eq!(dfs(t, t.root).0, t.len());
+    }
+
+    #[test]
+    fn test_select_and_count_below() {
+        let mut t = RBTree::new();
+        for k in [50, 20, 80, 10, 30, 70, 90, 60] {
+            t.insert(k, ());
+        }
+        unsafe {
+            assert_eq!((*t.select(0).ptr()).key, 10);
+            assert_eq!((*t.select(4).ptr()).key, 60);
+            assert_eq!((*t.select(7).ptr()).key, 90);
+        }
+        assert_eq!(t.select(8), t.nil);
+
+        assert_eq!(t.count_below(&10, false), 0);
+        assert_eq!(t.count_below(&10, true), 1);
+        assert_eq!(t.count_below(&55, false), 4);
+        assert_eq!(t.count_below(&100, true), 8);
+        assert_eq!(t.count_range(20..=70), 5);
+        assert_eq!(t.count_range(21..70), 3);
+        assert_eq!(t.count_range(..), 8);
+        let (lo, hi) = (70, 20);
+        assert_eq!(t.count_range(lo..hi), 0);
+    }
+
+    #[test]
+    fn test_augment_survives_updates() {
+        let mut t = RBTree::with_augment(SumValues);
+        let mut x = 12345u64;
+        let mut model = std::collections::BTreeMap::new();
+        for _ in 0..400 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let k = (x >> 40) as i32 % 200;
+            if let Some(link) = t.search_tree(&k) {
+                t.remove(Some(link));
+                model.remove(&k);
+            } else {
+                t.insert(k, k as i64 * 3);
+                model.insert(k, k as i64 * 3);
+            }
+            check_augment(&t);
+        }
+
+        assert_eq!(keys_in_order(&t), model.keys().copied().collect::<Vec<_>>());
+        assert_eq!(*t.summary(), model.values().sum::<i64>());
+        for (lo, hi) in [(0, 200), (17, 93), (50, 51), (120, 40)] {
+            let expected: i64 = if lo <= hi {
+                model.range(lo..hi).map(|(_, v)| v).sum()
+            } else {
+                0
+            };
+            assert_eq!(t.range_summary(lo..hi), expected);
+            assert_eq!(
+                t.count_range(lo..hi),
+                if lo <= hi {
+                    model.range(lo..hi).count()
+                } else {
+                    0
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_commutative_summary() {
+        let mut t = RBTree::with_augment(Concat);
+        for k in [5, 3, 8, 1, 4, 7, 9, 2, 6] {
+            t.insert(k, ());
+        }
+        assert_eq!(t.summary(), "1,2,3,4,5,6,7,8,9,");
+        assert_eq!(t.range_summary(3..=6), "3,4,5,6,");
+        assert_eq!(
+            t.range_summary((Bound::Excluded(7), Bound::Unbounded)),
+            "8,9,"
+        );
+
+        let link = t.search_tree(&5);
+        t.remove(link);
+        assert_eq!(t.summary(), "1,2,3,4,6,7,8,9,");
+        t.clear();
+        assert_eq!(t.summary(), "");
+    }
+}
@@ -0,0 +1,382 @@
+//! 基于红黑树的区间映射
+use crate::collections::rbtree::{
+    augment::Augment,
+    iter::Iter,
+    map::RBTreeMap,
+    tree::{Link, LinkExt, RBTree},
+};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Range;
+
+/// 区间键，按 (start, end) 字典序排序
+#[derive(Clone, PartialEq, Eq)]
+struct Interval<K>(Range<K>);
+
+impl<K: Ord> Ord for Interval<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .start
+            .cmp(&other.0.start)
+            .then_with(|| self.0.end.cmp(&other.0.end))
+    }
+}
+
+impl<K: Ord> PartialOrd for Interval<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 子树内区间右端点的最大值，空子树为 None
+#[derive(Clone, Default)]
+struct MaxEnd;
+
+impl<K: Ord + Clone, V> Augment<Interval<K>, V> for MaxEnd {
+    type Summary = Option<K>;
+
+    fn identity(&self) -> Option<K> {
+        None
+    }
+
+    fn summarize(&self, key: &Interval<K>, _val: &V) -> Option<K> {
+        Some(key.0.end.clone())
+    }
+
+    fn combine(&self, left: &Option<K>, right: &Option<K>) -> Option<K> {
+        left.as_ref().max(right.as_ref()).cloned()
+    }
+}
+
+/// 区间映射（区间树）
+///
+/// 保存半开区间 `[start, end)` 到值的映射，区间按 (start, end) 排序，
+/// 完全相同的区间只保存一份。每个节点维护子树内右端点的最大值，
+/// 查询重叠区间时可以整棵剪掉不可能重叠的子树
+///
+/// ## Notes
+/// 插入、删除: O(log n)；查询: O(min(n, (k + 1) log n))，k 为结果数量
+pub struct IntervalMap<K: Ord + Clone, V> {
+    map: RBTreeMap<Interval<K>, V, MaxEnd>,
+}
+
+/// 与查询区间重叠的区间迭代器，按区间升序产出
+pub struct Overlapping<'a, K: Ord + Clone, V> {
+    tree: &'a RBTree<Interval<K>, V, MaxEnd>,
+    /// 待访问的节点：其左子树均已处理
+    stack: Vec<Link<Interval<K>, V, MaxEnd>>,
+    /// 区间右端点须大于 lo
+    lo: K,
+    /// 区间左端点须小于 hi（`hi_inclusive` 时为小于等于）
+    hi: K,
+    hi_inclusive: bool,
+}
+
+/// 按区间升序的迭代器
+pub struct IntervalIter<'a, K: Ord + Clone, V>(Iter<'a, Interval<K>, V, MaxEnd>);
+
+impl<K: Ord + Clone, V> IntervalMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            map: RBTreeMap::with_augment(MaxEnd),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// 插入区间，区间已存在时替换并返回旧值
+    ///
+    /// ## Panics
+    /// 区间为空（`start >= end`）时 panic
+    pub fn insert(&mut self, interval: Range<K>, value: V) -> Option<V> {
+        assert!(
+            interval.start < interval.end,
+            "interval must be non-empty (start < end)"
+        );
+        self.map.insert(Interval(interval), value)
+    }
+
+    /// 获取与 interval 完全相同的区间对应的值
+    pub fn get(&self, interval: &Range<K>) -> Option<&V> {
+        self.map.get(&Interval(interval.clone()))
+    }
+
+    /// 获取与 interval 完全相同的区间对应的值的可变引用
+    ///
+    /// 摘要只依赖区间端点，修改值不影响查询
+    pub fn get_mut(&mut self, interval: &Range<K>) -> Option<&mut V> {
+        let link = self.map.tree.search_tree(&Interval(interval.clone()))?;
+        unsafe { Some(&mut (*link.as_ptr()).val) }
+    }
+
+    pub fn contains_interval(&self, interval: &Range<K>) -> bool {
+        self.get(interval).is_some()
+    }
+
+    /// 删除与 interval 完全相同的区间，返回其值
+    pub fn remove_interval(&mut self, interval: &Range<K>) -> Option<V> {
+        self.map.remove(&Interval(interval.clone()))
+    }
+
+    /// 与 `[range.start, range.end)` 重叠的所有区间，空查询区间不与任何区间重叠
+    pub fn overlapping(&self, range: Range<K>) -> Overlapping<'_, K, V> {
+        if range.start >= range.end {
+            return Overlapping::empty(&self.map.tree, range.start, range.end, false);
+        }
+        Overlapping::new(&self.map.tree, range.start, range.end, false)
+    }
+
+    /// 包含 point 的所有区间（`start <= point < end`）
+    pub fn containing(&self, point: &K) -> Overlapping<'_, K, V> {
+        Overlapping::new(&self.map.tree, point.clone(), point.clone(), true)
+    }
+
+    /// 是否存在与 range 重叠的区间
+    pub fn overlaps_any(&self, range: Range<K>) -> bool {
+        self.overlapping(range).next().is_some()
+    }
+
+    /// 所有区间中最大的右端点
+    pub fn max_end(&self) -> Option<&K> {
+        self.map.summary().as_ref()
+    }
+
+    pub fn iter(&self) -> IntervalIter<'_, K, V> {
+        IntervalIter(self.map.iter())
+    }
+}
+
+impl<K: Ord + Clone, V> Default for IntervalMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Clone for IntervalMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K: Ord + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for IntervalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Clone, V> FromIterator<(Range<K>, V)> for IntervalMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (Range<K>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord + Clone, V> Extend<(Range<K>, V)> for IntervalMap<K, V> {
+    fn extend<I: IntoIterator<Item = (Range<K>, V)>>(&mut self, iter: I) {
+        for (interval, value) in iter {
+            self.insert(interval, value);
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V> IntoIterator for &'a IntervalMap<K, V> {
+    type Item = (&'a Range<K>, &'a V);
+    type IntoIter = IntervalIter<'a, K, V>;
+
+    fn into_iter(self) -> IntervalIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Overlapping<'a, K, V> {
+    fn new(tree: &'a RBTree<Interval<K>, V, MaxEnd>, lo: K, hi: K, hi_inclusive: bool) -> Self {
+        let mut it = Self::empty(tree, lo, hi, hi_inclusive);
+        it.push_left(tree.root);
+        it
+    }
+
+    fn empty(tree: &'a RBTree<Interval<K>, V, MaxEnd>, lo: K, hi: K, hi_inclusive: bool) -> Self {
+        Self {
+            tree,
+            stack: Vec::new(),
+            lo,
+            hi,
+            hi_inclusive,
+        }
+    }
+
+    /// 子树中是否可能存在右端点大于 lo 的区间
+    fn reaches_lo(&self, x: Link<Interval<K>, V, MaxEnd>) -> bool {
+        unsafe {
+            (*x.ptr())
+                .summary
+                .as_ref()
+                .is_some_and(|end| *end > self.lo)
+        }
+    }
+
+    fn starts_before_hi(&self, start: &K) -> bool {
+        if self.hi_inclusive {
+            *start <= self.hi
+        } else {
+            *start < self.hi
+        }
+    }
+
+    /// 沿左链下降，跳过右端点全部不超过 lo 的子树
+    fn push_left(&mut self, mut x: Link<Interval<K>, V, MaxEnd>) {
+        while x != self.tree.nil && self.reaches_lo(x) {
+            self.stack.push(x);
+            x = unsafe { (*x.ptr()).lch };
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Iterator for Overlapping<'a, K, V> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(x) = self.stack.pop() {
+            let node = x.ptr();
+            let interval: &'a Range<K> = unsafe { &(*node).key.0 };
+            if !self.starts_before_hi(&interval.start) {
+                // 栈中其余节点与其右子树的左端点都不更小
+                self.stack.clear();
+                return None;
+            }
+            self.push_left(unsafe { (*node).rch });
+            if interval.end > self.lo {
+                return Some((interval, unsafe { &(*node).val }));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Iterator for IntervalIter<'a, K, V> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (&k.0, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Ord + Clone, V> DoubleEndedIterator for IntervalIter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (&k.0, v))
+    }
+}
+
+impl<K: Ord + Clone, V> ExactSizeIterator for IntervalIter<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<'a>(it: impl Iterator<Item = (&'a Range<i32>, &'a char)>) -> Vec<(i32, i32, char)> {
+        it.map(|(r, v)| (r.start, r.end, *v)).collect()
+    }
+
+    #[test]
+    fn test_calendar() {
+        let mut cal = IntervalMap::new();
+        cal.insert(9..10, 'a');
+        cal.insert(9..12, 'b');
+        cal.insert(13..14, 'c');
+        cal.insert(11..15, 'd');
+        assert_eq!(cal.len(), 4);
+        assert_eq!(cal.max_end(), Some(&15));
+
+        assert_eq!(
+            collect(cal.overlapping(10..13)),
+            [(9, 12, 'b'), (11, 15, 'd')]
+        );
+        // 半开区间：首尾相接不算重叠
+        assert_eq!(collect(cal.overlapping(14..20)), [(11, 15, 'd')]);
+        assert!(!cal.overlaps_any(15..16));
+        assert_eq!(collect(cal.overlapping(12..12)), []);
+
+        assert_eq!(collect(cal.containing(&9)), [(9, 10, 'a'), (9, 12, 'b')]);
+        assert_eq!(collect(cal.containing(&10)), [(9, 12, 'b')]);
+        assert_eq!(collect(cal.containing(&13)), [(11, 15, 'd'), (13, 14, 'c')]);
+        assert_eq!(collect(cal.containing(&15)), []);
+
+        assert_eq!(cal.insert(9..12, 'B'), Some('b'));
+        *cal.get_mut(&(13..14)).unwrap() = 'C';
+        assert_eq!(cal.remove_interval(&(11..15)), Some('d'));
+        assert_eq!(cal.remove_interval(&(11..15)), None);
+        assert_eq!(cal.max_end(), Some(&14));
+        assert_eq!(
+            collect(cal.iter()),
+            [(9, 10, 'a'), (9, 12, 'B'), (13, 14, 'C')]
+        );
+        assert_eq!(format!("{cal:?}"), "{9..10: 'a', 9..12: 'B', 13..14: 'C'}");
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut x = 99u64;
+        let mut next = move |m: u64| {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((x >> 33) % m) as i32
+        };
+
+        let mut map = IntervalMap::new();
+        let mut naive: Vec<Range<i32>> = Vec::new();
+        for step in 0..600 {
+            let start = next(1000);
+            let r = start..start + 1 + next(60);
+            if step % 3 == 2 && !naive.is_empty() {
+                let victim = naive.swap_remove(next(naive.len() as u64) as usize);
+                assert_eq!(map.remove_interval(&victim), Some(victim.start));
+            } else if !naive.contains(&r) {
+                naive.push(r.clone());
+                map.insert(r.clone(), r.start);
+            }
+
+            let q = next(1000);
+            let query = q..q + 1 + next(80);
+            let mut expected: Vec<_> = naive
+                .iter()
+                .filter(|iv| iv.start < query.end && query.start < iv.end)
+                .cloned()
+                .collect();
+            expected.sort_by_key(|iv| (iv.start, iv.end));
+            let got: Vec<_> = map.overlapping(query).map(|(r, _)| r.clone()).collect();
+            assert_eq!(got, expected);
+
+            let mut expected: Vec<_> = naive.iter().filter(|iv| iv.contains(&q)).cloned().collect();
+            expected.sort_by_key(|iv| (iv.start, iv.end));
+            let got: Vec<_> = map.containing(&q).map(|(r, _)| r.clone()).collect();
+            assert_eq!(got, expected);
+        }
+        assert_eq!(map.len(), naive.len());
+        assert_eq!(map.max_end(), naive.iter().map(|r| &r.end).max());
+    }
+
+    #[test]
+    #[should_panic(expected = "non-empty")]
+    fn test_empty_interval_panics() {
+        let mut map = IntervalMap::new();
+        map.insert(5..5, ());
+    }
+}
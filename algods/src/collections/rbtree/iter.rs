@@ -1,45 +1,112 @@
-use crate::collections::rbtree::tree::{Link, RBTree};
+use crate::collections::rbtree::{
+    augment::Augment,
+    map::RBTreeMap,
+    tree::{Link, RBTree},
+};
 
-pub struct Iter<'a, K, V> {
-    tree: &'a RBTree<K, V>,
-    next: Link<K, V>,
+/// 按键升序的不可变迭代器，可从两端迭代
+pub struct Iter<'a, K, V, A: Augment<K, V> = ()> {
+    tree: &'a RBTree<K, V, A>,
+    front: Link<K, V, A>,
+    back: Link<K, V, A>,
+    /// 尚未产出的节点数
+    len: usize,
 }
 
+/// 按键升序的可变迭代器，可从两端迭代
 pub struct IterMut<'a, K, V> {
     tree: &'a mut RBTree<K, V>,
-    next: Link<K, V>,
+    front: Link<K, V>,
+    back: Link<K, V>,
+    len: usize,
 }
 
-pub struct Keys<'a, K, V>(pub Iter<'a, K, V>);
-pub struct Values<'a, K, V>(pub Iter<'a, K, V>);
+/// 按键升序、拥有所有权的迭代器
+///
+/// ## Notes
+/// 构造时一次性按中序取出全部键值对并释放节点，时间复杂度 O(n)
+pub struct IntoIter<K, V>(pub(crate) std::vec::IntoIter<(K, V)>);
+
+/// [`RBTreeMap::extract_if`] 返回的惰性迭代器
+pub struct ExtractIf<'a, K, V, A: Augment<K, V>, F> {
+    map: &'a mut RBTreeMap<K, V, A>,
+    /// 下一个待检查的节点
+    cursor: Link<K, V, A>,
+    pred: F,
+    /// 是否有节点被检查后保留（其值可能已被 `pred` 修改）
+    kept: bool,
+}
+
+pub struct Keys<'a, K, V, A: Augment<K, V> = ()>(pub Iter<'a, K, V, A>);
+pub struct Values<'a, K, V, A: Augment<K, V> = ()>(pub Iter<'a, K, V, A>);
 pub struct ValuesMut<'a, K, V>(pub IterMut<'a, K, V>);
 
-impl<'a, K: Ord, V> Iter<'a, K, V> {
-    pub fn new(tree: &'a RBTree<K, V>) -> Self {
-        let next = tree.min(tree.root);
-        Self { tree, next }
+impl<'a, K: Ord, V, A: Augment<K, V>> Iter<'a, K, V, A> {
+    pub fn new(tree: &'a RBTree<K, V, A>) -> Self {
+        Self {
+            front: tree.min(tree.root),
+            back: tree.max(tree.root),
+            len: tree.len(),
+            tree,
+        }
     }
 }
 
-impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+impl<'a, K: Ord, V, A: Augment<K, V>> Iterator for Iter<'a, K, V, A> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.front.unwrap().as_ptr();
+            self.front = self.tree.successor(self.front);
+            Some((&(*node).key, &(*node).val))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> DoubleEndedIterator for Iter<'_, K, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
         unsafe {
-            if self.next == self.tree.nil {
-                return None;
-            }
-            let node = self.next.unwrap().as_ptr();
-            self.next = self.tree.successor(Some(self.next.unwrap()));
+            let node = self.back.unwrap().as_ptr();
+            self.back = self.tree.predecessor(self.back);
             Some((&(*node).key, &(*node).val))
         }
     }
 }
 
+impl<K: Ord, V, A: Augment<K, V>> ExactSizeIterator for Iter<'_, K, V, A> {}
+
+impl<K, V, A: Augment<K, V>> Clone for Iter<'_, K, V, A> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+}
+
 impl<'a, K: Ord, V> IterMut<'a, K, V> {
     pub fn new(tree: &'a mut RBTree<K, V>) -> Self {
-        let next = tree.min(tree.root);
-        Self { tree, next }
+        Self {
+            front: tree.min(tree.root),
+            back: tree.max(tree.root),
+            len: tree.len(),
+            tree,
+        }
     }
 }
 
@@ -47,34 +114,158 @@ impl<'a, K: Ord, V> Iterator for IterMut<'a, K, V> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let node = self.front.unwrap().as_ptr();
+            self.front = self.tree.successor(self.front);
+            Some((&(*node).key, &mut (*node).val))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
         unsafe {
-            if self.next == self.tree.nil {
-                return None;
-            }
-            let node = self.next.unwrap().as_ptr();
-            self.next = self.tree.successor(Some(self.next.unwrap()));
+            let node = self.back.unwrap().as_ptr();
+            self.back = self.tree.predecessor(self.back);
             Some((&(*node).key, &mut (*node).val))
         }
     }
 }
 
-impl<'a, K: Ord, V> Iterator for Keys<'a, K, V> {
+impl<K: Ord, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<'a, K: Ord, V, A: Augment<K, V>> Iterator for Keys<'a, K, V, A> {
     type Item = &'a K;
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(|(k, _)| k)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> DoubleEndedIterator for Keys<'_, K, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> ExactSizeIterator for Keys<'_, K, V, A> {}
+
+impl<K, V, A: Augment<K, V>> Clone for Keys<'_, K, V, A> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
 }
 
-impl<'a, K: Ord, V> Iterator for Values<'a, K, V> {
+impl<'a, K: Ord, V, A: Augment<K, V>> Iterator for Values<'a, K, V, A> {
     type Item = &'a V;
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(|(_, v)| v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> DoubleEndedIterator for Values<'_, K, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, v)| v)
+    }
 }
 
+impl<K: Ord, V, A: Augment<K, V>> ExactSizeIterator for Values<'_, K, V, A> {}
+
 impl<'a, K: Ord, V> Iterator for ValuesMut<'a, K, V> {
     type Item = &'a mut V;
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(|(_, v)| v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Ord, V> DoubleEndedIterator for ValuesMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<'a, K: Ord, V, A: Augment<K, V>, F> ExtractIf<'a, K, V, A, F> {
+    pub(crate) fn new(map: &'a mut RBTreeMap<K, V, A>, pred: F) -> Self {
+        let cursor = map.tree.min(map.tree.root);
+        Self {
+            map,
+            cursor,
+            pred,
+            kept: false,
+        }
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>, F> Iterator for ExtractIf<'_, K, V, A, F>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pred, kept) = (&mut self.pred, &mut self.kept);
+        self.map.tree.extract_next(&mut self.cursor, |k, v| {
+            let hit = pred(k, v);
+            *kept |= !hit;
+            hit
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.map.len()))
+    }
+}
+
+impl<K, V, A: Augment<K, V>, F> Drop for ExtractIf<'_, K, V, A, F> {
+    fn drop(&mut self) {
+        // 保留下来的值可能被修改过，重新计算摘要
+        if self.kept {
+            self.map.tree.pull_all();
+        }
+    }
 }
@@ -1,19 +1,26 @@
 use crate::collections::rbtree::{
     OccupiedEntry, VacantEntry,
+    augment::Augment,
     entry::Entry,
     iter::*,
     range::*,
     tree::{EntrySearch, RBTree},
 };
 use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
 use std::ops::RangeBounds;
 
 /// 红黑树Map，api仿std::collections::BTreeMap
-pub struct RBTreeMap<K, V> {
-    pub(crate) tree: RBTree<K, V>,
+///
+/// 节点维护子树大小，支持 O(log n) 的按序选取与排名；
+/// 通过 [`Augment`] 还可以维护任意子树摘要（如值之和、区间右端点最大值）。
+/// 摘要可能依赖值，因此带摘要的 map 不提供值的可变引用，改用 [`RBTreeMap::update`] 修改
+pub struct RBTreeMap<K, V, A: Augment<K, V> = ()> {
+    pub(crate) tree: RBTree<K, V, A>,
 }
 
-impl<K: Ord, V> RBTreeMap<K, V> {
+impl<K: Ord, V, A: Augment<K, V>> RBTreeMap<K, V, A> {
     pub fn len(&self) -> usize {
         self.tree.len()
     }
@@ -22,22 +29,19 @@ impl<K: Ord, V> RBTreeMap<K, V> {
         self.tree.clear();
     }
 
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         unsafe { self.tree.search_tree(key).map(|link| &(*link.as_ptr()).val) }
     }
 
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        unsafe {
-            self.tree
-                .search_tree(key)
-                .map(|link| &mut (*link.as_ptr()).val)
-        }
-    }
-
     pub fn insert(&mut self, key: K, val: V) -> Option<V> {
         if let Some(link) = self.tree.search_tree(&key) {
             unsafe {
                 let old = std::mem::replace(&mut (*link.as_ptr()).val, val);
+                self.tree.pull_to_root(Some(link));
                 Some(old)
             }
         } else {
@@ -46,13 +50,28 @@ impl<K: Ord, V> RBTreeMap<K, V> {
         }
     }
 
-    pub fn remove(&mut self, key: &K) -> Option<V> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let link = self.tree.search_tree(key)?;
-        let (_, val) = self.tree.remove(Some(link))?;
-        Some(val)
+        self.tree.remove(Some(link))
     }
 
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.tree.search_tree(key).is_some()
     }
 
@@ -84,6 +103,199 @@ impl<K: Ord, V> RBTreeMap<K, V> {
         }
     }
 
+    pub fn iter(&self) -> Iter<'_, K, V, A> {
+        Iter::new(&self.tree)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V, A> {
+        Keys(Iter::new(&self.tree))
+    }
+
+    pub fn values(&self) -> Values<'_, K, V, A> {
+        Values(Iter::new(&self.tree))
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range::new(&self.tree, range)
+    }
+
+    /// 按键升序的第 k 个（从 0 开始）键值对
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn nth(&self, k: usize) -> Option<(&K, &V)> {
+        let link = self.tree.select(k);
+        if link == self.tree.nil {
+            return None;
+        }
+        unsafe {
+            let node = link.unwrap().as_ref();
+            Some((&node.key, &node.val))
+        }
+    }
+
+    /// 严格小于 key 的键的数量；key 存在时即其在升序中的下标
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.tree.count_below(key, false)
+    }
+
+    /// 落在范围内的键的数量
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n)
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.tree.count_range(range)
+    }
+
+    /// 所有键值对按键升序合并的摘要，空 map 为单位元
+    pub fn summary(&self) -> &A::Summary {
+        self.tree.summary()
+    }
+
+    /// 范围内键值对按键升序合并的摘要
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(log n) 次合并
+    pub fn range_summary<Q, R>(&self, range: R) -> A::Summary
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.tree.range_summary(range)
+    }
+
+    /// 移除并返回最小的键值对
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let link = self.tree.min(self.tree.root);
+        self.tree.remove(link)
+    }
+
+    /// 移除并返回最大的键值对
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let link = self.tree.max(self.tree.root);
+        self.tree.remove(link)
+    }
+
+    /// 在 key 处一分为二：返回所有键 >= key 的部分，自身保留键 < key 的部分
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n)，两侧都按有序序列重建为平衡树
+    pub fn split_off<Q>(&mut self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        A: Clone,
+    {
+        let mut items = self.tree.take_all();
+        let at = items.partition_point(|(k, _)| k.borrow() < key);
+        let tail = items.split_off(at);
+        self.tree.build_sorted(items);
+        let mut other = Self::with_augment(self.tree.aug.clone());
+        other.tree.build_sorted(tail);
+        other
+    }
+
+    /// 将 other 的全部键值对移入自身，other 变为空；键重复时保留 other 的值
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n + m)，归并两个有序序列后重建平衡树
+    pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+        let left = self.tree.take_all();
+        let right = other.tree.take_all();
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+        loop {
+            let ord = match (left.peek(), right.peek()) {
+                (Some((a, _)), Some((b, _))) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ord {
+                Ordering::Less => merged.extend(left.next()),
+                Ordering::Greater => merged.extend(right.next()),
+                Ordering::Equal => {
+                    left.next();
+                    merged.extend(right.next());
+                }
+            }
+        }
+        self.tree.build_sorted(merged);
+    }
+
+    /// 只保留使 `f` 返回 true 的键值对
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n + d log n)，d 为删除的数量
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.extract_if(|k, v| !f(k, v)).for_each(drop);
+    }
+
+    /// 按升序惰性地移除并返回使 `pred` 返回 true 的键值对
+    ///
+    /// 迭代器提前丢弃时，尚未访问的键值对保持不变
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, A, F>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// 修改 key 对应的值，并更新受影响的摘要
+    ///
+    /// ## 返回
+    /// key 存在时返回 `f` 的结果，否则返回 None
+    pub fn update<Q, F, R>(&mut self, key: &Q, f: F) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        F: FnOnce(&mut V) -> R,
+    {
+        let link = self.tree.search_tree(key)?;
+        let r = unsafe { f(&mut (*link.as_ptr()).val) };
+        self.tree.pull_to_root(Some(link));
+        Some(r)
+    }
+}
+
+/// 值的可变访问只对不带摘要的 map 开放
+impl<K: Ord, V> RBTreeMap<K, V> {
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        unsafe {
+            self.tree
+                .search_tree(key)
+                .map(|link| &mut (*link.as_ptr()).val)
+        }
+    }
+
     pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         match self.tree.search_entry(&key) {
             EntrySearch::Occupied(link) => Entry::Occupied(OccupiedEntry {
@@ -99,35 +311,14 @@ impl<K: Ord, V> RBTreeMap<K, V> {
         }
     }
 
-    pub fn iter(&self) -> Iter<'_, K, V> {
-        Iter::new(&self.tree)
-    }
-
     pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
         IterMut::new(&mut self.tree)
     }
 
-    pub fn keys(&self) -> Keys<'_, K, V> {
-        Keys(Iter::new(&self.tree))
-    }
-
-    pub fn values(&self) -> Values<'_, K, V> {
-        Values(Iter::new(&self.tree))
-    }
-
     pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         ValuesMut(IterMut::new(&mut self.tree))
     }
 
-    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
-    where
-        K: Borrow<Q>,
-        Q: Ord + ?Sized,
-        R: RangeBounds<Q>,
-    {
-        Range::new(&self.tree, range)
-    }
-
     pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, V>
     where
         K: Borrow<Q>,
@@ -138,17 +329,108 @@ impl<K: Ord, V> RBTreeMap<K, V> {
     }
 }
 
-impl<K: Ord + Default, V: Default> RBTreeMap<K, V> {
+impl<K, V> RBTreeMap<K, V> {
     pub fn new() -> Self {
         Self {
-            tree: RBTree::new(K::default(), V::default()),
+            tree: RBTree::new(),
+        }
+    }
+}
+
+impl<K, V, A: Augment<K, V>> RBTreeMap<K, V, A> {
+    /// 创建空 map，按 `aug` 维护子树摘要
+    pub fn with_augment(aug: A) -> Self {
+        Self {
+            tree: RBTree::with_augment(aug),
         }
     }
 }
 
-impl<K: Ord + Default, V: Default> Default for RBTreeMap<K, V> {
+impl<K, V, A: Augment<K, V> + Default> Default for RBTreeMap<K, V, A> {
     fn default() -> Self {
-        Self::new()
+        Self::with_augment(A::default())
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V> + Default> FromIterator<(K, V)> for RBTreeMap<K, V, A> {
+    /// 排序后一次性建树，键重复时保留最后出现的值
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut items: Vec<(K, V)> = iter.into_iter().collect();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut dedup: Vec<(K, V)> = Vec::with_capacity(items.len());
+        for (k, v) in items {
+            match dedup.last_mut() {
+                Some(last) if last.0 == k => *last = (k, v),
+                _ => dedup.push((k, v)),
+            }
+        }
+        let mut map = Self::default();
+        map.tree.build_sorted(dedup);
+        map
+    }
+}
+
+impl<K: Ord, V, A: Augment<K, V>> Extend<(K, V)> for RBTreeMap<K, V, A> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K: Ord + Copy, V: Copy, A: Augment<K, V>> Extend<(&'a K, &'a V)> for RBTreeMap<K, V, A> {
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&k, &v)| (k, v)));
+    }
+}
+
+impl<K: Ord + Clone, V: Clone, A: Augment<K, V> + Clone> Clone for RBTreeMap<K, V, A> {
+    fn clone(&self) -> Self {
+        let items = self.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        let mut map = Self::with_augment(self.tree.aug.clone());
+        map.tree.build_sorted(items);
+        map
+    }
+}
+
+impl<K: Ord, V: PartialEq, A: Augment<K, V>> PartialEq for RBTreeMap<K, V, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V: Eq, A: Augment<K, V>> Eq for RBTreeMap<K, V, A> {}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug, A: Augment<K, V>> fmt::Debug for RBTreeMap<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, A: Augment<K, V>> IntoIterator for RBTreeMap<K, V, A> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> IntoIter<K, V> {
+        IntoIter(self.tree.take_all().into_iter())
+    }
+}
+
+impl<'a, K: Ord, V, A: Augment<K, V>> IntoIterator for &'a RBTreeMap<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, A>;
+
+    fn into_iter(self) -> Iter<'a, K, V, A> {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a mut RBTreeMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
     }
 }
 
@@ -324,4 +606,298 @@ mod tests {
         let range: Vec<_> = m.range_mut(2..4).collect();
         assert_eq!(range, vec![(&2, &mut 20), (&3, &mut 30)]);
     }
+
+    #[test]
+    fn test_keys_without_default() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Handle(u32);
+
+        let mut m = RBTreeMap::new();
+        for i in [3, 1, 2] {
+            m.insert(Handle(i), vec![i]);
+        }
+        assert_eq!(m.get(&Handle(2)), Some(&vec![2]));
+        assert_eq!(m.first_key_value().map(|(k, _)| k), Some(&Handle(1)));
+        assert_eq!(m.remove(&Handle(1)), Some(vec![1]));
+        assert_eq!(m.len(), 2);
+    }
+
+    #[test]
+    fn test_borrow_lookup() {
+        use std::ops::Bound;
+
+        let mut m: RBTreeMap<String, i32> = RBTreeMap::new();
+        m.insert("apple".to_string(), 1);
+        m.insert("banana".to_string(), 2);
+        m.insert("cherry".to_string(), 3);
+
+        assert_eq!(m.get("banana"), Some(&2));
+        assert!(m.contains_key("apple"));
+        *m.get_mut("cherry").unwrap() += 10;
+        assert_eq!(m.remove_entry("cherry"), Some(("cherry".to_string(), 13)));
+        assert_eq!(m.remove("durian"), None);
+
+        let keys: Vec<_> = m
+            .range::<str, _>((Bound::Included("b"), Bound::Unbounded))
+            .map(|(k, _)| k.as_str())
+            .collect();
+        assert_eq!(keys, ["banana"]);
+    }
+
+    #[test]
+    fn test_range_edges() {
+        let mut m = RBTreeMap::new();
+        for i in 1..=5 {
+            m.insert(i, i * 10);
+        }
+        // 上界超过最大键、下界小于最小键
+        let all: Vec<_> = m.range(0..100).map(|(k, _)| *k).collect();
+        assert_eq!(all, [1, 2, 3, 4, 5]);
+        assert_eq!(m.range(6..).count(), 0);
+        assert_eq!(m.range(..1).count(), 0);
+        let (lo, hi) = (4, 2);
+        assert_eq!(m.range(lo..hi).count(), 0);
+        assert_eq!(m.range_mut(lo..hi).count(), 0);
+        // 下界超过所有键，而上界落在真实节点上
+        let (lo, hi) = (6, 3);
+        assert_eq!(m.range(lo..hi).count(), 0);
+        assert_eq!(m.range(lo..hi).next_back(), None);
+        assert_eq!(m.range_mut(lo..=hi).count(), 0);
+
+        let empty: RBTreeMap<i32, i32> = RBTreeMap::new();
+        assert_eq!(empty.range(..).count(), 0);
+    }
+
+    #[test]
+    fn test_drops_each_value_once() {
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        {
+            let mut m = RBTreeMap::new();
+            for i in 0..100 {
+                m.insert(i, Rc::clone(&tracker));
+            }
+            for i in 0..50 {
+                m.remove(&i);
+            }
+            assert_eq!(Rc::strong_count(&tracker), 51);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_order_statistics() {
+        // 排行榜：分数 -> 玩家
+        let mut m = RBTreeMap::new();
+        for (score, name) in [
+            (870, "ann"),
+            (920, "bob"),
+            (640, "cat"),
+            (990, "dan"),
+            (700, "eve"),
+        ] {
+            m.insert(score, name);
+        }
+
+        assert_eq!(m.nth(0), Some((&640, &"cat")));
+        assert_eq!(m.nth(3), Some((&920, &"bob")));
+        assert_eq!(m.nth(5), None);
+        assert_eq!(m.rank(&870), 2);
+        assert_eq!(m.rank(&800), 2);
+        assert_eq!(m.rank(&0), 0);
+        assert_eq!(m.count_range(700..=920), 3);
+        assert_eq!(m.count_range(..700), 1);
+
+        m.remove(&640);
+        m.entry(800).or_insert("fay");
+        assert_eq!(m.rank(&870), 2);
+        assert_eq!(m.nth(1), Some((&800, &"fay")));
+        let ranks: Vec<_> = m.keys().map(|k| m.rank(k)).collect();
+        assert_eq!(ranks, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_range_sum_map() {
+        #[derive(Default)]
+        struct Sum;
+
+        impl Augment<u32, u64> for Sum {
+            type Summary = u64;
+
+            fn identity(&self) -> u64 {
+                0
+            }
+
+            fn summarize(&self, _key: &u32, val: &u64) -> u64 {
+                *val
+            }
+
+            fn combine(&self, left: &u64, right: &u64) -> u64 {
+                left + right
+            }
+        }
+
+        // 按天记录的销量
+        let mut m: RBTreeMap<u32, u64, Sum> = RBTreeMap::default();
+        for day in 1..=30 {
+            m.insert(day, day as u64 * 10);
+        }
+        assert_eq!(*m.summary(), 4650);
+        assert_eq!(m.range_summary(1..=7), 280);
+        assert_eq!(m.range_summary(29..), 590);
+
+        // 覆盖、原地修改与删除后摘要保持一致
+        assert_eq!(m.insert(1, 0), Some(10));
+        assert_eq!(m.update(&2, |v| std::mem::replace(v, 100)), Some(20));
+        assert_eq!(m.update(&31, |v| *v += 1), None);
+        m.remove(&7);
+        assert_eq!(m.range_summary(1..=7), 100 + 30 + 40 + 50 + 60);
+        assert_eq!(*m.summary(), 4650 - 10 + 80 - 70);
+        assert_eq!(m.nth(6), Some((&8, &80)));
+    }
+
+    #[test]
+    fn test_double_ended_iteration() {
+        let m: RBTreeMap<i32, i32> = (1..=5).map(|k| (k, k * 10)).collect();
+
+        let mut it = m.iter();
+        assert_eq!(it.len(), 5);
+        assert_eq!(it.next_back(), Some((&5, &50)));
+        assert_eq!(it.next(), Some((&1, &10)));
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.rev().map(|(k, _)| *k).collect::<Vec<_>>(), [4, 3, 2]);
+
+        assert_eq!(m.keys().rev().copied().collect::<Vec<_>>(), [5, 4, 3, 2, 1]);
+        assert_eq!(m.values().next_back(), Some(&50));
+
+        let mut r = m.range(2..=4);
+        assert_eq!(r.next_back(), Some((&4, &40)));
+        assert_eq!(r.next(), Some((&2, &20)));
+        assert_eq!(r.next_back(), Some((&3, &30)));
+        assert_eq!((r.next(), r.next_back()), (None, None));
+        assert_eq!(m.range(6..).next_back(), None);
+
+        let mut m = m;
+        for (_, v) in m.range_mut(..3).rev() {
+            *v += 1;
+        }
+        assert_eq!(m.values_mut().next_back().map(|v| *v), Some(50));
+        assert_eq!(m.get(&2), Some(&21));
+
+        let owned: Vec<_> = m.into_iter().rev().collect();
+        assert_eq!(owned, [(5, 50), (4, 40), (3, 30), (2, 21), (1, 11)]);
+    }
+
+    #[test]
+    fn test_split_off_and_append() {
+        let mut a: RBTreeMap<i32, &str> = [(1, "a"), (3, "c"), (5, "e"), (7, "g")]
+            .into_iter()
+            .collect();
+        let mut b = a.split_off(&4);
+        assert_eq!(a.keys().copied().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(b.keys().copied().collect::<Vec<_>>(), [5, 7]);
+        assert_eq!(b.nth(1), Some((&7, &"g")));
+
+        let mut c: RBTreeMap<i32, &str> = [(3, "C"), (4, "D"), (9, "I")].into_iter().collect();
+        a.append(&mut c);
+        assert!(c.is_empty());
+        a.append(&mut b);
+        assert_eq!(
+            a.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            [(1, "a"), (3, "C"), (4, "D"), (5, "e"), (7, "g"), (9, "I")]
+        );
+        assert_eq!(a.rank(&9), 5);
+        assert_eq!(a.split_off(&100).len(), 0);
+        assert_eq!(a.split_off(&0).len(), 6);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_retain_and_extract_if() {
+        let mut m: RBTreeMap<i32, i32> = (0..10).map(|k| (k, k)).collect();
+        m.retain(|k, v| {
+            *v *= 10;
+            k % 3 != 0
+        });
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 4, 5, 7, 8]);
+        assert_eq!(m.get(&8), Some(&80));
+
+        let mut it = m.extract_if(|k, _| *k > 3);
+        assert_eq!(it.next(), Some((4, 40)));
+        drop(it);
+        // 提前丢弃时其余键值对保持不变
+        assert_eq!(m.keys().copied().collect::<Vec<_>>(), [1, 2, 5, 7, 8]);
+        assert_eq!(m.nth(2), Some((&5, &50)));
+    }
+
+    #[test]
+    fn test_bulk_ops_keep_summaries() {
+        #[derive(Default, Clone)]
+        struct Sum;
+
+        impl Augment<i32, i32> for Sum {
+            type Summary = i32;
+
+            fn identity(&self) -> i32 {
+                0
+            }
+
+            fn summarize(&self, _key: &i32, val: &i32) -> i32 {
+                *val
+            }
+
+            fn combine(&self, left: &i32, right: &i32) -> i32 {
+                left + right
+            }
+        }
+
+        let mut m: RBTreeMap<i32, i32, Sum> = (1..=10).map(|k| (k, k)).collect();
+        assert_eq!(*m.summary(), 55);
+        let mut tail = m.split_off(&6);
+        assert_eq!((*m.summary(), *tail.summary()), (15, 40));
+
+        // extract_if 中修改保留下来的值后摘要仍然正确
+        let removed: Vec<_> = tail
+            .extract_if(|k, v| {
+                *v *= 2;
+                *k == 8
+            })
+            .collect();
+        assert_eq!(removed, [(8, 16)]);
+        assert_eq!(*tail.summary(), 2 * (6 + 7 + 9 + 10));
+
+        m.append(&mut tail);
+        assert_eq!(*m.summary(), 15 + 64);
+        assert_eq!(m.range_summary(5..=7), 5 + 12 + 14);
+        let copy = m.clone();
+        assert_eq!(*copy.summary(), *m.summary());
+        assert_eq!(copy, m);
+    }
+
+    #[test]
+    fn test_std_traits() {
+        let m: RBTreeMap<i32, char> = [(2, 'b'), (1, 'x'), (1, 'a')].into_iter().collect();
+        assert_eq!(format!("{m:?}"), "{1: 'a', 2: 'b'}");
+
+        let mut n = m.clone();
+        assert_eq!(m, n);
+        n.extend([(3, 'c')]);
+        assert_ne!(m, n);
+        n.extend(&m);
+        assert_eq!(n.len(), 3);
+
+        let mut total = 0;
+        for (k, _) in &n {
+            total += k;
+        }
+        assert_eq!(total, 6);
+        for (_, v) in &mut n {
+            *v = v.to_ascii_uppercase();
+        }
+        assert_eq!(n.values().collect::<String>(), "ABC");
+        assert_eq!(n.pop_first(), Some((1, 'A')));
+        assert_eq!(n.pop_last(), Some((3, 'C')));
+        assert_eq!(n.len(), 1);
+    }
 }
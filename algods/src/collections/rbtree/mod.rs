@@ -1,10 +1,16 @@
+pub mod augment;
 pub mod entry;
+pub mod interval;
 pub mod iter;
 pub mod map;
 pub mod range;
+pub mod set;
 pub mod tree;
 
+pub use augment::*;
 pub use entry::*;
+pub use interval::*;
 pub use iter::*;
 pub use map::*;
 pub use range::*;
+pub use set::*;
@@ -1,70 +1,58 @@
-use crate::collections::rbtree::tree::{Link, RBTree};
-use std::ops::{Bound, RangeBounds};
-
-/// 不可变范围
+use crate::collections::rbtree::{
+    augment::Augment,
+    tree::{Link, Node, RBTree},
+};
+use std::{
+    borrow::Borrow,
+    ops::{Bound, RangeBounds},
+};
+
+/// 不可变范围，可从两端迭代
 ///
 /// ## Notes
-/// - Range 使用 key-bound（只读，允许 &K）
-pub struct Range<'a, K, V> {
-    tree: &'a RBTree<K, V>,
-    next: Link<K, V>,
-    end: Bound<&'a K>,
+/// 构造时即定位首尾节点，迭代时只比较节点指针
+pub struct Range<'a, K, V, A: Augment<K, V> = ()> {
+    tree: &'a RBTree<K, V, A>,
+    /// 下一个从前端产出的节点，范围耗尽时与 `back` 同为 nil
+    front: Link<K, V, A>,
+    /// 下一个从后端产出的节点（包含）
+    back: Link<K, V, A>,
 }
 
-/// 可变范围
-///
-/// ## Nodes
-/// RangeMut 使用 node-bound（可变，避免 &K）
+/// 可变范围，可从两端迭代
 pub struct RangeMut<'a, K, V> {
     tree: &'a mut RBTree<K, V>,
-    next: Link<K, V>,
-    end: Link<K, V>,
+    front: Link<K, V>,
+    back: Link<K, V>,
 }
 
-impl<'a, K: Ord, V> Range<'a, K, V> {
-    pub fn new<Q, R>(tree: &'a RBTree<K, V>, range: R) -> Self
+impl<'a, K: Ord, V, A: Augment<K, V>> Range<'a, K, V, A> {
+    pub fn new<Q, R>(tree: &'a RBTree<K, V, A>, range: R) -> Self
     where
-        K: std::borrow::Borrow<Q>,
+        K: Borrow<Q>,
         Q: Ord + ?Sized,
         R: RangeBounds<Q>,
     {
-        unsafe {
-            let next = match range.start_bound() {
-                Bound::Included(k) => tree.find_ge(k),
-                Bound::Excluded(k) => tree.find_gt(k),
-                Bound::Unbounded => tree.min(tree.root),
-            };
-            let end = match range.end_bound() {
-                Bound::Included(k) => Bound::Included(tree.borrow_key(k)),
-                Bound::Excluded(k) => Bound::Excluded(tree.borrow_key(k)),
-                Bound::Unbounded => Bound::Unbounded,
-            };
-            Self { tree, next, end }
-        }
+        let (front, back) = tree.range_ends(range);
+        Self { tree, front, back }
     }
 }
 
-impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+impl<'a, K: Ord, V, A: Augment<K, V>> Iterator for Range<'a, K, V, A> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         unsafe {
-            if self.next == self.tree.nil {
-                return None;
-            }
-
-            let within_end = match &self.end {
-                Bound::Included(end_key) => &(*self.next.unwrap().as_ptr()).key <= *end_key,
-                Bound::Excluded(end_key) => &(*self.next.unwrap().as_ptr()).key < *end_key,
-                Bound::Unbounded => true,
-            };
-
-            if !within_end {
-                return None;
-            }
+            let node = self.tree.step(&mut self.front, &mut self.back, true)?;
+            Some((&(*node).key, &(*node).val))
+        }
+    }
+}
 
-            let node = self.next.unwrap().as_ptr();
-            self.next = self.tree.successor(self.next);
+impl<K: Ord, V, A: Augment<K, V>> DoubleEndedIterator for Range<'_, K, V, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let node = self.tree.step(&mut self.front, &mut self.back, false)?;
             Some((&(*node).key, &(*node).val))
         }
     }
@@ -73,23 +61,12 @@ impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
 impl<'a, K: Ord, V> RangeMut<'a, K, V> {
     pub fn new<Q, R>(tree: &'a mut RBTree<K, V>, range: R) -> Self
     where
-        K: std::borrow::Borrow<Q>,
+        K: Borrow<Q>,
         Q: Ord + ?Sized,
         R: RangeBounds<Q>,
     {
-        let next = match range.start_bound() {
-            Bound::Included(k) => tree.find_ge(k),
-            Bound::Excluded(k) => tree.find_gt(k),
-            Bound::Unbounded => tree.min(tree.root),
-        };
-
-        let end = match range.end_bound() {
-            Bound::Included(k) => tree.find_gt(k),
-            Bound::Excluded(k) => tree.find_ge(k),
-            Bound::Unbounded => tree.nil,
-        };
-
-        Self { tree, next, end }
+        let (front, back) = tree.range_ends(range);
+        Self { tree, front, back }
     }
 }
 
@@ -98,25 +75,26 @@ impl<'a, K: Ord, V> Iterator for RangeMut<'a, K, V> {
 
     fn next(&mut self) -> Option<Self::Item> {
         unsafe {
-            if self.next == self.end {
-                return None;
-            }
-            if self.next == self.tree.nil {
-                return None;
-            }
+            let node = self.tree.step(&mut self.front, &mut self.back, true)?;
+            Some((&(*node).key, &mut (*node).val))
+        }
+    }
+}
 
-            let node = self.next.unwrap().as_ptr();
-            self.next = self.tree.successor(self.next);
+impl<K: Ord, V> DoubleEndedIterator for RangeMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let node = self.tree.step(&mut self.front, &mut self.back, false)?;
             Some((&(*node).key, &mut (*node).val))
         }
     }
 }
 
-impl<K: Ord, V> RBTree<K, V> {
+impl<K: Ord, V, A: Augment<K, V>> RBTree<K, V, A> {
     /// 查找第一个 >= key 的节点
-    fn find_ge<Q: ?Sized + Ord>(&self, key: &Q) -> Link<K, V>
+    fn find_ge<Q: ?Sized + Ord>(&self, key: &Q) -> Link<K, V, A>
     where
-        K: std::borrow::Borrow<Q>,
+        K: Borrow<Q>,
     {
         unsafe {
             let mut result = self.nil;
@@ -137,9 +115,9 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 查找第一个 > key 的节点
-    fn find_gt<Q: ?Sized + Ord>(&self, key: &Q) -> Link<K, V>
+    fn find_gt<Q: ?Sized + Ord>(&self, key: &Q) -> Link<K, V, A>
     where
-        K: std::borrow::Borrow<Q>,
+        K: Borrow<Q>,
     {
         unsafe {
             let mut result = self.nil;
@@ -159,14 +137,76 @@ impl<K: Ord, V> RBTree<K, V> {
         }
     }
 
-    /// ## Safety
-    /// q must be <= max key in tree
-    unsafe fn borrow_key<Q: ?Sized + Ord>(&self, q: &Q) -> &K
+    /// 范围内的第一个节点与范围之后的第一个节点（均可能为 nil）
+    ///
+    /// 起点大于终点时返回空范围
+    pub(crate) fn range_links<Q, R>(&self, range: R) -> (Link<K, V, A>, Link<K, V, A>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let next = match range.start_bound() {
+            Bound::Included(k) => self.find_ge(k),
+            Bound::Excluded(k) => self.find_gt(k),
+            Bound::Unbounded => self.min(self.root),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(k) => self.find_gt(k),
+            Bound::Excluded(k) => self.find_ge(k),
+            Bound::Unbounded => self.nil,
+        };
+        // 下界超过所有键时区间为空；否则 next 只能从 end 处停下
+        if next == self.nil {
+            return (end, end);
+        }
+        unsafe {
+            if end != self.nil && (*next.unwrap().as_ptr()).key > (*end.unwrap().as_ptr()).key {
+                return (end, end);
+            }
+        }
+        (next, end)
+    }
+
+    /// 范围内的首尾节点（均包含），空范围时均为 nil
+    fn range_ends<Q, R>(&self, range: R) -> (Link<K, V, A>, Link<K, V, A>)
     where
-        K: std::borrow::Borrow<Q>,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
     {
-        let link = self.find_ge(q);
-        unsafe { &(*link.unwrap().as_ptr()).key }
+        let (first, end) = self.range_links(range);
+        if first == end {
+            return (self.nil, self.nil);
+        }
+        let last = if end == self.nil {
+            self.max(self.root)
+        } else {
+            self.predecessor(end)
+        };
+        (first, last)
+    }
+
+    /// 从 `[front, back]` 的一端取出一个节点，两端相遇后均置为 nil
+    fn step(
+        &self,
+        front: &mut Link<K, V, A>,
+        back: &mut Link<K, V, A>,
+        from_front: bool,
+    ) -> Option<*mut Node<K, V, A>> {
+        if *front == self.nil {
+            return None;
+        }
+        let node = if from_front { *front } else { *back };
+        if front == back {
+            *front = self.nil;
+            *back = self.nil;
+        } else if from_front {
+            *front = self.successor(node);
+        } else {
+            *back = self.predecessor(node);
+        }
+        node.map(|n| n.as_ptr())
     }
 }
 
@@ -228,7 +268,7 @@ mod tests {
     }
 
     fn new_i32_i32_tree() -> RBTree<i32, i32> {
-        RBTree::new(0, 0)
+        RBTree::new()
     }
 
     fn insert_seq(t: &mut RBTree<i32, i32>, xs: &[i32]) {
@@ -0,0 +1,587 @@
+use crate::collections::rbtree::{
+    iter::{IntoIter, Keys},
+    map::RBTreeMap,
+    range::Range,
+    tree::Link,
+};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Peekable;
+use std::ops::RangeBounds;
+
+/// 基于红黑树的有序集合，api仿std::collections::BTreeSet
+pub struct RBTreeSet<T> {
+    map: RBTreeMap<T, ()>,
+}
+
+/// 按升序的元素迭代器，可从两端迭代
+pub struct SetIter<'a, T>(Keys<'a, T, ()>);
+
+/// 按升序、拥有所有权的元素迭代器
+pub struct SetIntoIter<T>(IntoIter<T, ()>);
+
+/// 范围内元素的迭代器，可从两端迭代
+pub struct SetRange<'a, T>(Range<'a, T, ()>);
+
+/// 并集迭代器，按升序产出在任一集合中的元素
+pub struct Union<'a, T: Ord> {
+    a: Peekable<SetIter<'a, T>>,
+    b: Peekable<SetIter<'a, T>>,
+}
+
+/// 交集迭代器，按升序产出同时在两个集合中的元素
+pub struct Intersection<'a, T: Ord> {
+    a: Peekable<SetIter<'a, T>>,
+    b: Peekable<SetIter<'a, T>>,
+}
+
+/// 差集迭代器，按升序产出在 self 中但不在 other 中的元素
+pub struct Difference<'a, T: Ord> {
+    a: Peekable<SetIter<'a, T>>,
+    b: Peekable<SetIter<'a, T>>,
+}
+
+/// 对称差迭代器，按升序产出恰在一个集合中的元素
+pub struct SymmetricDifference<'a, T: Ord> {
+    a: Peekable<SetIter<'a, T>>,
+    b: Peekable<SetIter<'a, T>>,
+}
+
+/// [`RBTreeSet::extract_if`] 返回的惰性迭代器
+pub struct SetExtractIf<'a, T, F> {
+    set: &'a mut RBTreeSet<T>,
+    cursor: Link<T, ()>,
+    pred: F,
+}
+
+impl<T> RBTreeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RBTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord> RBTreeSet<T> {
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// 插入元素
+    ///
+    /// ## 返回
+    /// 元素原先不存在时返回 true
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.map.tree.search_tree(&value) {
+            Some(_) => false,
+            None => {
+                self.map.tree.insert(value, ());
+                true
+            }
+        }
+    }
+
+    /// 删除元素，元素存在时返回 true
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    /// 删除并返回集合中与 value 相等的元素
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(value).map(|(k, _)| k)
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// 获取集合中与 value 相等的元素
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let link = self.map.tree.search_tree(value)?;
+        unsafe { Some(&(*link.as_ptr()).key) }
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.map.first_key_value().map(|(k, _)| k)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.map.last_key_value().map(|(k, _)| k)
+    }
+
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.map.pop_first().map(|(k, _)| k)
+    }
+
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.map.pop_last().map(|(k, _)| k)
+    }
+
+    /// 升序第 k 个（从 0 开始）元素。时间复杂度: O(log n)
+    pub fn nth(&self, k: usize) -> Option<&T> {
+        self.map.nth(k).map(|(k, _)| k)
+    }
+
+    /// 严格小于 value 的元素数量。时间复杂度: O(log n)
+    pub fn rank<Q>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.rank(value)
+    }
+
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter(self.map.keys())
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> SetRange<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        SetRange(self.map.range(range))
+    }
+
+    /// 并集
+    ///
+    /// ## Notes
+    /// 两个集合同步归并，时间复杂度: O(n + m)，下同
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T> {
+        Union {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// 交集
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a, T> {
+        Intersection {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// 差集：在 self 中但不在 other 中的元素
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T> {
+        Difference {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// 对称差
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<'a, T> {
+        SymmetricDifference {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.difference(other).next().is_none()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// 在 value 处一分为二：返回所有 >= value 的元素，自身保留 < value 的元素
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n)
+    pub fn split_off<Q>(&mut self, value: &Q) -> Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Self {
+            map: self.map.split_off(value),
+        }
+    }
+
+    /// 将 other 的全部元素移入自身，other 变为空
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n + m)
+    pub fn append(&mut self, other: &mut Self) {
+        self.map.append(&mut other.map);
+    }
+
+    /// 只保留使 `f` 返回 true 的元素
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.extract_if(|v| !f(v)).for_each(drop);
+    }
+
+    /// 按升序惰性地移除并返回使 `pred` 返回 true 的元素
+    pub fn extract_if<F>(&mut self, pred: F) -> SetExtractIf<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let cursor = self.map.tree.min(self.map.tree.root);
+        SetExtractIf {
+            set: self,
+            cursor,
+            pred,
+        }
+    }
+}
+
+impl<T> Default for RBTreeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RBTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            map: iter.into_iter().map(|v| (v, ())).collect(),
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for RBTreeSet<T> {
+    fn from(arr: [T; N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+impl<T: Ord> Extend<T> for RBTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.insert(v);
+        }
+    }
+}
+
+impl<'a, T: Ord + Copy + 'a> Extend<&'a T> for RBTreeSet<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T: Ord + Clone> Clone for RBTreeSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T: Ord> PartialEq for RBTreeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T: Ord> Eq for RBTreeSet<T> {}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for RBTreeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> IntoIterator for RBTreeSet<T> {
+    type Item = T;
+    type IntoIter = SetIntoIter<T>;
+
+    fn into_iter(self) -> SetIntoIter<T> {
+        SetIntoIter(self.map.into_iter())
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a RBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> SetIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: Ord> Iterator for SetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for SetIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for SetIter<'_, T> {}
+
+impl<T> Clone for SetIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Iterator for SetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SetIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<T> ExactSizeIterator for SetIntoIter<T> {}
+
+impl<'a, T: Ord> Iterator for SetRange<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for SetRange<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+/// 比较两个归并游标的队首元素，None 视为大于任何元素
+fn cmp_heads<T: Ord>(a: Option<&&T>, b: Option<&&T>) -> Option<Ordering> {
+    match (a, b) {
+        (None, None) => None,
+        (Some(_), None) => Some(Ordering::Less),
+        (None, Some(_)) => Some(Ordering::Greater),
+        (Some(x), Some(y)) => Some(x.cmp(y)),
+    }
+}
+
+impl<'a, T: Ord> Iterator for Union<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match cmp_heads(self.a.peek(), self.b.peek())? {
+            Ordering::Less => self.a.next(),
+            Ordering::Greater => self.b.next(),
+            Ordering::Equal => {
+                self.b.next();
+                self.a.next()
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (x, y) = (self.a.peek()?, self.b.peek()?);
+            match x.cmp(y) {
+                Ordering::Less => {
+                    self.a.next();
+                }
+                Ordering::Greater => {
+                    self.b.next();
+                }
+                Ordering::Equal => {
+                    self.b.next();
+                    return self.a.next();
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let x = self.a.peek()?;
+            match self.b.peek().map(|y| x.cmp(y)) {
+                None | Some(Ordering::Less) => return self.a.next(),
+                Some(Ordering::Greater) => {
+                    self.b.next();
+                }
+                Some(Ordering::Equal) => {
+                    self.a.next();
+                    self.b.next();
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match cmp_heads(self.a.peek(), self.b.peek())? {
+                Ordering::Less => return self.a.next(),
+                Ordering::Greater => return self.b.next(),
+                Ordering::Equal => {
+                    self.a.next();
+                    self.b.next();
+                }
+            }
+        }
+    }
+}
+
+impl<T: Ord, F> Iterator for SetExtractIf<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+        self.set
+            .map
+            .tree
+            .extract_next(&mut self.cursor, |k, _| pred(k))
+            .map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.set.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_vec<'a>(it: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+        it.copied().collect()
+    }
+
+    #[test]
+    fn test_basic_ops() {
+        let mut s = RBTreeSet::new();
+        assert!(s.insert(3));
+        assert!(s.insert(1));
+        assert!(!s.insert(3));
+        s.extend([5, 2, 4]);
+        assert_eq!(s.len(), 5);
+        assert!(s.contains(&2));
+        assert_eq!(s.get(&4), Some(&4));
+        assert_eq!((s.first(), s.last()), (Some(&1), Some(&5)));
+        assert_eq!(s.nth(2), Some(&3));
+        assert_eq!(s.rank(&4), 3);
+
+        assert!(s.remove(&3));
+        assert!(!s.remove(&3));
+        assert_eq!(s.take(&4), Some(4));
+        assert_eq!(s.pop_first(), Some(1));
+        assert_eq!(s.pop_last(), Some(5));
+        assert_eq!(to_vec(s.iter()), [2]);
+        assert_eq!(format!("{s:?}"), "{2}");
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = RBTreeSet::from([1, 3, 5, 7, 9]);
+        let b = RBTreeSet::from([3, 4, 5, 6]);
+
+        assert_eq!(to_vec(a.union(&b)), [1, 3, 4, 5, 6, 7, 9]);
+        assert_eq!(to_vec(a.intersection(&b)), [3, 5]);
+        assert_eq!(to_vec(a.difference(&b)), [1, 7, 9]);
+        assert_eq!(to_vec(b.difference(&a)), [4, 6]);
+        assert_eq!(to_vec(a.symmetric_difference(&b)), [1, 4, 6, 7, 9]);
+
+        let empty = RBTreeSet::new();
+        assert_eq!(to_vec(a.union(&empty)), to_vec(a.iter()));
+        assert_eq!(a.intersection(&empty).count(), 0);
+        assert!(a.is_disjoint(&empty));
+        assert!(!a.is_disjoint(&b));
+        assert!(RBTreeSet::from([3, 5]).is_subset(&a));
+        assert!(!b.is_subset(&a));
+        assert!(a.is_superset(&RBTreeSet::from([1, 9])));
+    }
+
+    #[test]
+    fn test_double_ended() {
+        let s: RBTreeSet<i32> = (1..=6).collect();
+        let mut it = s.iter();
+        assert_eq!(it.len(), 6);
+        assert_eq!((it.next(), it.next_back()), (Some(&1), Some(&6)));
+        assert_eq!(to_vec(it.rev()), [5, 4, 3, 2]);
+
+        let mut r = s.range(2..5);
+        assert_eq!((r.next_back(), r.next()), (Some(&4), Some(&2)));
+        assert_eq!(
+            (r.next_back(), r.next(), r.next_back()),
+            (Some(&3), None, None)
+        );
+
+        let owned: Vec<_> = s.clone().into_iter().rev().collect();
+        assert_eq!(owned, [6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_split_append_retain() {
+        let mut a: RBTreeSet<i32> = (0..20).collect();
+        let mut b = a.split_off(&12);
+        assert_eq!(to_vec(a.iter()), (0..12).collect::<Vec<_>>());
+        assert_eq!(to_vec(b.iter()), (12..20).collect::<Vec<_>>());
+
+        a.retain(|v| v % 2 == 0);
+        let odds: Vec<_> = b.extract_if(|v| v % 2 == 1).collect();
+        assert_eq!(odds, [13, 15, 17, 19]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(to_vec(a.iter()), [0, 2, 4, 6, 8, 10, 12, 14, 16, 18]);
+        assert_eq!(a, (0..10).map(|v| v * 2).collect());
+    }
+}
@@ -1,35 +1,60 @@
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{borrow::Borrow, cmp::Ordering, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use crate::collections::rbtree::augment::Augment;
 
 /// 红黑树
-pub struct RBTree<K, V> {
-    pub(crate) root: Link<K, V>,
+///
+/// 采用 CLRS 风格的 nil 哨兵：所有叶子与根的父节点都指向同一个 nil 节点。
+/// 哨兵只初始化了链接、颜色、子树大小（0）与摘要（单位元）字段，键值未初始化、永不读取，
+/// 因此对 K、V 没有额外约束
+///
+/// 每个节点维护子树大小与增强摘要 `A::Summary`，二者在插入、删除与旋转时沿受影响的路径更新，
+/// 默认的 `A = ()` 不携带任何摘要
+pub struct RBTree<K, V, A: Augment<K, V> = ()> {
+    pub(crate) root: Link<K, V, A>,
     len: usize,
-    pub(crate) nil: Link<K, V>,
+    pub(crate) nil: Link<K, V, A>,
+    pub(crate) aug: A,
     _boo: PhantomData<Box<(K, V)>>,
 }
 
-pub type Link<K, V> = Option<RawLink<K, V>>;
-type RawLink<K, V> = NonNull<Node<K, V>>;
+pub type Link<K, V, A = ()> = Option<RawLink<K, V, A>>;
+type RawLink<K, V, A> = NonNull<Node<K, V, A>>;
 
-pub(crate) enum EntrySearch<K, V> {
-    Occupied(Link<K, V>),
-    Vacant(VacantPos<K, V>),
+pub(crate) enum EntrySearch<K, V, A: Augment<K, V> = ()> {
+    Occupied(Link<K, V, A>),
+    Vacant(VacantPos<K, V, A>),
 }
 
-pub(crate) struct VacantPos<K, V> {
-    pub(crate) parent: Link<K, V>,
+pub(crate) struct VacantPos<K, V, A: Augment<K, V> = ()> {
+    pub(crate) parent: Link<K, V, A>,
     pub(crate) insert_left: bool,
 }
 
-trait LinkExt<K, V> {
-    fn ptr(self) -> *mut Node<K, V>;
+pub(crate) trait LinkExt<K, V, A: Augment<K, V>> {
+    fn ptr(self) -> *mut Node<K, V, A>;
+
+    /// 颜色判断可能作用于 nil 哨兵，只能经由原始指针读取字段，不能创建 `&Node`
+    fn is_red(&self) -> bool;
+
+    fn is_black(&self) -> bool;
 }
 
-impl<K, V> LinkExt<K, V> for Link<K, V> {
+impl<K, V, A: Augment<K, V>> LinkExt<K, V, A> for Link<K, V, A> {
     #[inline(always)]
-    fn ptr(self) -> *mut Node<K, V> {
+    fn ptr(self) -> *mut Node<K, V, A> {
         self.expect("link should never be None here").as_ptr()
     }
+
+    #[inline(always)]
+    fn is_red(&self) -> bool {
+        unsafe { (*self.ptr()).color == Color::Red }
+    }
+
+    #[inline(always)]
+    fn is_black(&self) -> bool {
+        unsafe { (*self.ptr()).color == Color::Black }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,18 +63,22 @@ enum Color {
     Black,
 }
 
-#[derive(Debug)]
-pub struct Node<K, V> {
+pub struct Node<K, V, A: Augment<K, V> = ()> {
     pub(crate) key: K,
     pub(crate) val: V,
-    pub(crate) lch: Link<K, V>,
-    pub(crate) rch: Link<K, V>,
-    pub(crate) parent: Link<K, V>,
+    pub(crate) lch: Link<K, V, A>,
+    pub(crate) rch: Link<K, V, A>,
+    pub(crate) parent: Link<K, V, A>,
     color: Color,
+    /// 子树节点数，nil 为 0
+    pub(crate) size: usize,
+    /// 子树摘要（按中序合并），nil 为单位元
+    pub(crate) summary: A::Summary,
 }
 
-impl<K, V> Node<K, V> {
-    fn new(key: K, val: V, color: Color, nil: Link<K, V>) -> Self {
+impl<K, V, A: Augment<K, V>> Node<K, V, A> {
+    fn new(key: K, val: V, color: Color, nil: Link<K, V, A>, aug: &A) -> Self {
+        let summary = aug.summarize(&key, &val);
         Self {
             key,
             val,
@@ -57,34 +86,39 @@ impl<K, V> Node<K, V> {
             rch: nil,
             parent: nil,
             color,
+            size: 1,
+            summary,
         }
     }
+}
 
-    fn is_red(&self) -> bool {
-        self.color == Color::Red
-    }
-
-    fn is_black(&self) -> bool {
-        self.color == Color::Black
+impl<K, V> RBTree<K, V> {
+    /// 创建空的红黑树
+    pub fn new() -> Self {
+        Self::with_augment(())
     }
 }
 
-impl<K, V> RBTree<K, V> {
-    /// 创建红黑树，哨兵键值需要传入
-    pub fn new(nil_key: K, nil_val: V) -> Self {
+impl<K, V, A: Augment<K, V>> RBTree<K, V, A> {
+    /// 创建空的红黑树，节点按 `aug` 维护子树摘要
+    pub fn with_augment(aug: A) -> Self {
+        let mut nil: Box<MaybeUninit<Node<K, V, A>>> = Box::new_uninit();
+        let p = nil.as_mut_ptr();
         unsafe {
-            let nil = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
-                key: nil_key,
-                val: nil_val,
-                lch: None,
-                rch: None,
-                parent: None,
-                color: Color::Black,
-            })));
+            (&raw mut (*p).lch).write(None);
+            (&raw mut (*p).rch).write(None);
+            (&raw mut (*p).parent).write(None);
+            (&raw mut (*p).color).write(Color::Black);
+            (&raw mut (*p).size).write(0);
+            (&raw mut (*p).summary).write(aug.identity());
+            let nil = Some(NonNull::new_unchecked(
+                Box::into_raw(nil).cast::<Node<K, V, A>>(),
+            ));
             Self {
-                root: Some(nil),
+                root: nil,
                 len: 0,
-                nil: Some(nil),
+                nil,
+                aug,
                 _boo: PhantomData,
             }
         }
@@ -107,7 +141,7 @@ impl<K, V> RBTree<K, V> {
         }
     }
 
-    unsafe fn free_all(tree: &mut RBTree<K, V>, node: Link<K, V>) {
+    unsafe fn free_all(tree: &mut RBTree<K, V, A>, node: Link<K, V, A>) {
         if node == tree.nil {
             return;
         }
@@ -118,10 +152,130 @@ impl<K, V> RBTree<K, V> {
             let _ = Box::from_raw(n); // 释放节点
         }
     }
+
+    /// 由左右子节点重新计算 x 的子树大小与摘要，x 不能是 nil
+    ///
+    /// 左右子节点可能是 nil，只经由原始指针读取其已初始化的字段
+    unsafe fn pull(&self, x: Link<K, V, A>) {
+        unsafe {
+            let n = x.ptr();
+            let (l, r) = ((*n).lch.ptr(), (*n).rch.ptr());
+            (*n).size = (*l).size + (*r).size + 1;
+            let own = self.aug.summarize(&(*n).key, &(*n).val);
+            let left = self.aug.combine(&(*l).summary, &own);
+            (*n).summary = self.aug.combine(&left, &(*r).summary);
+        }
+    }
+
+    /// 从 x 开始沿父链向上重新计算直到根
+    ///
+    /// 节点的值被原地修改后调用，使祖先的摘要保持一致。时间复杂度: O(log n)
+    pub(crate) fn pull_to_root(&self, mut x: Link<K, V, A>) {
+        unsafe {
+            while x != self.nil {
+                self.pull(x);
+                x = (*x.ptr()).parent;
+            }
+        }
+    }
+
+    /// 重新计算所有节点的子树大小与摘要（后序）
+    ///
+    /// 批量原地修改值之后调用。时间复杂度: O(n)
+    pub(crate) fn pull_all(&self) {
+        unsafe fn go<K, V, A: Augment<K, V>>(tree: &RBTree<K, V, A>, x: Link<K, V, A>) {
+            if x == tree.nil {
+                return;
+            }
+            unsafe {
+                go(tree, (*x.ptr()).lch);
+                go(tree, (*x.ptr()).rch);
+                tree.pull(x);
+            }
+        }
+        unsafe { go(self, self.root) }
+    }
+
+    /// 按中序取出全部键值对并释放节点，树变为空
+    ///
+    /// 时间复杂度: O(n)
+    pub(crate) fn take_all(&mut self) -> Vec<(K, V)> {
+        let mut out = Vec::with_capacity(self.len);
+        let mut stack = Vec::new();
+        let mut curr = self.root;
+        unsafe {
+            loop {
+                while curr != self.nil {
+                    stack.push(curr);
+                    curr = (*curr.ptr()).lch;
+                }
+                let Some(x) = stack.pop() else { break };
+                // 先读出右子树再释放 x，栈中只剩尚未访问的祖先
+                curr = (*x.ptr()).rch;
+                let Node { key, val, .. } = *Box::from_raw(x.ptr());
+                out.push((key, val));
+            }
+        }
+        self.root = self.nil;
+        self.len = 0;
+        out
+    }
+
+    /// 由按键严格递增的键值对构建平衡的红黑树，原树须为空
+    ///
+    /// 每个子树按中位数划分，叶子只出现在最后两层；仅把不满的最后一层染红，
+    /// 其余节点为黑，即满足红黑性质。时间复杂度: O(n)
+    pub(crate) fn build_sorted(&mut self, items: Vec<(K, V)>) {
+        debug_assert!(self.is_empty());
+        let n = items.len();
+        let red_depth = (n + 1).ilog2() as usize;
+        let mut items = items.into_iter();
+        unsafe {
+            self.root = self.build(&mut items, n, 0, red_depth);
+            (*self.root.ptr()).parent = self.nil;
+        }
+        self.len = n;
+    }
+
+    unsafe fn build(
+        &mut self,
+        items: &mut std::vec::IntoIter<(K, V)>,
+        n: usize,
+        depth: usize,
+        red_depth: usize,
+    ) -> Link<K, V, A> {
+        if n == 0 {
+            return self.nil;
+        }
+        let left_len = (n - 1) / 2;
+        unsafe {
+            let left = self.build(items, left_len, depth + 1, red_depth);
+            let (key, val) = items.next().expect("exactly n items");
+            let color = if depth == red_depth {
+                Color::Red
+            } else {
+                Color::Black
+            };
+            let z = Node::new(key, val, color, self.nil, &self.aug);
+            let z = NonNull::new(Box::into_raw(Box::new(z)));
+            let right = self.build(items, n - 1 - left_len, depth + 1, red_depth);
+
+            (*z.ptr()).lch = left;
+            (*z.ptr()).rch = right;
+            if left != self.nil {
+                (*left.ptr()).parent = z;
+            }
+            if right != self.nil {
+                (*right.ptr()).parent = z;
+            }
+            self.pull(z);
+            z
+        }
+    }
 }
 
-impl<K: Ord, V> RBTree<K, V> {
-    pub(crate) fn search_entry(&self, key: &K) -> EntrySearch<K, V> {
+impl<K: Ord, V, A: Augment<K, V>> RBTree<K, V, A> {
+    pub(crate) fn search_entry(&self, key: &K) -> EntrySearch<K, V, A> {
         unsafe {
             let mut curr = self.root;
             let mut parent = self.nil;
@@ -153,11 +307,11 @@ impl<K: Ord, V> RBTree<K, V> {
         &mut self,
         key: K,
         val: V,
-        parent: Link<K, V>,
+        parent: Link<K, V, A>,
         insert_left: bool,
-    ) -> Link<K, V> {
+    ) -> Link<K, V, A> {
         unsafe {
-            let mut z = Node::new(key, val, Color::Red, self.nil);
+            let mut z = Node::new(key, val, Color::Red, self.nil, &self.aug);
             z.parent = parent;
             let z_link = NonNull::new(Box::into_raw(Box::new(z)));
 
@@ -169,22 +323,59 @@ impl<K: Ord, V> RBTree<K, V> {
                 (*parent.ptr()).rch = z_link;
             }
 
+            self.pull_to_root(parent);
             self.insert_fixup(z_link);
             self.len += 1;
             z_link
         }
     }
 
-    /// 查找节点
-    pub fn search_tree(&self, key: &K) -> Link<K, V> {
-        match self.search_entry(key) {
-            EntrySearch::Occupied(link) => link,
-            EntrySearch::Vacant(_) => None,
+    /// 查找节点，支持以 `K` 的借用形式查找（如以 `&str` 查找 `String` 键）
+    pub fn search_tree<Q>(&self, key: &Q) -> Link<K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        unsafe {
+            let mut curr = self.root;
+            while curr != self.nil {
+                let node = curr.unwrap().as_ref();
+                match key.cmp(node.key.borrow()) {
+                    Ordering::Less => curr = node.lch,
+                    Ordering::Greater => curr = node.rch,
+                    Ordering::Equal => return curr,
+                }
+            }
+            None
         }
     }
 
+    /// 从 cursor 开始按升序寻找第一个满足 `pred` 的节点并删除，cursor 移动到其后继
+    ///
+    /// 删除只重新链接节点、不移动键值，因此 cursor 在删除后仍然有效。
+    /// `pred` 可能修改保留下来的值，调用方需在结束后调用 [`RBTree::pull_all`]
+    pub(crate) fn extract_next<F>(
+        &mut self,
+        cursor: &mut Link<K, V, A>,
+        mut pred: F,
+    ) -> Option<(K, V)>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        unsafe {
+            while *cursor != self.nil {
+                let x = *cursor;
+                *cursor = self.successor(x);
+                if pred(&(*x.ptr()).key, &mut (*x.ptr()).val) {
+                    return self.remove(x);
+                }
+            }
+        }
+        None
+    }
+
     /// 最小节点
-    pub fn min(&self, mut x: Link<K, V>) -> Link<K, V> {
+    pub fn min(&self, mut x: Link<K, V, A>) -> Link<K, V, A> {
         unsafe {
             while x != self.nil {
                 let node = x.unwrap().as_ref();
@@ -198,7 +389,7 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 最大节点
-    pub fn max(&self, mut x: Link<K, V>) -> Link<K, V> {
+    pub fn max(&self, mut x: Link<K, V, A>) -> Link<K, V, A> {
         unsafe {
             while x != self.nil {
                 let node = x.unwrap().as_ref();
@@ -212,7 +403,7 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 后继节点
-    pub fn successor(&self, mut x: Link<K, V>) -> Link<K, V> {
+    pub fn successor(&self, mut x: Link<K, V, A>) -> Link<K, V, A> {
         unsafe {
             if x == self.nil {
                 return self.nil;
@@ -233,7 +424,7 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 前驱节点
-    pub fn predecessor(&self, mut x: Link<K, V>) -> Link<K, V> {
+    pub fn predecessor(&self, mut x: Link<K, V, A>) -> Link<K, V, A> {
         unsafe {
             if x == self.nil {
                 return self.nil;
@@ -254,9 +445,9 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 插入 TODO: 这里的insert需要配合entry吗
-    pub fn insert(&mut self, key: K, val: V) -> Link<K, V> {
+    pub fn insert(&mut self, key: K, val: V) -> Link<K, V, A> {
         unsafe {
-            let mut z = Node::new(key, val, Color::Red, self.nil);
+            let mut z = Node::new(key, val, Color::Red, self.nil, &self.aug);
             let mut y = self.nil;
             let mut x = self.root;
 
@@ -279,6 +470,7 @@ impl<K: Ord, V> RBTree<K, V> {
                 (*y.ptr()).rch = z_link;
             }
 
+            self.pull_to_root(y);
             self.insert_fixup(z_link);
             self.len += 1;
             z_link
@@ -291,13 +483,13 @@ impl<K: Ord, V> RBTree<K, V> {
     /// Case 1. z的叔节点y是红色的：将父/叔/爷节点变色，当前节点指向爷节点，继续调整
     /// Case 2. z的叔节点y是黑色的且z是一个右孩子：左旋，转换为 case 3
     /// Case 3. z的叔节点y是黑色的且z是一个左孩子：将父/爷节点变色，对爷节点右旋
-    fn insert_fixup(&mut self, z: Link<K, V>) {
+    fn insert_fixup(&mut self, z: Link<K, V, A>) {
         unsafe {
             let mut z = z;
-            while (*(*z.ptr()).parent.ptr()).is_red() {
+            while (*z.ptr()).parent.is_red() {
                 if (*z.ptr()).parent == (*(*(*z.ptr()).parent.ptr()).parent.unwrap().as_ptr()).lch {
                     let y = (*(*(*z.ptr()).parent.ptr()).parent.unwrap().as_ptr()).rch; // 叔结点
-                    if (*y.ptr()).is_red() {
+                    if y.is_red() {
                         // Case 1: 叔节点为红色
                         (*(*z.ptr()).parent.ptr()).color = Color::Black;
                         (*y.ptr()).color = Color::Black;
@@ -318,7 +510,7 @@ impl<K: Ord, V> RBTree<K, V> {
                 } else {
                     // 对称情况
                     let y = (*(*(*z.ptr()).parent.ptr()).parent.unwrap().as_ptr()).lch;
-                    if (*y.ptr()).is_red() {
+                    if y.is_red() {
                         (*(*z.ptr()).parent.ptr()).color = Color::Black;
                         (*y.ptr()).color = Color::Black;
                         (*(*(*z.ptr()).parent.ptr()).parent.unwrap().as_ptr()).color = Color::Red;
@@ -352,7 +544,7 @@ impl<K: Ord, V> RBTree<K, V> {
     /// ## Return
     /// 若 x 为 None 或 nil，则返回 None
     /// 若 x.rch 为 nil，则返回 None
-    unsafe fn left_rotate(&mut self, x: Link<K, V>) -> Option<()> {
+    unsafe fn left_rotate(&mut self, x: Link<K, V, A>) -> Option<()> {
         if x == self.nil {
             return None;
         }
@@ -380,6 +572,9 @@ impl<K: Ord, V> RBTree<K, V> {
                 // 将x放在y的左边
                 (*y.ptr()).lch = x;
                 (*x_p.as_ptr()).parent = y;
+                // x 成为 y 的子节点，先更新 x 再更新 y
+                self.pull(x);
+                self.pull(y);
                 return Some(());
             }
         }
@@ -400,7 +595,7 @@ impl<K: Ord, V> RBTree<K, V> {
     /// ## Return
     /// 若 y 为 None 或 nil，则返回 None
     /// 若 y.lch 为 nil，则返回 None
-    unsafe fn right_rotate(&mut self, y: Link<K, V>) -> Option<()> {
+    unsafe fn right_rotate(&mut self, y: Link<K, V, A>) -> Option<()> {
         if y == self.nil {
             return None;
         }
@@ -429,6 +624,8 @@ impl<K: Ord, V> RBTree<K, V> {
                 // 将y放在x的右边
                 (*x.ptr()).rch = y;
                 (*y_p.as_ptr()).parent = x;
+                self.pull(y);
+                self.pull(x);
                 return Some(());
             }
         }
@@ -436,7 +633,7 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 
     /// 删除节点
-    pub fn remove(&mut self, z: Link<K, V>) -> Option<(K, V)> {
+    pub fn remove(&mut self, z: Link<K, V, A>) -> Option<(K, V)> {
         if self.len == 0 || z == self.nil {
             return None;
         }
@@ -471,6 +668,9 @@ impl<K: Ord, V> RBTree<K, V> {
                 (*y.ptr()).color = (*z.ptr()).color;
             }
 
+            // x 的父节点即结构发生变化的最低位置（x 为 nil 时其 parent 也已由 transplant 设置）
+            self.pull_to_root((*x.ptr()).parent);
+
             if y_original_color == Color::Black {
                 self.remove_fixup(x);
             }
@@ -482,7 +682,7 @@ impl<K: Ord, V> RBTree<K, V> {
         }
     }
 
-    unsafe fn transplant(&mut self, u: Link<K, V>, v: Link<K, V>) {
+    unsafe fn transplant(&mut self, u: Link<K, V, A>, v: Link<K, V, A>) {
         unsafe {
             if (*u.ptr()).parent == self.nil {
                 self.root = v;
@@ -502,14 +702,14 @@ impl<K: Ord, V> RBTree<K, V> {
     /// Case 2. 兄弟节点w是黑色，且w的两个子节点都是黑色的：兄弟变红，双黑上移
     /// Case 3. 兄弟节点w是黑色，w的左孩子是红色的，右孩子是黑色的：交换兄弟与其左孩子的颜色，对兄弟右旋，转换为 Case 4
     /// Case 4. 兄弟节点w是黑色，w的右孩子是红色的：变色，对父节点左旋
-    fn remove_fixup(&mut self, x: Link<K, V>) {
+    fn remove_fixup(&mut self, x: Link<K, V, A>) {
         unsafe {
             let mut x = x;
-            while x != self.root && (*x.ptr()).is_black() {
+            while x != self.root && x.is_black() {
                 if x == (*(*x.ptr()).parent.ptr()).lch {
                     let mut w = (*(*x.ptr()).parent.ptr()).rch; // 兄弟节点
 
-                    if (*w.ptr()).is_red() {
+                    if w.is_red() {
                         // Case 1: 兄弟节点w是红色的
                         (*w.ptr()).color = Color::Black;
                         (*(*x.ptr()).parent.ptr()).color = Color::Red;
@@ -517,14 +717,14 @@ impl<K: Ord, V> RBTree<K, V> {
                         w = (*(*x.ptr()).parent.ptr()).rch;
                     }
 
-                    if (*(*w.ptr()).lch.ptr()).is_black() && (*(*w.ptr()).rch.ptr()).is_black() {
+                    if (*w.ptr()).lch.is_black() && (*w.ptr()).rch.is_black() {
                         // Case 2: 兄弟节点w是黑色，且w的两个子节点都是黑色的
                         (*w.ptr()).color = Color::Red;
                         x = (*x.ptr()).parent;
                         continue;
                     }
 
-                    if (*(*w.ptr()).rch.ptr()).is_black() {
+                    if (*w.ptr()).rch.is_black() {
                         // Case 3: 兄弟节点w是黑色，w的左孩子是红色，右孩子是黑色
                         (*(*w.ptr()).lch.ptr()).color = Color::Black;
                         (*w.ptr()).color = Color::Red;
@@ -541,20 +741,20 @@ impl<K: Ord, V> RBTree<K, V> {
                     // 对称情况
                     let mut w = (*(*x.ptr()).parent.ptr()).lch;
 
-                    if (*w.ptr()).is_red() {
+                    if w.is_red() {
                         (*w.ptr()).color = Color::Black;
                         (*(*x.ptr()).parent.ptr()).color = Color::Red;
                         self.right_rotate((*x.ptr()).parent);
                         w = (*(*x.ptr()).parent.ptr()).lch;
                     }
 
-                    if (*(*w.ptr()).lch.ptr()).is_black() && (*(*w.ptr()).rch.ptr()).is_black() {
+                    if (*w.ptr()).lch.is_black() && (*w.ptr()).rch.is_black() {
                         (*w.ptr()).color = Color::Red;
                         x = (*x.ptr()).parent;
                         continue;
                     }
 
-                    if (*(*w.ptr()).lch.ptr()).is_black() {
+                    if (*w.ptr()).lch.is_black() {
                         (*(*w.ptr()).rch.ptr()).color = Color::Black;
                         (*w.ptr()).color = Color::Red;
                         self.left_rotate(w);
@@ -572,11 +772,19 @@ impl<K: Ord, V> RBTree<K, V> {
     }
 }
 
-impl<K, V> Drop for RBTree<K, V> {
+impl<K, V, A: Augment<K, V> + Default> Default for RBTree<K, V, A> {
+    fn default() -> Self {
+        Self::with_augment(A::default())
+    }
+}
+
+impl<K, V, A: Augment<K, V>> Drop for RBTree<K, V, A> {
     fn drop(&mut self) {
         unsafe {
             Self::free_all(self, self.root);
-            let _ = Box::from_raw(self.nil.ptr()); // 释放 nil
+            // 释放 nil：键值未初始化，不能运行其析构，只单独析构已初始化的摘要
+            std::ptr::drop_in_place(&raw mut (*self.nil.ptr()).summary);
+            let _ = Box::from_raw(self.nil.ptr().cast::<MaybeUninit<Node<K, V, A>>>());
         }
     }
 }
@@ -651,7 +859,7 @@ mod tests {
 
     #[test]
     fn test_insert() {
-        let mut tree = RBTree::<i32, i32>::new(0, 0);
+        let mut tree = RBTree::<i32, i32>::new();
         let keys = vec![17, 18, 23, 34, 27, 15, 9, 6, 8, 5, 25]; // 强数据
 
         for (idx, &key) in keys.iter().enumerate() {
@@ -675,7 +883,7 @@ mod tests {
 
     #[test]
     fn test_remove() {
-        let mut tree = RBTree::<i32, i32>::new(0, 0);
+        let mut tree = RBTree::<i32, i32>::new();
         let initial_keys = vec![15, 9, 18, 6, 13, 17, 27, 10, 23, 34, 25, 37]; // 强数据
         let remove_keys = vec![18, 25, 15, 6, 13, 37, 27, 17, 34, 9, 10, 23]; // 删除顺序
 
@@ -706,8 +914,34 @@ mod tests {
         assert_eq!(tree.root, tree.nil, "Root should be nil after all removals");
     }
 
+    #[test]
+    fn test_build_sorted_and_take_all() {
+        for n in [0, 1, 2, 3, 4, 7, 8, 15, 16, 100, 255, 1000] {
+            let mut tree = RBTree::<i32, i32>::new();
+            tree.build_sorted((0..n).map(|k| (k, k * 2)).collect());
+            assert_eq!(tree.len(), n as usize);
+            check_red_black_properties(&tree);
+            unsafe {
+                assert_eq!((*tree.root.ptr()).size, n as usize);
+            }
+
+            // 建好的树仍可正常增删
+            tree.insert(n, 0);
+            if let Some(link) = tree.search_tree(&0) {
+                tree.remove(Some(link));
+            }
+            check_red_black_properties(&tree);
+
+            let items = tree.take_all();
+            assert!(tree.is_empty());
+            assert_eq!(tree.root, tree.nil);
+            let keys: Vec<_> = items.iter().map(|(k, _)| *k).collect();
+            assert_eq!(keys, (1..=n).collect::<Vec<_>>());
+        }
+    }
+
     fn build_test_tree() -> RBTree<i32, i32> {
-        let mut tree = RBTree::<i32, i32>::new(0, 0);
+        let mut tree = RBTree::<i32, i32>::new();
         let keys = vec![20, 10, 30, 5, 15, 25, 35];
         for &k in &keys {
             tree.insert(k, k + 100);
@@ -5,8 +5,39 @@ pub enum AlgodsError {
     /// 使用了无效或过期的 NodeId
     InvalidNodeId,
 
+    /// 预处理索引构建之后树结构已被修改
+    StaleIndex,
+
     /// 对根节点执行了非法操作（如删除）
     CannotRemoveRoot,
+
+    /// 操作会使节点成为自身的后代（如把子树移动到其内部）
+    WouldCreateCycle,
+
+    /// 根节点没有兄弟节点，无法在其前后插入
+    RootHasNoSiblings,
+
+    /// 子节点位置越界
+    IndexOutOfBounds {
+        /// 给定的位置
+        index: usize,
+        /// 允许的上界
+        len: usize,
+    },
+
+    /// 构建树时没有找到根节点
+    MissingRoot,
+
+    /// 构建树时出现了多个根节点
+    MultipleRoots,
+
+    /// 解析文本格式失败
+    Parse {
+        /// 出错位置所在行（从 1 开始）
+        line: usize,
+        /// 错误描述
+        message: String,
+    },
 }
 
 pub type AlgodsResult<T> = core::result::Result<T, AlgodsError>;
@@ -17,7 +48,18 @@ impl Display for AlgodsError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             AlgodsError::InvalidNodeId => write!(f, "invalid or stale NodeId"),
+            AlgodsError::StaleIndex => write!(f, "index is stale: tree structure has changed"),
             AlgodsError::CannotRemoveRoot => write!(f, "cannot remove root node"),
+            AlgodsError::WouldCreateCycle => write!(f, "operation would create a cycle"),
+            AlgodsError::RootHasNoSiblings => write!(f, "root node has no siblings"),
+            AlgodsError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} out of bounds for length {len}")
+            }
+            AlgodsError::MissingRoot => write!(f, "tree has no root node"),
+            AlgodsError::MultipleRoots => write!(f, "tree has more than one root node"),
+            AlgodsError::Parse { line, message } => {
+                write!(f, "parse error at line {line}: {message}")
+            }
         }
     }
 }
@@ -1,10 +1,10 @@
 //! 图算法模块
 use std::{
-    cmp::Reverse,
+    cmp::{Ordering, Reverse},
     collections::{BinaryHeap, HashMap, HashSet, VecDeque},
 };
 
-use crate::graph::GraphBase;
+use crate::graph::{GraphBase, Measure, TotalOrd};
 
 pub fn dfs<G: GraphBase>(g: &G, start: G::Node) -> Vec<G::Node> {
     fn dfs_inner<G: GraphBase>(
@@ -48,21 +48,50 @@ pub fn bfs<G: GraphBase>(g: &G, start: G::Node) -> Vec<G::Node> {
     out
 }
 
+/// 单源单汇最短路（Dijkstra），以边权本身作为代价
+///
+/// ## 返回
+/// - Some(dist)：`from` 到 `to` 的最短距离
+/// - None：不可达
+///
+/// ## Notes
+/// 边权须非负；NaN 视为比任何值都大的代价。
+/// 时间复杂度: O((V + E) log V)
 pub fn dijkstra<G>(g: &G, from: G::Node, to: G::Node) -> Option<G::EdgeWeight>
 where
     G: GraphBase,
-    G::EdgeWeight: From<u8>,
+    G::EdgeWeight: Measure,
 {
-    let mut dis: HashMap<G::Node, G::EdgeWeight> = HashMap::new();
+    dijkstra_by(g, from, to, |_, _, w| w.clone())
+}
+
+/// 以 `edge_cost(u, v, &weight)` 计算边代价的 Dijkstra，
+/// 同一张图可按不同代价函数（距离、时间、费用……）求最短路
+///
+/// ## 参数
+/// - `edge_cost`: 边 u → v 的代价，须非负
+///
+/// ## 返回
+/// - Some(dist)：`from` 到 `to` 的最短距离
+/// - None：不可达
+///
+/// ## Notes
+/// 时间复杂度: O((V + E) log V)
+pub fn dijkstra_by<G, K, F>(g: &G, from: G::Node, to: G::Node, mut edge_cost: F) -> Option<K>
+where
+    G: GraphBase,
+    K: Measure,
+    F: FnMut(G::Node, G::Node, &G::EdgeWeight) -> K,
+{
+    let mut dis: HashMap<G::Node, K> = HashMap::new();
     let mut heap = BinaryHeap::new();
 
-    let zero = 0u8.into();
-    dis.insert(from, zero);
-    heap.push((Reverse(zero), from));
+    dis.insert(from, K::zero());
+    heap.push((Reverse(TotalOrd(K::zero())), from));
 
-    while let Some((Reverse(d), u)) = heap.pop() {
-        if let Some(&best) = dis.get(&u)
-            && d > best
+    while let Some((Reverse(TotalOrd(d)), u)) = heap.pop() {
+        if let Some(best) = dis.get(&u)
+            && d.measure_cmp(best) == Ordering::Greater
         {
             continue;
         }
@@ -72,15 +101,15 @@ where
         }
 
         for (v, w) in g.neighbors(u) {
-            let nd = w + d;
+            let nd = d.clone() + edge_cost(u, v, &w);
             let relax = match dis.get(&v) {
-                Some(&old) => nd < old,
+                Some(old) => nd.measure_cmp(old) == Ordering::Less,
                 None => true,
             };
 
             if relax {
-                dis.insert(v, nd);
-                heap.push((Reverse(nd), v));
+                dis.insert(v, nd.clone());
+                heap.push((Reverse(TotalOrd(nd)), v));
             }
         }
     }
@@ -150,6 +179,58 @@ mod tests {
         assert_eq!(dist, Some(4));
     }
 
+    #[test]
+    fn test_dijkstra_f64() {
+        use crate::graph::{Directed, Graph};
+
+        let mut g: Graph<(), f64, Directed> = Graph::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.5);
+        g.add_edge(n[1], n[3], 2.25);
+        g.add_edge(n[0], n[2], f64::NAN); // NaN 视为最大代价
+        g.add_edge(n[2], n[3], 0.0);
+        assert_eq!(dijkstra(&g, n[0], n[3]), Some(3.75));
+        assert!(dijkstra(&g, n[0], n[2]).unwrap().is_nan());
+        assert_eq!(dijkstra(&g, n[3], n[0]), None);
+    }
+
+    #[test]
+    fn test_dijkstra_by_edge_cost() {
+        // 边权为 (距离, 时间)，同一张图按不同代价求最短路
+        let mut g = crate::graph::Graph::<(), (u32, f32)>::new();
+        let n: Vec<_> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[2], (10, 1.0));
+        g.add_edge(n[0], n[1], (3, 2.0));
+        g.add_edge(n[1], n[2], (3, 2.0));
+
+        assert_eq!(dijkstra_by(&g, n[0], n[2], |_, _, w| w.0), Some(6));
+        assert_eq!(dijkstra_by(&g, n[0], n[2], |_, _, w| w.1), Some(1.0));
+        assert_eq!(dijkstra_by(&g, n[0], n[2], |_, _, _| 1u8), Some(1));
+    }
+
+    #[cfg(not(feature = "core"))]
+    #[test]
+    fn test_dijkstra_exact_numbers() {
+        use num::{big_num::big_integer::big_integer::BigInteger, frac::Frac};
+
+        use crate::graph::{Directed, Graph};
+
+        let mut g: Graph<(), Frac<i64>, Directed> = Graph::new();
+        let n: Vec<_> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], Frac::new(1, 3));
+        g.add_edge(n[1], n[2], Frac::new(1, 6));
+        g.add_edge(n[0], n[2], Frac::new(2, 3));
+        assert_eq!(dijkstra(&g, n[0], n[2]), Some(Frac::new(1, 2)));
+
+        let big: BigInteger = "100000000000000000000000".parse().unwrap();
+        let mut h: Graph<(), BigInteger, Directed> = Graph::new();
+        let m: Vec<_> = (0..3).map(|_| h.add_node(())).collect();
+        h.add_edge(m[0], m[1], big.clone());
+        h.add_edge(m[1], m[2], big.clone());
+        h.add_edge(m[0], m[2], big.clone() + big.clone() + BigInteger::from(1));
+        assert_eq!(dijkstra(&h, m[0], m[2]), Some(big.clone() + big));
+    }
+
     struct TestGraph {
         adj: HashMap<usize, Vec<(usize, usize)>>, // (to, weight)
     }
@@ -173,6 +254,11 @@ mod tests {
         where
             Self: 'a;
 
+        type Nodes<'a>
+            = std::iter::Copied<std::collections::hash_map::Keys<'a, usize, Vec<(usize, usize)>>>
+        where
+            Self: 'a;
+
         fn neighbors(&self, n: usize) -> Self::Neighbors<'_> {
             self.adj
                 .get(&n)
@@ -180,6 +266,10 @@ mod tests {
                 .unwrap_or_else(|| [].iter().copied())
         }
 
+        fn nodes(&self) -> Self::Nodes<'_> {
+            self.adj.keys().copied()
+        }
+
         fn node_count(&self) -> usize {
             self.adj.len()
         }
@@ -0,0 +1,533 @@
+//! 中心性与排序算法
+//!
+//! 包含 PageRank、Brandes 介数中心性、接近中心性、度中心性与 HITS。
+//! 所有结果均以 [`GraphBase::Node`] 为键；除 PageRank 外均忽略边权，按跳数计算距离。
+//!
+//! 以单个源点为单位的算法（介数、接近中心性）提供 `_par` 版本，
+//! 将源点分块提交到 [`ThreadPool`] 并行计算
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use concurrency::thread_pool::{ThreadPool, ThreadPoolError};
+
+use crate::graph::GraphBase;
+
+/// 节点得分
+pub type Scores<N> = HashMap<N, f64>;
+
+/// 节点与 0..V 下标之间的双向映射
+struct NodeMap<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Copy + Eq + std::hash::Hash> NodeMap<N> {
+    fn new<G: GraphBase<Node = N>>(g: &G) -> Self {
+        let nodes: Vec<N> = g.nodes().collect();
+        let index = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        Self { nodes, index }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 每个下标的出邻居下标（保留重边）
+    fn adjacency<G: GraphBase<Node = N>>(&self, g: &G) -> Vec<Vec<usize>> {
+        self.nodes
+            .iter()
+            .map(|&u| g.neighbors(u).map(|(v, _)| self.index[&v]).collect())
+            .collect()
+    }
+
+    fn scores(&self, values: Vec<f64>) -> Scores<N> {
+        self.nodes.iter().copied().zip(values).collect()
+    }
+}
+
+/// PageRank 参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRankParams {
+    /// 阻尼系数，即沿出边随机游走的概率
+    pub damping: f64,
+    /// 两次迭代得分之差的 L1 范数小于该值时停止
+    pub tolerance: f64,
+    /// 最大迭代次数
+    pub max_iter: usize,
+}
+
+impl Default for PageRankParams {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iter: 100,
+        }
+    }
+}
+
+/// PageRank（幂迭代）
+///
+/// 随机游走以 `damping` 的概率沿出边前进，否则按个性化向量跳转；
+/// 悬挂节点（出度为 0）的得分同样按个性化向量重新分配。
+/// 重边会按条数增加转移概率
+///
+/// ## 参数
+/// - `params`: 阻尼系数、收敛阈值与最大迭代次数
+/// - `personalization`: 跳转分布，缺省为均匀分布；未出现的节点权重为 0，权重会被归一化
+///
+/// ## 返回
+/// 每个节点的得分，总和为 1
+///
+/// ## Panics
+/// 个性化向量的权重和不为正时 panic
+///
+/// ## Notes
+/// 时间复杂度: O(max_iter * (V + E))
+pub fn pagerank<G: GraphBase>(
+    g: &G,
+    params: PageRankParams,
+    personalization: Option<&HashMap<G::Node, f64>>,
+) -> Scores<G::Node> {
+    let map = NodeMap::new(g);
+    let n = map.len();
+    if n == 0 {
+        return Scores::new();
+    }
+    let adj = map.adjacency(g);
+
+    let p: Vec<f64> = match personalization {
+        Some(weights) => {
+            let raw: Vec<f64> = map
+                .nodes
+                .iter()
+                .map(|u| weights.get(u).copied().unwrap_or(0.0))
+                .collect();
+            let sum: f64 = raw.iter().sum();
+            assert!(
+                sum > 0.0,
+                "personalization weights must sum to a positive value"
+            );
+            raw.into_iter().map(|w| w / sum).collect()
+        }
+        None => vec![1.0 / n as f64; n],
+    };
+
+    let d = params.damping;
+    let mut rank = p.clone();
+    for _ in 0..params.max_iter {
+        let dangling: f64 = (0..n).filter(|&u| adj[u].is_empty()).map(|u| rank[u]).sum();
+        let mut next: Vec<f64> = p.iter().map(|&pv| (1.0 - d + d * dangling) * pv).collect();
+        for (u, out) in adj.iter().enumerate() {
+            if out.is_empty() {
+                continue;
+            }
+            let share = d * rank[u] / out.len() as f64;
+            for &v in out {
+                next[v] += share;
+            }
+        }
+
+        let err: f64 = rank.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if err < params.tolerance {
+            break;
+        }
+    }
+    map.scores(rank)
+}
+
+/// 从下标 `s` 出发的一轮 Brandes 依赖累积，结果累加到 `cb`
+fn brandes_from(adj: &[Vec<usize>], s: usize, cb: &mut [f64]) {
+    let n = adj.len();
+    let mut order = Vec::with_capacity(n);
+    let mut pred: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut sigma = vec![0.0; n];
+    let mut dist: Vec<Option<usize>> = vec![None; n];
+    sigma[s] = 1.0;
+    dist[s] = Some(0);
+
+    let mut q = VecDeque::from([s]);
+    while let Some(u) = q.pop_front() {
+        order.push(u);
+        let du = dist[u].unwrap();
+        for &v in &adj[u] {
+            if dist[v].is_none() {
+                dist[v] = Some(du + 1);
+                q.push_back(v);
+            }
+            if dist[v] == Some(du + 1) {
+                sigma[v] += sigma[u];
+                pred[v].push(u);
+            }
+        }
+    }
+
+    let mut delta = vec![0.0; n];
+    for &w in order.iter().rev() {
+        for &u in &pred[w] {
+            delta[u] += sigma[u] / sigma[w] * (1.0 + delta[w]);
+        }
+        if w != s {
+            cb[w] += delta[w];
+        }
+    }
+}
+
+/// 介数中心性归一化系数 1 / ((V - 1)(V - 2))
+fn betweenness_scale(n: usize, normalized: bool) -> f64 {
+    if normalized && n > 2 {
+        1.0 / ((n - 1) * (n - 2)) as f64
+    } else {
+        1.0
+    }
+}
+
+/// 介数中心性（Brandes 算法，无权）
+///
+/// 节点 v 的得分为所有有序对 (s, t) 中，经过 v 的最短路所占比例之和。
+/// 无向图中每个无序对会按两个方向各计一次，如需无向定义可将结果除以 2
+///
+/// ## 参数
+/// - `normalized`: 是否除以 (V - 1)(V - 2)
+///
+/// ## Notes
+/// 时间复杂度: O(V * E)
+pub fn betweenness_centrality<G: GraphBase>(g: &G, normalized: bool) -> Scores<G::Node> {
+    let map = NodeMap::new(g);
+    let adj = map.adjacency(g);
+    let mut cb = vec![0.0; map.len()];
+    for s in 0..map.len() {
+        brandes_from(&adj, s, &mut cb);
+    }
+    let scale = betweenness_scale(map.len(), normalized);
+    map.scores(cb.into_iter().map(|x| x * scale).collect())
+}
+
+/// 从下标 `s` 出发的 BFS，返回 (可达节点数（含自身）, 距离和)
+fn distance_sum(adj: &[Vec<usize>], s: usize) -> (usize, usize) {
+    let mut dist: Vec<Option<usize>> = vec![None; adj.len()];
+    dist[s] = Some(0);
+    let mut q = VecDeque::from([s]);
+    let (mut reached, mut total) = (0, 0);
+    while let Some(u) = q.pop_front() {
+        let du = dist[u].unwrap();
+        reached += 1;
+        total += du;
+        for &v in &adj[u] {
+            if dist[v].is_none() {
+                dist[v] = Some(du + 1);
+                q.push_back(v);
+            }
+        }
+    }
+    (reached, total)
+}
+
+/// 由可达节点数与距离和计算接近中心性（Wasserman–Faust 修正）
+fn closeness(n: usize, (reached, total): (usize, usize)) -> f64 {
+    if total == 0 || n <= 1 {
+        return 0.0;
+    }
+    let r = (reached - 1) as f64;
+    (r / total as f64) * (r / (n - 1) as f64)
+}
+
+/// 接近中心性（无权，沿出边计算距离）
+///
+/// 节点 u 的得分为 `(r / Σd) * (r / (V - 1))`，其中 r 为 u 可达的其它节点数、
+/// Σd 为到它们的距离和；后一个因子使非连通图中的得分仍可比较。
+/// 无法到达任何节点的 u 得分为 0
+///
+/// ## Notes
+/// 时间复杂度: O(V * (V + E))
+pub fn closeness_centrality<G: GraphBase>(g: &G) -> Scores<G::Node> {
+    let map = NodeMap::new(g);
+    let adj = map.adjacency(g);
+    let n = map.len();
+    map.scores(
+        (0..n)
+            .map(|s| closeness(n, distance_sum(&adj, s)))
+            .collect(),
+    )
+}
+
+/// 度中心性：出度除以 V - 1
+///
+/// 无向图中即为度数除以 V - 1；自环计入度数
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn degree_centrality<G: GraphBase>(g: &G) -> Scores<G::Node> {
+    let n = g.node_count();
+    let scale = if n > 1 { 1.0 / (n - 1) as f64 } else { 1.0 };
+    g.nodes()
+        .map(|u| (u, g.neighbors(u).count() as f64 * scale))
+        .collect()
+}
+
+/// HITS 的计算结果
+#[derive(Debug, Clone)]
+pub struct Hits<N> {
+    /// 枢纽值：指向高权威节点的节点得分高
+    pub hubs: Scores<N>,
+    /// 权威值：被高枢纽节点指向的节点得分高
+    pub authorities: Scores<N>,
+}
+
+/// HITS（Hubs and Authorities）
+///
+/// 交替迭代 `a = Aᵀh`、`h = Aa`，每轮将两个向量分别归一化为和为 1
+///
+/// ## 参数
+/// - `max_iter`: 最大迭代次数
+/// - `tolerance`: 枢纽向量的 L1 变化量小于该值时停止
+///
+/// ## Notes
+/// 时间复杂度: O(max_iter * (V + E))
+pub fn hits<G: GraphBase>(g: &G, max_iter: usize, tolerance: f64) -> Hits<G::Node> {
+    let map = NodeMap::new(g);
+    let adj = map.adjacency(g);
+    let n = map.len();
+
+    fn normalize(v: &mut [f64]) {
+        let sum: f64 = v.iter().sum();
+        if sum > 0.0 {
+            v.iter_mut().for_each(|x| *x /= sum);
+        }
+    }
+
+    let mut hubs = vec![1.0 / n.max(1) as f64; n];
+    let mut auth = vec![0.0; n];
+    for _ in 0..max_iter {
+        auth.iter_mut().for_each(|x| *x = 0.0);
+        for (u, out) in adj.iter().enumerate() {
+            for &v in out {
+                auth[v] += hubs[u];
+            }
+        }
+        normalize(&mut auth);
+
+        let mut next: Vec<f64> = adj
+            .iter()
+            .map(|out| out.iter().map(|&v| auth[v]).sum())
+            .collect();
+        normalize(&mut next);
+
+        let err: f64 = hubs.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        hubs = next;
+        if err < tolerance {
+            break;
+        }
+    }
+
+    Hits {
+        hubs: map.scores(hubs),
+        authorities: map.scores(auth),
+    }
+}
+
+/// 将源点下标分块提交到线程池，每块由 `f(adj, sources)` 计算一份结果
+fn par_sources<R, F>(
+    adj: Vec<Vec<usize>>,
+    pool: &ThreadPool,
+    f: F,
+) -> Result<Vec<R>, ThreadPoolError>
+where
+    R: Send + 'static,
+    F: Fn(&[Vec<usize>], std::ops::Range<usize>) -> R + Send + Sync + Copy + 'static,
+{
+    let n = adj.len();
+    let chunks = std::thread::available_parallelism().map_or(1, |p| p.get()) * 4;
+    let size = n.div_ceil(chunks).max(1);
+    let adj = Arc::new(adj);
+
+    let handles = (0..n)
+        .step_by(size)
+        .map(|lo| {
+            let adj = Arc::clone(&adj);
+            pool.spawn(move || f(&adj, lo..(lo + size).min(n)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(handles
+        .into_iter()
+        .map(|h| h.join().expect("centrality worker panicked"))
+        .collect())
+}
+
+/// 并行版 [`betweenness_centrality`]，源点被分块提交到 `pool`
+///
+/// ## 返回
+/// - Ok(scores)：与串行版本相同的结果（浮点求和顺序不同，可能有舍入误差）
+/// - Err(ThreadPoolError)：线程池拒绝了任务
+///
+/// ## Notes
+/// 时间复杂度: O(V * E / P)，P 为线程数
+pub fn betweenness_centrality_par<G: GraphBase>(
+    g: &G,
+    normalized: bool,
+    pool: &ThreadPool,
+) -> Result<Scores<G::Node>, ThreadPoolError> {
+    let map = NodeMap::new(g);
+    let n = map.len();
+    let parts = par_sources(map.adjacency(g), pool, |adj, sources| {
+        let mut cb = vec![0.0; adj.len()];
+        for s in sources {
+            brandes_from(adj, s, &mut cb);
+        }
+        cb
+    })?;
+
+    let mut cb = vec![0.0; n];
+    for part in parts {
+        cb.iter_mut().zip(part).for_each(|(x, y)| *x += y);
+    }
+    let scale = betweenness_scale(n, normalized);
+    Ok(map.scores(cb.into_iter().map(|x| x * scale).collect()))
+}
+
+/// 并行版 [`closeness_centrality`]，源点被分块提交到 `pool`
+///
+/// ## 返回
+/// - Ok(scores)：与串行版本相同的结果
+/// - Err(ThreadPoolError)：线程池拒绝了任务
+pub fn closeness_centrality_par<G: GraphBase>(
+    g: &G,
+    pool: &ThreadPool,
+) -> Result<Scores<G::Node>, ThreadPoolError> {
+    let map = NodeMap::new(g);
+    let n = map.len();
+    let parts = par_sources(map.adjacency(g), pool, move |adj, sources| {
+        sources
+            .map(|s| closeness(n, distance_sum(adj, s)))
+            .collect::<Vec<_>>()
+    })?;
+    Ok(map.scores(parts.into_iter().flatten().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Directed, Graph, NodeIndex, Undirected};
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    fn directed(n: usize, edges: &[(usize, usize)]) -> (Graph<(), u32, Directed>, Vec<NodeIndex>) {
+        let mut g = Graph::new();
+        let v: Vec<_> = (0..n).map(|_| g.add_node(())).collect();
+        for &(a, b) in edges {
+            g.add_edge(v[a], v[b], 1);
+        }
+        (g, v)
+    }
+
+    fn path(n: usize) -> (Graph<(), u32, Undirected>, Vec<NodeIndex>) {
+        let mut g = Graph::new();
+        let v: Vec<_> = (0..n).map(|_| g.add_node(())).collect();
+        for i in 1..n {
+            g.add_edge(v[i - 1], v[i], 1);
+        }
+        (g, v)
+    }
+
+    #[test]
+    fn test_pagerank() {
+        // 对称的环上得分相等
+        let (g, v) = directed(3, &[(0, 1), (1, 2), (2, 0)]);
+        let pr = pagerank(&g, PageRankParams::default(), None);
+        for u in &v {
+            assert!(approx(pr[u], 1.0 / 3.0));
+        }
+
+        // 0、1 都指向 2，2 为悬挂节点
+        let (g, v) = directed(3, &[(0, 2), (1, 2)]);
+        let pr = pagerank(&g, PageRankParams::default(), None);
+        assert!(approx(pr.values().sum::<f64>(), 1.0));
+        assert!(pr[&v[2]] > pr[&v[0]]);
+        assert!(approx(pr[&v[0]], pr[&v[1]]));
+
+        // 个性化：跳转总是回到 0
+        let (g, v) = directed(3, &[(0, 1), (1, 2), (2, 0)]);
+        let p = HashMap::from([(v[0], 1.0)]);
+        let pr = pagerank(&g, PageRankParams::default(), Some(&p));
+        assert!(pr[&v[0]] > pr[&v[1]] && pr[&v[1]] > pr[&v[2]]);
+
+        let empty: Graph<(), u32> = Graph::new();
+        assert!(pagerank(&empty, PageRankParams::default(), None).is_empty());
+    }
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // 无向路径 0 - 1 - 2 - 3：每个无序对按两个方向各计一次
+        let (g, v) = path(4);
+        let bc = betweenness_centrality(&g, false);
+        assert!(approx(bc[&v[0]], 0.0));
+        assert!(approx(bc[&v[1]], 4.0));
+        assert!(approx(bc[&v[2]], 4.0));
+
+        let bc = betweenness_centrality(&g, true);
+        assert!(approx(bc[&v[1]], 4.0 / 6.0));
+
+        // 两条等长最短路平分：0 -> {1, 2} -> 3
+        let (g, v) = directed(4, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        let bc = betweenness_centrality(&g, false);
+        assert!(approx(bc[&v[1]], 0.5));
+        assert!(approx(bc[&v[2]], 0.5));
+        assert!(approx(bc[&v[3]], 0.0));
+    }
+
+    #[test]
+    fn test_closeness_and_degree() {
+        let (g, v) = path(3);
+        let cc = closeness_centrality(&g);
+        assert!(approx(cc[&v[1]], 1.0));
+        assert!(approx(cc[&v[0]], 2.0 / 3.0));
+
+        // 非连通：0 -> 1，2 孤立
+        let (g, v) = directed(3, &[(0, 1)]);
+        let cc = closeness_centrality(&g);
+        assert!(approx(cc[&v[0]], 0.5));
+        assert!(approx(cc[&v[1]], 0.0));
+
+        let dc = degree_centrality(&g);
+        assert!(approx(dc[&v[0]], 0.5));
+        assert!(approx(dc[&v[2]], 0.0));
+    }
+
+    #[test]
+    fn test_hits() {
+        // 0、1 为枢纽，都指向权威 2 和 3；0 额外指向 3
+        let (g, v) = directed(4, &[(0, 2), (0, 3), (1, 3)]);
+        let h = hits(&g, 100, 1e-10);
+        assert!(h.hubs[&v[0]] > h.hubs[&v[1]]);
+        assert!(approx(h.hubs[&v[2]], 0.0));
+        assert!(h.authorities[&v[3]] > h.authorities[&v[2]]);
+        assert!(approx(h.authorities[&v[0]], 0.0));
+        assert!(approx(h.hubs.values().sum(), 1.0));
+        assert!(approx(h.authorities.values().sum(), 1.0));
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let edges: Vec<_> = (0..40)
+            .flat_map(|i| [(i, (i * 7 + 3) % 40), (i, (i + 1) % 40)])
+            .collect();
+        let (g, v) = directed(40, &edges);
+        let pool = ThreadPool::new(4);
+
+        let serial = betweenness_centrality(&g, true);
+        let par = betweenness_centrality_par(&g, true, &pool).unwrap();
+        let serial_cc = closeness_centrality(&g);
+        let par_cc = closeness_centrality_par(&g, &pool).unwrap();
+        for u in &v {
+            assert!(approx(serial[u], par[u]));
+            assert_eq!(serial_cc[u], par_cc[u]);
+        }
+        pool.shutdown();
+    }
+}
@@ -0,0 +1,211 @@
+//! 团与独立集
+//!
+//! 与 [`coloring`](crate::graph::coloring) 相同，图按无向图处理且忽略自环。
+//! 候选集合均以 [`BitSet`] 表示，集合运算按 64 位字批量完成
+use crate::{
+    collections::BitSet,
+    graph::{GraphBase, coloring::undirected_adjacency},
+};
+
+/// 从 P ∪ X 中选出在 P 中邻居最多的节点作为枢轴
+fn choose_pivot(p: &BitSet, x: &BitSet, adj: &[BitSet]) -> Option<usize> {
+    p.iter()
+        .chain(x.iter())
+        .max_by_key(|&u| p.intersection_len(&adj[u]))
+}
+
+/// 带枢轴的 Bron–Kerbosch（Tomita 变体）
+fn bron_kerbosch<F: FnMut(&[usize])>(
+    r: &mut Vec<usize>,
+    mut p: BitSet,
+    mut x: BitSet,
+    adj: &[BitSet],
+    report: &mut F,
+) {
+    let Some(pivot) = choose_pivot(&p, &x, adj) else {
+        report(r);
+        return;
+    };
+    for v in p.difference(&adj[pivot]).iter() {
+        r.push(v);
+        bron_kerbosch(
+            r,
+            p.intersection(&adj[v]),
+            x.intersection(&adj[v]),
+            adj,
+            report,
+        );
+        r.pop();
+        p.remove(v);
+        x.insert(v);
+    }
+}
+
+/// 枚举所有极大团（带枢轴的 Bron–Kerbosch）
+///
+/// 每个团内的节点按 [`GraphBase::nodes`] 的顺序排列；孤立节点自身构成一个极大团
+///
+/// ## Notes
+/// 最坏时间复杂度: O(3^(V/3))，与图中极大团个数的上界同阶
+pub fn maximal_cliques<G: GraphBase>(g: &G) -> Vec<Vec<G::Node>> {
+    let (nodes, adj) = undirected_adjacency(g);
+    let n = nodes.len();
+    let mut out = Vec::new();
+    if n == 0 {
+        return out;
+    }
+    bron_kerbosch(
+        &mut Vec::new(),
+        BitSet::full(n),
+        BitSet::new(n),
+        &adj,
+        &mut |r: &[usize]| {
+            let mut r = r.to_vec();
+            r.sort_unstable();
+            out.push(r.into_iter().map(|i| nodes[i]).collect());
+        },
+    );
+    out
+}
+
+/// 分支限界求最大团：当前团大小加候选数不超过已知最优时剪枝
+fn max_clique_search(r: &mut Vec<usize>, mut p: BitSet, adj: &[BitSet], best: &mut Vec<usize>) {
+    if p.is_empty() {
+        if r.len() > best.len() {
+            *best = r.clone();
+        }
+        return;
+    }
+    while let Some(v) = p.min_element() {
+        if r.len() + p.len() <= best.len() {
+            return;
+        }
+        r.push(v);
+        max_clique_search(r, p.intersection(&adj[v]), adj, best);
+        r.pop();
+        p.remove(v);
+    }
+}
+
+/// 在邻接位集上求最大团，返回节点下标（升序）
+fn max_clique_indices(adj: &[BitSet]) -> Vec<usize> {
+    let n = adj.len();
+    let mut best = Vec::new();
+    if n > 0 {
+        max_clique_search(&mut Vec::new(), BitSet::full(n), adj, &mut best);
+    }
+    best
+}
+
+/// 最大团（节点数最多的团）
+///
+/// ## 返回
+/// 团中的节点，按 [`GraphBase::nodes`] 的顺序排列；空图返回空集
+///
+/// ## Notes
+/// NP 困难问题，最坏时间复杂度为指数级
+pub fn maximum_clique<G: GraphBase>(g: &G) -> Vec<G::Node> {
+    let (nodes, adj) = undirected_adjacency(g);
+    max_clique_indices(&adj)
+        .into_iter()
+        .map(|i| nodes[i])
+        .collect()
+}
+
+/// 最大独立集，即补图的最大团
+///
+/// ## 返回
+/// 两两不相邻的最大节点集合，按 [`GraphBase::nodes`] 的顺序排列
+///
+/// ## Notes
+/// NP 困难问题，最坏时间复杂度为指数级
+pub fn maximum_independent_set<G: GraphBase>(g: &G) -> Vec<G::Node> {
+    let (nodes, adj) = undirected_adjacency(g);
+    let n = nodes.len();
+    let complement: Vec<BitSet> = (0..n)
+        .map(|u| {
+            let mut c = BitSet::full(n);
+            c.difference_with(&adj[u]);
+            c.remove(u);
+            c
+        })
+        .collect();
+    max_clique_indices(&complement)
+        .into_iter()
+        .map(|i| nodes[i])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        CsrGraph, Graph, NodeIndex, Undirected,
+        generators::{complete_graph, cycle_graph, grid_graph, star_graph},
+    };
+
+    type U = Graph<(), u32, Undirected>;
+
+    fn idx(v: &[NodeIndex]) -> Vec<usize> {
+        v.iter().map(|n| n.index()).collect()
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        // 两个共享边 (1, 2) 的三角形，外加悬挂边 3 - 4
+        let g: CsrGraph<(), u32> = CsrGraph::from_edges([
+            (0, 1, 0),
+            (0, 2, 0),
+            (1, 2, 0),
+            (1, 3, 0),
+            (2, 3, 0),
+            (3, 4, 0),
+        ]);
+        let mut cliques: Vec<_> = maximal_cliques(&g).iter().map(|c| idx(c)).collect();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2], vec![1, 2, 3], vec![3, 4]]);
+
+        let k5: U = complete_graph(5);
+        assert_eq!(maximal_cliques(&k5).len(), 1);
+        let c5: U = cycle_graph(5);
+        assert_eq!(maximal_cliques(&c5).len(), 5);
+        assert!(maximal_cliques(&U::new()).is_empty());
+    }
+
+    #[test]
+    fn test_maximum_clique() {
+        let g: CsrGraph<(), u32> = CsrGraph::from_edges([
+            (0, 1, 0),
+            (0, 2, 0),
+            (0, 3, 0),
+            (1, 2, 0),
+            (1, 3, 0),
+            (2, 3, 0),
+            (3, 4, 0),
+            (4, 5, 0),
+        ]);
+        assert_eq!(idx(&maximum_clique(&g)), vec![0, 1, 2, 3]);
+        let grid: U = grid_graph(3, 3);
+        assert_eq!(maximum_clique(&grid).len(), 2);
+    }
+
+    #[test]
+    fn test_maximum_independent_set() {
+        let star: U = star_graph(5);
+        assert_eq!(idx(&maximum_independent_set(&star)), vec![1, 2, 3, 4, 5]);
+
+        let c7: U = cycle_graph(7);
+        let mis = maximum_independent_set(&c7);
+        assert_eq!(mis.len(), 3);
+        for (i, &a) in mis.iter().enumerate() {
+            for &b in &mis[i + 1..] {
+                assert!(!c7.neighbors(a).any(|(v, _)| v == b));
+            }
+        }
+
+        let grid: U = grid_graph(4, 4);
+        assert_eq!(maximum_independent_set(&grid).len(), 8);
+        let k4: U = complete_graph(4);
+        assert_eq!(maximum_independent_set(&k4).len(), 1);
+    }
+}
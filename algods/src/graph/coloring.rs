@@ -0,0 +1,270 @@
+//! 顶点着色
+//!
+//! 图按无向图处理：只要 u → v 或 v → u 中有一条边，u 与 v 就不能同色；自环被忽略。
+//! 颜色以 0, 1, 2, … 编号
+use std::collections::HashMap;
+
+use crate::{
+    collections::BitSet,
+    graph::{GraphBase, clique::maximum_clique},
+};
+
+/// 节点到颜色编号的映射
+pub type Coloring<N> = HashMap<N, usize>;
+
+/// 节点列表与对称化的邻接位集（不含自环）
+pub(crate) fn undirected_adjacency<G: GraphBase>(g: &G) -> (Vec<G::Node>, Vec<BitSet>) {
+    let nodes: Vec<G::Node> = g.nodes().collect();
+    let index: HashMap<G::Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let n = nodes.len();
+    let mut adj = vec![BitSet::new(n); n];
+    for (u, &node) in nodes.iter().enumerate() {
+        for (v, _) in g.neighbors(node) {
+            let v = index[&v];
+            if u != v {
+                adj[u].insert(v);
+                adj[v].insert(u);
+            }
+        }
+    }
+    (nodes, adj)
+}
+
+/// 将按下标排列的颜色转换为节点到颜色的映射
+fn to_coloring<N: Copy + Eq + std::hash::Hash>(nodes: &[N], colors: &[usize]) -> Coloring<N> {
+    nodes.iter().copied().zip(colors.iter().copied()).collect()
+}
+
+/// 邻居未使用的最小颜色
+fn smallest_free_color(adj: &BitSet, colors: &[Option<usize>]) -> usize {
+    let used: Vec<usize> = adj.iter().filter_map(|v| colors[v]).collect();
+    (0..).find(|c| !used.contains(c)).unwrap()
+}
+
+/// 着色方案使用的颜色数
+pub fn color_count<N>(coloring: &Coloring<N>) -> usize {
+    coloring.values().max().map_or(0, |&c| c + 1)
+}
+
+/// 贪心着色（Welsh–Powell）：按度数降序依次为节点分配最小可用颜色
+///
+/// 至多使用 Δ + 1 种颜色，Δ 为最大度数
+///
+/// ## Notes
+/// 时间复杂度: O(V^2 + E)
+pub fn greedy_coloring<G: GraphBase>(g: &G) -> Coloring<G::Node> {
+    let (nodes, adj) = undirected_adjacency(g);
+    let n = nodes.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&u| std::cmp::Reverse(adj[u].len()));
+
+    let mut colors = vec![None; n];
+    for u in order {
+        colors[u] = Some(smallest_free_color(&adj[u], &colors));
+    }
+    let colors: Vec<usize> = colors.into_iter().map(Option::unwrap).collect();
+    to_coloring(&nodes, &colors)
+}
+
+/// DSATUR 着色：每次选择饱和度（邻居已用颜色数）最大的节点，
+/// 饱和度相同时选度数最大者，并分配最小可用颜色
+///
+/// 对二分图、环、轮图等能给出最优解，一般情况下通常优于 [`greedy_coloring`]
+///
+/// ## Notes
+/// 时间复杂度: O(V^2 + E)
+pub fn dsatur_coloring<G: GraphBase>(g: &G) -> Coloring<G::Node> {
+    let (nodes, adj) = undirected_adjacency(g);
+    let colors = dsatur(&adj);
+    to_coloring(&nodes, &colors)
+}
+
+fn dsatur(adj: &[BitSet]) -> Vec<usize> {
+    let n = adj.len();
+    let mut colors: Vec<Option<usize>> = vec![None; n];
+    // 每个节点的邻居已使用的颜色集合
+    let mut seen: Vec<BitSet> = vec![BitSet::new(n.max(1)); n];
+    for _ in 0..n {
+        let u = (0..n)
+            .filter(|&u| colors[u].is_none())
+            .max_by_key(|&u| (seen[u].len(), adj[u].len(), std::cmp::Reverse(u)))
+            .unwrap();
+        let c = (0..).find(|&c| !seen[u].contains(c)).unwrap();
+        colors[u] = Some(c);
+        for v in adj[u].iter() {
+            seen[v].insert(c);
+        }
+    }
+    colors.into_iter().map(Option::unwrap).collect()
+}
+
+/// 尝试用至多 k 种颜色为所有节点着色（DSATUR 顺序的回溯）
+struct KColoring<'a> {
+    adj: &'a [BitSet],
+    k: usize,
+    colors: Vec<Option<usize>>,
+}
+
+impl KColoring<'_> {
+    fn solve(&mut self, colored: usize, used: usize) -> bool {
+        let n = self.adj.len();
+        if colored == n {
+            return true;
+        }
+
+        // 选择饱和度最大的未着色节点
+        let saturation = |u: usize| {
+            let mut s = BitSet::new(self.k);
+            for v in self.adj[u].iter() {
+                if let Some(c) = self.colors[v] {
+                    s.insert(c);
+                }
+            }
+            s
+        };
+        let (u, forbidden) = (0..n)
+            .filter(|&u| self.colors[u].is_none())
+            .map(|u| (u, saturation(u)))
+            .max_by_key(|(u, s)| (s.len(), self.adj[*u].len()))
+            .unwrap();
+
+        // 对称性剪枝：新颜色只尝试编号最小的一种
+        for c in 0..self.k.min(used + 1) {
+            if forbidden.contains(c) {
+                continue;
+            }
+            self.colors[u] = Some(c);
+            if self.solve(colored + 1, used.max(c + 1)) {
+                return true;
+            }
+        }
+        self.colors[u] = None;
+        false
+    }
+}
+
+/// 精确色数与一个最优着色方案
+///
+/// 以最大团大小为下界、DSATUR 结果为上界，从下界开始逐个尝试回溯求解
+///
+/// ## 返回
+/// (χ(G), coloring)
+///
+/// ## Notes
+/// 问题是 NP 困难的，最坏时间复杂度为指数级，适用于数十个节点以内的图
+pub fn chromatic_number<G: GraphBase>(g: &G) -> (usize, Coloring<G::Node>) {
+    let (nodes, adj) = undirected_adjacency(g);
+    let upper = dsatur(&adj);
+    let hi = upper.iter().max().map_or(0, |&c| c + 1);
+    let lo = maximum_clique(g).len();
+
+    for k in lo..hi {
+        let mut search = KColoring {
+            adj: &adj,
+            k,
+            colors: vec![None; nodes.len()],
+        };
+        if search.solve(0, 0) {
+            let colors: Vec<usize> = search.colors.into_iter().map(Option::unwrap).collect();
+            return (k, to_coloring(&nodes, &colors));
+        }
+    }
+    (hi, to_coloring(&nodes, &upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        Graph, NodeIndex, Undirected,
+        generators::{complete_graph, cycle_graph, grid_graph},
+    };
+
+    type U = Graph<(), u32, Undirected>;
+
+    fn from_edges(n: usize, edges: &[(usize, usize)]) -> U {
+        let mut g = U::new();
+        let v: Vec<_> = (0..n).map(|_| g.add_node(())).collect();
+        for &(a, b) in edges {
+            g.add_edge(v[a], v[b], 1);
+        }
+        g
+    }
+
+    fn assert_proper(g: &U, coloring: &Coloring<NodeIndex>) {
+        assert_eq!(coloring.len(), g.node_count());
+        for e in g.edge_references() {
+            if e.node[0] != e.node[1] {
+                assert_ne!(coloring[&e.node[0]], coloring[&e.node[1]]);
+            }
+        }
+    }
+
+    /// Grötzsch 图：无三角形但色数为 4
+    fn grotzsch() -> U {
+        let mut edges = Vec::new();
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((5 + i, (i + 1) % 5));
+            edges.push((5 + i, (i + 4) % 5));
+            edges.push((10, 5 + i));
+        }
+        from_edges(11, &edges)
+    }
+
+    #[test]
+    fn test_greedy_and_dsatur() {
+        for g in [
+            complete_graph(6),
+            cycle_graph(7),
+            grid_graph(4, 5),
+            grotzsch(),
+        ] {
+            let greedy = greedy_coloring(&g);
+            let dsatur = dsatur_coloring(&g);
+            assert_proper(&g, &greedy);
+            assert_proper(&g, &dsatur);
+        }
+
+        // DSATUR 对二分图和奇环是最优的
+        let grid: U = grid_graph(4, 5);
+        assert_eq!(color_count(&dsatur_coloring(&grid)), 2);
+        let odd: U = cycle_graph(7);
+        assert_eq!(color_count(&dsatur_coloring(&odd)), 3);
+        assert_eq!(
+            color_count(&greedy_coloring(&complete_graph::<(), u32, Undirected>(6))),
+            6
+        );
+        assert!(greedy_coloring(&U::new()).is_empty());
+    }
+
+    #[test]
+    fn test_chromatic_number() {
+        let (k, coloring) = chromatic_number(&grotzsch());
+        assert_eq!(k, 4);
+        assert_proper(&grotzsch(), &coloring);
+        assert_eq!(color_count(&coloring), 4);
+
+        assert_eq!(
+            chromatic_number(&cycle_graph::<(), u32, Undirected>(6)).0,
+            2
+        );
+        assert_eq!(
+            chromatic_number(&cycle_graph::<(), u32, Undirected>(5)).0,
+            3
+        );
+        assert_eq!(
+            chromatic_number(&complete_graph::<(), u32, Undirected>(5)).0,
+            5
+        );
+        assert_eq!(chromatic_number(&from_edges(3, &[])).0, 1);
+        assert_eq!(chromatic_number(&U::new()).0, 0);
+
+        // 排考：相同学生的科目不能同时考
+        //   0 数学 - 1 物理 - 2 化学 - 0 数学，3 英语 - 0 数学
+        let exams = from_edges(4, &[(0, 1), (1, 2), (2, 0), (3, 0)]);
+        let (slots, plan) = chromatic_number(&exams);
+        assert_eq!(slots, 3);
+        assert_proper(&exams, &plan);
+    }
+}
@@ -0,0 +1,304 @@
+use std::marker::PhantomData;
+
+use crate::graph::{
+    Directed, Direction, EdgeType, Graph, GraphBase, NodeIndex, graph::NodeIndices,
+};
+
+/// 压缩稀疏行（CSR）存储的不可变图
+///
+/// 节点 u 的出边连续存放在 `targets[offsets[u]..offsets[u + 1]]` 中，
+/// 邻居遍历是一次顺序内存扫描，适合读多写少的大规模分析场景。
+/// 节点句柄与 [`Graph`] 相同，均为 [`NodeIndex`]
+///
+/// N: 节点权重类型
+/// E: 边权重类型
+/// Ty: Directed / Undirected
+pub struct CsrGraph<N, E, Ty = Directed> {
+    nodes: Vec<N>,
+    /// 长度为 V + 1 的行偏移
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<E>,
+    _boo: PhantomData<Ty>,
+}
+
+/// CSR 邻居迭代器
+pub struct CsrNeighbors<'a, E> {
+    targets: std::slice::Iter<'a, usize>,
+    weights: std::slice::Iter<'a, E>,
+}
+
+impl<N, E, Ty: EdgeType> CsrGraph<N, E, Ty> {
+    /// 由节点权重和边列表 (source, target, weight) 构建
+    ///
+    /// 同一节点的出边保持输入中的相对顺序；无向图会为每条边补一条反向边
+    ///
+    /// ## Panics
+    /// 边的端点越界时 panic
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V + E)
+    pub fn with_nodes<I>(nodes: Vec<N>, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        E: Clone,
+    {
+        let n = nodes.len();
+        let mut list: Vec<(usize, usize, E)> = Vec::new();
+        for (a, b, w) in edges {
+            assert!(a < n && b < n, "edge ({a}, {b}) out of bounds");
+            if Ty::DIRECTED {
+                list.push((a, b, w));
+            } else {
+                list.push((a, b, w.clone()));
+                list.push((b, a, w));
+            }
+        }
+
+        // 计数排序（稳定）
+        let mut offsets = vec![0; n + 1];
+        for &(a, _, _) in &list {
+            offsets[a + 1] += 1;
+        }
+        for i in 0..n {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut order = vec![0; list.len()];
+        let mut next = offsets.clone();
+        for (i, &(a, _, _)) in list.iter().enumerate() {
+            order[next[a]] = i;
+            next[a] += 1;
+        }
+
+        let mut slots: Vec<Option<(usize, usize, E)>> = list.into_iter().map(Some).collect();
+        let mut targets = Vec::with_capacity(slots.len());
+        let mut weights = Vec::with_capacity(slots.len());
+        for i in order {
+            let (_, b, w) = slots[i].take().unwrap();
+            targets.push(b);
+            weights.push(w);
+        }
+
+        Self {
+            nodes,
+            offsets,
+            targets,
+            weights,
+            _boo: PhantomData,
+        }
+    }
+
+    /// 节点数量
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 边数量（无向图中每条边计为两条有向边）
+    ///
+    /// 时间复杂度: O(1)
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// 获取节点权重
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_weight(&self, node: NodeIndex) -> Option<&N> {
+        self.nodes.get(node.index())
+    }
+
+    /// 节点的出度
+    ///
+    /// 时间复杂度: O(1)
+    pub fn out_degree(&self, node: NodeIndex) -> usize {
+        let u = node.index();
+        self.offsets[u + 1] - self.offsets[u]
+    }
+
+    /// 返回节点的出边 (target, &weight)
+    ///
+    /// 时间复杂度: O(1)
+    pub fn edges(&self, node: NodeIndex) -> impl Iterator<Item = (NodeIndex, &E)> {
+        let range = self.offsets[node.index()]..self.offsets[node.index() + 1];
+        self.targets[range.clone()]
+            .iter()
+            .zip(&self.weights[range])
+            .map(|(&v, w)| (NodeIndex(v), w))
+    }
+
+    /// 返回所有节点索引
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_indices(&self) -> NodeIndices<usize> {
+        NodeIndices::new(self.nodes.len())
+    }
+}
+
+impl<E, Ty: EdgeType> CsrGraph<(), E, Ty> {
+    /// 由边列表构建，节点数为最大端点 + 1
+    ///
+    /// 时间复杂度: O(V + E)
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        E: Clone,
+    {
+        let edges: Vec<_> = edges.into_iter().collect();
+        let n = edges
+            .iter()
+            .map(|&(a, b, _)| a.max(b) + 1)
+            .max()
+            .unwrap_or(0);
+        Self::with_nodes(vec![(); n], edges)
+    }
+}
+
+impl<N, E, Ty, Idx> From<&Graph<N, E, Ty, Idx>> for CsrGraph<N, E, Ty>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+{
+    /// 从 [`Graph`] 构建，每个节点的邻居顺序与 [`Graph::neighbors`] 一致
+    ///
+    /// 时间复杂度: O(V + E)
+    fn from(g: &Graph<N, E, Ty, Idx>) -> Self {
+        let n = g.node_count();
+        let mut nodes = Vec::with_capacity(n);
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut targets = Vec::with_capacity(g.edge_count());
+        let mut weights = Vec::with_capacity(g.edge_count());
+        offsets.push(0);
+        for u in g.node_indices() {
+            nodes.push(g.node_weight(u).unwrap().clone());
+            for e in g.edges_directed(u, Direction::Outgoing) {
+                targets.push(e.node[1].index());
+                weights.push(e.weight.clone());
+            }
+            offsets.push(targets.len());
+        }
+        Self {
+            nodes,
+            offsets,
+            targets,
+            weights,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty> GraphBase for CsrGraph<N, E, Ty>
+where
+    Ty: EdgeType,
+    E: Clone,
+{
+    type Node = NodeIndex;
+    type EdgeWeight = E;
+
+    type Neighbors<'a>
+        = CsrNeighbors<'a, E>
+    where
+        Self: 'a;
+
+    type Nodes<'a>
+        = NodeIndices<usize>
+    where
+        Self: 'a;
+
+    fn neighbors(&self, n: Self::Node) -> Self::Neighbors<'_> {
+        let range = self.offsets[n.index()]..self.offsets[n.index() + 1];
+        CsrNeighbors {
+            targets: self.targets[range.clone()].iter(),
+            weights: self.weights[range].iter(),
+        }
+    }
+
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.node_indices()
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn contains_node(&self, n: Self::Node) -> bool {
+        n.index() < self.nodes.len()
+    }
+}
+
+impl<'a, E: Clone> Iterator for CsrNeighbors<'a, E> {
+    type Item = (NodeIndex, E);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let &v = self.targets.next()?;
+        let w = self.weights.next()?;
+        Some((NodeIndex(v), w.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.targets.size_hint()
+    }
+}
+
+impl<'a, E: Clone> ExactSizeIterator for CsrNeighbors<'a, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Undirected, algo, dag};
+
+    #[test]
+    fn test_from_edges() {
+        let g: CsrGraph<(), i32> = CsrGraph::from_edges([(2, 0, 5), (0, 1, 1), (0, 2, 2)]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.out_degree(NodeIndex(0)), 2);
+
+        let out: Vec<_> = g
+            .edges(NodeIndex(0))
+            .map(|(v, &w)| (v.index(), w))
+            .collect();
+        assert_eq!(out, vec![(1, 1), (2, 2)]);
+        let out: Vec<_> = g.neighbors(NodeIndex(2)).collect();
+        assert_eq!(out, vec![(NodeIndex(0), 5)]);
+        assert_eq!(g.neighbors(NodeIndex(1)).len(), 0);
+    }
+
+    #[test]
+    fn test_undirected_with_nodes() {
+        let g: CsrGraph<&str, u32, Undirected> =
+            CsrGraph::with_nodes(vec!["a", "b", "c"], [(0, 1, 3), (1, 2, 4)]);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.node_weight(NodeIndex(2)), Some(&"c"));
+        let nbrs: Vec<_> = g.neighbors(NodeIndex(1)).collect();
+        assert_eq!(nbrs, vec![(NodeIndex(0), 3), (NodeIndex(2), 4)]);
+    }
+
+    #[test]
+    fn test_from_graph_matches_neighbors() {
+        let mut g: Graph<u8, i32, Directed> = Graph::new();
+        let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+        for (a, b, w) in [(0, 1, 2), (0, 2, 5), (1, 2, 1), (1, 3, 3), (2, 3, 1)] {
+            g.add_edge(n[a], n[b], w);
+        }
+        let csr = CsrGraph::from(&g);
+
+        for u in g.node_indices() {
+            assert_eq!(
+                GraphBase::neighbors(&g, u).collect::<Vec<_>>(),
+                csr.neighbors(u).collect::<Vec<_>>()
+            );
+            assert_eq!(g.node_weight(u), csr.node_weight(u));
+        }
+
+        // 所有算法无需修改即可运行
+        assert_eq!(algo::dfs(&csr, n[0]), algo::dfs(&g, n[0]));
+        assert_eq!(algo::bfs(&csr, n[0]), algo::bfs(&g, n[0]));
+        assert_eq!(algo::dijkstra(&csr, n[0], n[3]), Some(4));
+        assert_eq!(dag::toposort(&csr), dag::toposort(&g));
+    }
+}
@@ -0,0 +1,531 @@
+//! 有向无环图（DAG）算法
+//!
+//! 包含拓扑排序、环检测、DAG 最长路、传递闭包与传递归约，
+//! 以及按依赖完成顺序惰性产出节点的 [`TopoWalker`]
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    collections::BitSet,
+    graph::{GraphBase, Measure},
+};
+
+/// 图中存在的环，拓扑排序失败时返回
+///
+/// 节点按环上的顺序排列：`nodes[i] -> nodes[i + 1]`，最后一个节点指回第一个节点
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    nodes: Vec<N>,
+}
+
+impl<N> Cycle<N> {
+    /// 环上的节点
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// 取出环上的节点
+    pub fn into_nodes(self) -> Vec<N> {
+        self.nodes
+    }
+}
+
+impl<N: fmt::Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle: {:?}", self.nodes)
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for Cycle<N> {}
+
+/// 可能因图中存在环而失败的 DAG 算法结果
+pub type DagResult<G, T> = Result<T, Cycle<<G as GraphBase>::Node>>;
+
+/// 以 (source, target) 表示的边集
+pub type NodePairs<G> = Vec<(<G as GraphBase>::Node, <G as GraphBase>::Node)>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Gray,  // 在当前搜索路径上
+    Black, // 已完成
+}
+
+/// 迭代式 DFS，返回所有节点的后序序列；遇到回边时返回该环
+fn dfs_postorder<G: GraphBase>(g: &G) -> Result<Vec<G::Node>, Vec<G::Node>> {
+    let mut color: HashMap<G::Node, Color> = HashMap::with_capacity(g.node_count());
+    let mut post = Vec::with_capacity(g.node_count());
+    let mut path: Vec<G::Node> = Vec::new();
+    let mut stack: Vec<G::Neighbors<'_>> = Vec::new();
+
+    for s in g.nodes() {
+        if color.contains_key(&s) {
+            continue;
+        }
+        color.insert(s, Color::Gray);
+        path.push(s);
+        stack.push(g.neighbors(s));
+
+        while let Some(it) = stack.last_mut() {
+            match it.next() {
+                Some((v, _)) => match color.get(&v) {
+                    None => {
+                        color.insert(v, Color::Gray);
+                        path.push(v);
+                        stack.push(g.neighbors(v));
+                    }
+                    Some(Color::Gray) => {
+                        let pos = path.iter().position(|&x| x == v).unwrap();
+                        return Err(path.split_off(pos));
+                    }
+                    Some(Color::Black) => {}
+                },
+                None => {
+                    stack.pop();
+                    let u = path.pop().unwrap();
+                    color.insert(u, Color::Black);
+                    post.push(u);
+                }
+            }
+        }
+    }
+    Ok(post)
+}
+
+/// 拓扑排序（Kahn 算法）
+///
+/// 入度为 0 的节点按 [`GraphBase::nodes`] 的顺序出队
+///
+/// ## 返回
+/// - Ok(order)：拓扑序
+/// - Err(cycle)：图中存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn toposort<G: GraphBase>(g: &G) -> DagResult<G, Vec<G::Node>> {
+    let mut in_degree: HashMap<G::Node, usize> = g.nodes().map(|n| (n, 0)).collect();
+    for u in g.nodes() {
+        for (v, _) in g.neighbors(u) {
+            *in_degree.entry(v).or_insert(0) += 1;
+        }
+    }
+
+    let mut queue: VecDeque<G::Node> = g.nodes().filter(|n| in_degree[n] == 0).collect();
+    let mut order = Vec::with_capacity(in_degree.len());
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for (v, _) in g.neighbors(u) {
+            let d = in_degree.get_mut(&v).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                queue.push_back(v);
+            }
+        }
+    }
+
+    if order.len() == in_degree.len() {
+        Ok(order)
+    } else {
+        let nodes = find_cycle(g).expect("Kahn's algorithm stalled without a cycle");
+        Err(Cycle { nodes })
+    }
+}
+
+/// 拓扑排序（DFS 逆后序）
+///
+/// ## 返回
+/// - Ok(order)：拓扑序
+/// - Err(cycle)：图中存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn toposort_dfs<G: GraphBase>(g: &G) -> DagResult<G, Vec<G::Node>> {
+    match dfs_postorder(g) {
+        Ok(mut post) => {
+            post.reverse();
+            Ok(post)
+        }
+        Err(nodes) => Err(Cycle { nodes }),
+    }
+}
+
+/// 判断有向图中是否存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn is_cyclic_directed<G: GraphBase>(g: &G) -> bool {
+    dfs_postorder(g).is_err()
+}
+
+/// 查找有向图中的一个环
+///
+/// ## 返回
+/// - Some(cycle)：环上的节点，`cycle[i] -> cycle[i + 1]`，末节点指回首节点
+/// - None：图是 DAG
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn find_cycle<G: GraphBase>(g: &G) -> Option<Vec<G::Node>> {
+    dfs_postorder(g).err()
+}
+
+/// DAG 上的最长路，以边权本身作为长度
+///
+/// ## 返回
+/// - Ok((len, path))：最长路的长度和路径上的节点；空图返回 (0, [])
+/// - Err(cycle)：图中存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn dag_longest_path<G>(g: &G) -> DagResult<G, (G::EdgeWeight, Vec<G::Node>)>
+where
+    G: GraphBase,
+    G::EdgeWeight: Measure,
+{
+    dag_longest_path_by(g, |_, _, w| w.clone())
+}
+
+/// 以 `edge_cost(u, v, &weight)` 作为边长的 DAG 最长路
+///
+/// ## 返回
+/// - Ok((len, path))：最长路的长度和路径上的节点；空图返回 (0, [])
+/// - Err(cycle)：图中存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V + E)
+pub fn dag_longest_path_by<G, K, F>(g: &G, mut edge_cost: F) -> DagResult<G, (K, Vec<G::Node>)>
+where
+    G: GraphBase,
+    K: Measure,
+    F: FnMut(G::Node, G::Node, &G::EdgeWeight) -> K,
+{
+    let order = toposort(g)?;
+
+    // 每个节点自身构成长度为 0 的路径
+    let mut dist: HashMap<G::Node, K> = order.iter().map(|&n| (n, K::zero())).collect();
+    let mut pred: HashMap<G::Node, G::Node> = HashMap::new();
+    for &u in &order {
+        let du = dist[&u].clone();
+        for (v, w) in g.neighbors(u) {
+            let cand = du.clone() + edge_cost(u, v, &w);
+            let dv = dist.get_mut(&v).unwrap();
+            if cand.measure_cmp(dv) == Ordering::Greater {
+                *dv = cand;
+                pred.insert(v, u);
+            }
+        }
+    }
+
+    let Some(mut end) = order
+        .iter()
+        .copied()
+        .max_by(|a, b| dist[a].measure_cmp(&dist[b]))
+    else {
+        return Ok((K::zero(), Vec::new()));
+    };
+    let len = dist[&end].clone();
+    let mut path = vec![end];
+    while let Some(&p) = pred.get(&end) {
+        path.push(p);
+        end = p;
+    }
+    path.reverse();
+    Ok((len, path))
+}
+
+/// 传递闭包
+///
+/// 返回所有满足「从 u 出发经过至少一条边可达 v」的有序对 (u, v)，按 (u, v) 升序排列。
+/// 图中允许存在环（此时环上节点可达自身）
+///
+/// ## Notes
+/// 时间复杂度: O(V * (V + E))
+pub fn transitive_closure<G: GraphBase>(g: &G) -> NodePairs<G> {
+    let mut pairs = Vec::new();
+    for u in g.nodes() {
+        let mut vis = HashSet::new();
+        let mut stack: Vec<G::Node> = g.neighbors(u).map(|(v, _)| v).collect();
+        while let Some(v) = stack.pop() {
+            if !vis.insert(v) {
+                continue;
+            }
+            pairs.push((u, v));
+            stack.extend(g.neighbors(v).map(|(w, _)| w));
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// 传递归约
+///
+/// 返回与原 DAG 可达关系相同的最小边集，按 (u, v) 升序排列；重边只保留一条
+///
+/// ## 返回
+/// - Ok(edges)：归约后的边
+/// - Err(cycle)：图中存在环
+///
+/// ## Notes
+/// 时间复杂度: O(V * E / 64)
+pub fn transitive_reduction<G: GraphBase>(g: &G) -> DagResult<G, NodePairs<G>> {
+    let order = toposort(g)?;
+    let n = order.len();
+    let index: HashMap<G::Node, usize> = order.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+
+    // reach[i]：拓扑序为 i 的节点经过至少一条边可达的节点集合
+    let mut reach = vec![BitSet::new(n); n];
+    for i in (0..n).rev() {
+        let mut r = BitSet::new(n);
+        for (v, _) in g.neighbors(order[i]) {
+            let j = index[&v];
+            r.insert(j);
+            r.union_with(&reach[j]);
+        }
+        reach[i] = r;
+    }
+
+    let mut edges = Vec::new();
+    for (i, &u) in order.iter().enumerate() {
+        let mut indirect = BitSet::new(n);
+        let mut direct = BitSet::new(n);
+        for (v, _) in g.neighbors(u) {
+            let j = index[&v];
+            direct.insert(j);
+            indirect.union_with(&reach[j]);
+        }
+        for j in direct.difference(&indirect).iter() {
+            edges.push((order[i], order[j]));
+        }
+    }
+    edges.sort();
+    Ok(edges)
+}
+
+/// 依赖驱动的拓扑遍历器
+///
+/// 节点的所有前驱都被 [`complete`](TopoWalker::complete) 后，该节点才会变为就绪。
+/// 适合构建系统等「取出任务 → 执行 → 标记完成」的场景，多个就绪节点可并行处理。
+///
+/// 作为 [`Iterator`] 使用时，每产出一个节点就立即视为完成，即惰性的 Kahn 拓扑序。
+/// 若图中存在环，环上的节点永远不会就绪，遍历结束后 [`remaining`](TopoWalker::remaining) 非零
+pub struct TopoWalker<'a, G: GraphBase> {
+    graph: &'a G,
+    in_degree: HashMap<G::Node, usize>,
+    ready: VecDeque<G::Node>,
+    remaining: usize,
+}
+
+impl<'a, G: GraphBase> TopoWalker<'a, G> {
+    /// 创建遍历器，初始时所有入度为 0 的节点就绪
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V + E)
+    pub fn new(graph: &'a G) -> Self {
+        let mut in_degree: HashMap<G::Node, usize> = graph.nodes().map(|n| (n, 0)).collect();
+        for u in graph.nodes() {
+            for (v, _) in graph.neighbors(u) {
+                *in_degree.entry(v).or_insert(0) += 1;
+            }
+        }
+        let ready = graph.nodes().filter(|n| in_degree[n] == 0).collect();
+        let remaining = in_degree.len();
+        Self {
+            graph,
+            in_degree,
+            ready,
+            remaining,
+        }
+    }
+
+    /// 取出一个就绪节点（不标记完成）
+    pub fn next_ready(&mut self) -> Option<G::Node> {
+        self.ready.pop_front()
+    }
+
+    /// 标记节点已完成，其所有依赖均已完成的后继变为就绪
+    ///
+    /// ## Notes
+    /// 每个节点只应完成一次；时间复杂度: O(deg(node))
+    pub fn complete(&mut self, node: G::Node) {
+        self.remaining -= 1;
+        for (v, _) in self.graph.neighbors(node) {
+            let d = self.in_degree.get_mut(&v).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                self.ready.push_back(v);
+            }
+        }
+    }
+
+    /// 当前就绪但尚未取出的节点数
+    pub fn ready_count(&self) -> usize {
+        self.ready.len()
+    }
+
+    /// 尚未完成的节点数
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// 是否所有节点均已完成
+    pub fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl<'a, G: GraphBase> Iterator for TopoWalker<'a, G> {
+    type Item = G::Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.next_ready()?;
+        self.complete(n);
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Directed, Graph, NodeIndex};
+
+    fn build(
+        n: usize,
+        edges: &[(usize, usize, i32)],
+    ) -> (Graph<(), i32, Directed>, Vec<NodeIndex>) {
+        let mut g = Graph::new();
+        let nodes: Vec<_> = (0..n).map(|_| g.add_node(())).collect();
+        for &(a, b, w) in edges {
+            g.add_edge(nodes[a], nodes[b], w);
+        }
+        (g, nodes)
+    }
+
+    fn assert_topological(g: &Graph<(), i32, Directed>, order: &[NodeIndex]) {
+        let pos: HashMap<_, _> = order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        assert_eq!(pos.len(), g.node_count());
+        for u in g.node_indices() {
+            for (v, _) in g.neighbors(u) {
+                assert!(pos[&u] < pos[&v]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_toposort_kahn_and_dfs() {
+        let (g, _) = build(
+            6,
+            &[
+                (5, 2, 1),
+                (5, 0, 1),
+                (4, 0, 1),
+                (4, 1, 1),
+                (2, 3, 1),
+                (3, 1, 1),
+            ],
+        );
+        assert_topological(&g, &toposort(&g).unwrap());
+        assert_topological(&g, &toposort_dfs(&g).unwrap());
+        assert!(!is_cyclic_directed(&g));
+        assert_eq!(find_cycle(&g), None);
+    }
+
+    #[test]
+    fn test_toposort_reports_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1
+        let (g, n) = build(4, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1)]);
+        let expected = vec![n[1], n[2], n[3]];
+
+        let err = toposort(&g).unwrap_err();
+        assert_eq!(err.nodes(), expected.as_slice());
+        let err = toposort_dfs(&g).unwrap_err();
+        assert_eq!(err.into_nodes(), expected);
+        assert!(is_cyclic_directed(&g));
+    }
+
+    #[test]
+    fn test_self_loop_is_cycle() {
+        let (g, n) = build(2, &[(0, 1, 1), (1, 1, 1)]);
+        assert_eq!(find_cycle(&g), Some(vec![n[1]]));
+    }
+
+    #[test]
+    fn test_dag_longest_path() {
+        // 0 -> 1 (3), 0 -> 2 (2), 1 -> 3 (4), 2 -> 3 (6), 3 -> 4 (1)
+        let (g, n) = build(5, &[(0, 1, 3), (0, 2, 2), (1, 3, 4), (2, 3, 6), (3, 4, 1)]);
+        let (len, path) = dag_longest_path(&g).unwrap();
+        assert_eq!(len, 9);
+        assert_eq!(path, vec![n[0], n[2], n[3], n[4]]);
+
+        let (empty, _) = build(0, &[]);
+        assert_eq!(dag_longest_path(&empty).unwrap(), (0, vec![]));
+
+        // 每条边长度为 1，即最长路径的边数
+        let (hops, _) = dag_longest_path_by(&g, |_, _, _| 1usize).unwrap();
+        assert_eq!(hops, path.len() - 1);
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let (g, n) = build(3, &[(0, 1, 1), (1, 2, 1)]);
+        assert_eq!(
+            transitive_closure(&g),
+            vec![(n[0], n[1]), (n[0], n[2]), (n[1], n[2])]
+        );
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        // 0 -> 1 -> 2 -> 3，外加冗余边 0 -> 2、0 -> 3、1 -> 3 和重边 1 -> 2
+        let (g, n) = build(
+            4,
+            &[
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 3, 1),
+                (0, 2, 1),
+                (0, 3, 1),
+                (1, 3, 1),
+                (1, 2, 1),
+            ],
+        );
+        assert_eq!(
+            transitive_reduction(&g).unwrap(),
+            vec![(n[0], n[1]), (n[1], n[2]), (n[2], n[3])]
+        );
+    }
+
+    #[test]
+    fn test_topo_walker_releases_dependents() {
+        // 0 -> 2, 1 -> 2, 2 -> 3
+        let (g, n) = build(4, &[(0, 2, 1), (1, 2, 1), (2, 3, 1)]);
+        let mut w = TopoWalker::new(&g);
+
+        assert_eq!(w.ready_count(), 2);
+        let a = w.next_ready().unwrap();
+        let b = w.next_ready().unwrap();
+        assert_eq!(w.next_ready(), None);
+
+        w.complete(a);
+        assert_eq!(w.next_ready(), None); // 2 仍依赖 b
+        w.complete(b);
+        assert_eq!(w.next_ready(), Some(n[2]));
+        w.complete(n[2]);
+        assert_eq!(w.next_ready(), Some(n[3]));
+        w.complete(n[3]);
+        assert!(w.is_finished());
+    }
+
+    #[test]
+    fn test_topo_walker_as_iterator() {
+        let (g, _) = build(4, &[(3, 2, 1), (2, 1, 1), (1, 0, 1)]);
+        let order: Vec<_> = TopoWalker::new(&g).collect();
+        assert_topological(&g, &order);
+
+        let (cyclic, _) = build(3, &[(0, 1, 1), (1, 2, 1), (2, 1, 1)]);
+        let mut w = TopoWalker::new(&cyclic);
+        assert_eq!(w.by_ref().count(), 1);
+        assert_eq!(w.remaining(), 2);
+    }
+}
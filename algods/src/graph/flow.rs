@@ -0,0 +1,928 @@
+//! 网络流与匹配
+//!
+//! 包含 Dinic / 预流推进最大流、最小割、最小费用最大流、
+//! Hopcroft–Karp 二分图最大匹配，以及求解指派问题的匈牙利算法
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::graph::{EdgeIndex, EdgeType, Graph, GraphBase, NodeIndex};
+
+/// 容量（或费用）类型
+pub trait Capacity: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T> Capacity for T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8> {}
+
+/// 最大流结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow<C, Idx = usize> {
+    /// 最大流值
+    pub value: C,
+    /// 每条边上的流量，下标与 EdgeIndex 一致
+    pub edge_flows: Vec<C>,
+    /// 最小割中源点一侧的节点（残量网络中从源点可达）
+    pub source_side: Vec<NodeIndex<Idx>>,
+    /// 最小割中汇点一侧的节点
+    pub sink_side: Vec<NodeIndex<Idx>>,
+}
+
+impl<C: Copy, Idx: Into<usize>> MaxFlow<C, Idx> {
+    /// 返回边 e 上的流量
+    pub fn flow(&self, e: EdgeIndex<Idx>) -> C {
+        self.edge_flows[e.0.into()]
+    }
+}
+
+/// 最小费用最大流结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCostFlow<C> {
+    /// 最大流值
+    pub value: C,
+    /// 达到最大流时的最小总费用
+    pub cost: C,
+    /// 每条边上的流量，下标与 EdgeIndex 一致
+    pub edge_flows: Vec<C>,
+}
+
+/// 残量网络
+///
+/// 原图第 i 条边对应弧 2i（正向）与 2i+1（反向），`a ^ 1` 即为 a 的反向弧
+struct Residual<C> {
+    adj: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<C>,
+    orig: Vec<C>,
+}
+
+impl<C: Capacity> Residual<C> {
+    fn new<N, E, Ty, Idx>(g: &Graph<N, E, Ty, Idx>, mut capacity: impl FnMut(&E) -> C) -> Self
+    where
+        Ty: EdgeType,
+        Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    {
+        let zero = C::from(0);
+        let mut adj = vec![Vec::new(); g.node_count()];
+        let mut to = Vec::with_capacity(2 * g.edge_count());
+        let mut cap = Vec::with_capacity(2 * g.edge_count());
+        for e in g.edge_references() {
+            let (u, v) = (e.node[0].index(), e.node[1].index());
+            let c = capacity(e.weight);
+            adj[u].push(to.len());
+            to.push(v);
+            cap.push(c);
+            adj[v].push(to.len());
+            to.push(u);
+            cap.push(zero);
+        }
+        let orig = cap.clone();
+        Self { adj, to, cap, orig }
+    }
+
+    fn edge_flows(&self) -> Vec<C> {
+        (0..self.to.len() / 2)
+            .map(|i| self.orig[2 * i] - self.cap[2 * i])
+            .collect()
+    }
+
+    /// 残量网络中从 s 可达的节点
+    fn reachable(&self, s: usize) -> Vec<bool> {
+        let zero = C::from(0);
+        let mut vis = vec![false; self.adj.len()];
+        let mut q = VecDeque::new();
+        vis[s] = true;
+        q.push_back(s);
+        while let Some(u) = q.pop_front() {
+            for &a in &self.adj[u] {
+                let v = self.to[a];
+                if !vis[v] && self.cap[a] > zero {
+                    vis[v] = true;
+                    q.push_back(v);
+                }
+            }
+        }
+        vis
+    }
+
+    fn into_max_flow<Idx: From<usize>>(self, s: usize, value: C) -> MaxFlow<C, Idx> {
+        let side = self.reachable(s);
+        let (mut source_side, mut sink_side) = (Vec::new(), Vec::new());
+        for (i, &in_s) in side.iter().enumerate() {
+            if in_s {
+                source_side.push(NodeIndex(Idx::from(i)));
+            } else {
+                sink_side.push(NodeIndex(Idx::from(i)));
+            }
+        }
+        MaxFlow {
+            value,
+            edge_flows: self.edge_flows(),
+            source_side,
+            sink_side,
+        }
+    }
+}
+
+/// Dinic 最大流
+///
+/// 以边权作为容量。无向图中每条边按两个方向各自拥有该容量处理
+///
+/// ## 返回
+/// 最大流值、每条边的流量以及一个最小割
+///
+/// ## Notes
+/// 时间复杂度: O(V^2 E)
+pub fn dinic<N, E, Ty, Idx>(
+    g: &Graph<N, E, Ty, Idx>,
+    source: NodeIndex<Idx>,
+    sink: NodeIndex<Idx>,
+) -> MaxFlow<E, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Capacity,
+{
+    /// 沿分层图找一条 s 到 t 的增广路并推送其瓶颈流量，找不到时返回 0
+    ///
+    /// 以显式栈保存路径上的弧，`it[u]` 为 u 的当前弧，死路上的弧不会被再次尝试
+    fn augment<C: Capacity>(
+        r: &mut Residual<C>,
+        s: usize,
+        t: usize,
+        level: &[usize],
+        it: &mut [usize],
+    ) -> C {
+        let zero = C::from(0);
+        let mut path: Vec<usize> = Vec::new();
+        let mut u = s;
+        loop {
+            if u == t {
+                let d = path.iter().map(|&a| r.cap[a]).min().unwrap_or(zero);
+                for &a in &path {
+                    r.cap[a] = r.cap[a] - d;
+                    r.cap[a ^ 1] = r.cap[a ^ 1] + d;
+                }
+                return d;
+            }
+            let mut advanced = false;
+            while it[u] < r.adj[u].len() {
+                let a = r.adj[u][it[u]];
+                let v = r.to[a];
+                if r.cap[a] > zero && level[v] == level[u] + 1 {
+                    path.push(a);
+                    u = v;
+                    advanced = true;
+                    break;
+                }
+                it[u] += 1;
+            }
+            if !advanced {
+                // u 是死路，退回上一个节点并跳过这条弧
+                let Some(a) = path.pop() else {
+                    return zero;
+                };
+                u = r.to[a ^ 1];
+                it[u] += 1;
+            }
+        }
+    }
+
+    let zero = E::from(0);
+    let (s, t) = (source.index(), sink.index());
+    let mut r = Residual::new(g, |&c| c);
+    let mut value = zero;
+    if s == t {
+        return r.into_max_flow(s, value);
+    }
+
+    let n = r.adj.len();
+    loop {
+        let mut level = vec![usize::MAX; n];
+        let mut q = VecDeque::new();
+        level[s] = 0;
+        q.push_back(s);
+        while let Some(u) = q.pop_front() {
+            for &a in &r.adj[u] {
+                let v = r.to[a];
+                if level[v] == usize::MAX && r.cap[a] > zero {
+                    level[v] = level[u] + 1;
+                    q.push_back(v);
+                }
+            }
+        }
+        if level[t] == usize::MAX {
+            break;
+        }
+
+        let mut it = vec![0; n];
+        loop {
+            let d = augment(&mut r, s, t, &level, &mut it);
+            if d == zero {
+                break;
+            }
+            value = value + d;
+        }
+    }
+    r.into_max_flow(s, value)
+}
+
+/// 预流推进（push-relabel）最大流，FIFO 选点
+///
+/// 以边权作为容量，结果与 [`dinic`] 相同
+///
+/// ## Notes
+/// 时间复杂度: O(V^3)
+pub fn push_relabel<N, E, Ty, Idx>(
+    g: &Graph<N, E, Ty, Idx>,
+    source: NodeIndex<Idx>,
+    sink: NodeIndex<Idx>,
+) -> MaxFlow<E, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Capacity,
+{
+    let zero = E::from(0);
+    let (s, t) = (source.index(), sink.index());
+    let mut r = Residual::new(g, |&c| c);
+    if s == t {
+        return r.into_max_flow(s, zero);
+    }
+
+    let n = r.adj.len();
+    let mut height = vec![0; n];
+    let mut excess = vec![zero; n];
+    let mut cur = vec![0; n];
+    let mut active = VecDeque::new();
+
+    height[s] = n;
+    for i in 0..r.adj[s].len() {
+        let a = r.adj[s][i];
+        let (v, c) = (r.to[a], r.cap[a]);
+        if c > zero {
+            r.cap[a] = zero;
+            r.cap[a ^ 1] = r.cap[a ^ 1] + c;
+            excess[v] = excess[v] + c;
+            if v != s && v != t && excess[v] == c {
+                active.push_back(v);
+            }
+        }
+    }
+
+    while let Some(u) = active.pop_front() {
+        // discharge
+        while excess[u] > zero {
+            if cur[u] == r.adj[u].len() {
+                height[u] = r.adj[u]
+                    .iter()
+                    .filter(|&&a| r.cap[a] > zero)
+                    .map(|&a| height[r.to[a]] + 1)
+                    .min()
+                    .expect("node with excess has no residual arc");
+                cur[u] = 0;
+                continue;
+            }
+            let a = r.adj[u][cur[u]];
+            let v = r.to[a];
+            if r.cap[a] > zero && height[u] == height[v] + 1 {
+                let d = excess[u].min(r.cap[a]);
+                r.cap[a] = r.cap[a] - d;
+                r.cap[a ^ 1] = r.cap[a ^ 1] + d;
+                excess[u] = excess[u] - d;
+                let was_idle = excess[v] == zero;
+                excess[v] = excess[v] + d;
+                if was_idle && v != s && v != t {
+                    active.push_back(v);
+                }
+            } else {
+                cur[u] += 1;
+            }
+        }
+    }
+    r.into_max_flow(s, excess[t])
+}
+
+/// 最小费用最大流（带势能的逐次最短路）
+///
+/// `params` 将边权映射为 (容量, 单位费用)。费用可以为负，但图中不能有负费用环。
+/// 残量网络的反向弧费用为负，因此费用类型须为有符号类型
+///
+/// ## Notes
+/// 时间复杂度: O(F * E log V)，F 为增广次数
+pub fn min_cost_max_flow<N, E, Ty, Idx, C, F>(
+    g: &Graph<N, E, Ty, Idx>,
+    source: NodeIndex<Idx>,
+    sink: NodeIndex<Idx>,
+    mut params: F,
+) -> MinCostFlow<C>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    C: Capacity + Mul<Output = C> + Neg<Output = C>,
+    F: FnMut(&E) -> (C, C),
+{
+    let zero = C::from(0);
+    let (s, t) = (source.index(), sink.index());
+    let mut cost = Vec::with_capacity(2 * g.edge_count());
+    let mut r = Residual::new(g, |w| {
+        let (c, w) = params(w);
+        cost.push(w);
+        cost.push(-w);
+        c
+    });
+
+    if s == t {
+        return MinCostFlow {
+            value: zero,
+            cost: zero,
+            edge_flows: r.edge_flows(),
+        };
+    }
+
+    let n = r.adj.len();
+    // Bellman-Ford 求初始势能，允许负费用边
+    let mut pot: Vec<Option<C>> = vec![None; n];
+    pot[s] = Some(zero);
+    for _ in 0..n {
+        let mut changed = false;
+        for u in 0..n {
+            let Some(pu) = pot[u] else { continue };
+            for &a in &r.adj[u] {
+                let v = r.to[a];
+                let nd = pu + cost[a];
+                if r.cap[a] > zero && pot[v].is_none_or(|pv| nd < pv) {
+                    pot[v] = Some(nd);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut pot: Vec<C> = pot.into_iter().map(|p| p.unwrap_or(zero)).collect();
+
+    let (mut value, mut total) = (zero, zero);
+    loop {
+        // 约化费用 cost + pot[u] - pot[v] 非负，可使用 Dijkstra
+        let mut dist: Vec<Option<C>> = vec![None; n];
+        let mut prev = vec![usize::MAX; n];
+        let mut heap = BinaryHeap::new();
+        dist[s] = Some(zero);
+        heap.push((Reverse(zero), s));
+        while let Some((Reverse(d), u)) = heap.pop() {
+            if dist[u].is_some_and(|best| d > best) {
+                continue;
+            }
+            for &a in &r.adj[u] {
+                let v = r.to[a];
+                if r.cap[a] == zero {
+                    continue;
+                }
+                let nd = d + cost[a] + pot[u] - pot[v];
+                if dist[v].is_none_or(|old| nd < old) {
+                    dist[v] = Some(nd);
+                    prev[v] = a;
+                    heap.push((Reverse(nd), v));
+                }
+            }
+        }
+        if dist[t].is_none() {
+            break;
+        }
+        for (p, d) in pot.iter_mut().zip(&dist) {
+            if let Some(d) = *d {
+                *p = *p + d;
+            }
+        }
+
+        let mut f = None::<C>;
+        let mut v = t;
+        while v != s {
+            let a = prev[v];
+            f = Some(f.map_or(r.cap[a], |f| f.min(r.cap[a])));
+            v = r.to[a ^ 1];
+        }
+        let f = f.unwrap();
+        let mut v = t;
+        while v != s {
+            let a = prev[v];
+            r.cap[a] = r.cap[a] - f;
+            r.cap[a ^ 1] = r.cap[a ^ 1] + f;
+            total = total + f * cost[a];
+            v = r.to[a ^ 1];
+        }
+        value = value + f;
+    }
+
+    MinCostFlow {
+        value,
+        cost: total,
+        edge_flows: r.edge_flows(),
+    }
+}
+
+/// Hopcroft–Karp 二分图最大匹配
+///
+/// `left` 为左部节点，右部节点为它们的邻居（邻居中属于左部的节点被忽略）。
+/// 有向图与无向图均可使用
+///
+/// ## 返回
+/// 匹配边 (left, right) 列表，按 `left` 中的顺序排列
+///
+/// ## Notes
+/// 时间复杂度: O(E √V)
+pub fn hopcroft_karp<G: GraphBase>(g: &G, left: &[G::Node]) -> Vec<(G::Node, G::Node)> {
+    const NIL: usize = usize::MAX;
+
+    let left_set: HashSet<G::Node> = left.iter().copied().collect();
+    let mut right = Vec::new();
+    let mut right_index: HashMap<G::Node, usize> = HashMap::new();
+    let adj: Vec<Vec<usize>> = left
+        .iter()
+        .map(|&u| {
+            g.neighbors(u)
+                .filter(|(v, _)| !left_set.contains(v))
+                .map(|(v, _)| {
+                    *right_index.entry(v).or_insert_with(|| {
+                        right.push(v);
+                        right.len() - 1
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut match_l = vec![NIL; left.len()];
+    let mut match_r = vec![NIL; right.len()];
+    let mut dist = vec![0usize; left.len()];
+
+    fn bfs(adj: &[Vec<usize>], match_l: &[usize], match_r: &[usize], dist: &mut [usize]) -> bool {
+        let mut q = VecDeque::new();
+        for (u, d) in dist.iter_mut().enumerate() {
+            if match_l[u] == NIL {
+                *d = 0;
+                q.push_back(u);
+            } else {
+                *d = usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(u) = q.pop_front() {
+            for &v in &adj[u] {
+                match match_r[v] {
+                    NIL => found = true,
+                    w if dist[w] == usize::MAX => {
+                        dist[w] = dist[u] + 1;
+                        q.push_back(w);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        found
+    }
+
+    /// 从未匹配的左部节点 root 出发沿分层图找增广路，找到时翻转路径上的匹配
+    ///
+    /// 栈中为路径上的左部节点，`it[u]` 为 u 的当前邻居下标
+    fn dfs(
+        root: usize,
+        adj: &[Vec<usize>],
+        match_l: &mut [usize],
+        match_r: &mut [usize],
+        dist: &mut [usize],
+        it: &mut [usize],
+    ) -> bool {
+        let mut stack = vec![root];
+        while let Some(&u) = stack.last() {
+            let Some(&v) = adj[u].get(it[u]) else {
+                // 邻居用尽，u 在本轮不再可用
+                dist[u] = usize::MAX;
+                stack.pop();
+                if let Some(&p) = stack.last() {
+                    it[p] += 1;
+                }
+                continue;
+            };
+            match match_r[v] {
+                NIL => {
+                    for &x in &stack {
+                        let y = adj[x][it[x]];
+                        match_l[x] = y;
+                        match_r[y] = x;
+                    }
+                    return true;
+                }
+                w if dist[w] == dist[u] + 1 => stack.push(w),
+                _ => it[u] += 1,
+            }
+        }
+        false
+    }
+
+    let mut it = vec![0; left.len()];
+    while bfs(&adj, &match_l, &match_r, &mut dist) {
+        it.fill(0);
+        for u in 0..left.len() {
+            if match_l[u] == NIL {
+                dfs(u, &adj, &mut match_l, &mut match_r, &mut dist, &mut it);
+            }
+        }
+    }
+
+    match_l
+        .iter()
+        .enumerate()
+        .filter(|&(_, &v)| v != NIL)
+        .map(|(u, &v)| (left[u], right[v]))
+        .collect()
+}
+
+/// 匈牙利算法（Kuhn–Munkres）求解最小费用指派
+///
+/// `cost[i][j]` 为第 i 行分配到第 j 列的费用，矩阵可以不是方阵，
+/// 此时较少的一侧全部被分配
+///
+/// ## 返回
+/// (最小总费用, 每一行分配到的列)
+///
+/// ## Notes
+/// 计算过程中的势能与约化费用均保持非负，费用可以为无符号类型
+///
+/// 时间复杂度: O(n^2 m)，n = min(行, 列)，m = max(行, 列)
+pub fn hungarian<C, R>(cost: &[R]) -> (C, Vec<Option<usize>>)
+where
+    C: Capacity,
+    R: AsRef<[C]>,
+{
+    let rows = cost.len();
+    let cols = cost.first().map_or(0, |r| r.as_ref().len());
+    assert!(
+        cost.iter().all(|r| r.as_ref().len() == cols),
+        "cost matrix rows must have equal length"
+    );
+
+    if rows <= cols {
+        let col_of = hungarian_inner(rows, cols, |i, j| cost[i].as_ref()[j]);
+        let total = col_of
+            .iter()
+            .enumerate()
+            .fold(C::from(0), |acc, (i, &j)| acc + cost[i].as_ref()[j]);
+        (total, col_of.into_iter().map(Some).collect())
+    } else {
+        // 行多于列时转置求解
+        let row_of = hungarian_inner(cols, rows, |j, i| cost[i].as_ref()[j]);
+        let mut col_of = vec![None; rows];
+        let mut total = C::from(0);
+        for (j, &i) in row_of.iter().enumerate() {
+            col_of[i] = Some(j);
+            total = total + cost[i].as_ref()[j];
+        }
+        (total, col_of)
+    }
+}
+
+/// 以 [`num::matrix::Matrix`] 作为费用矩阵的 [`hungarian`]
+#[cfg(not(feature = "core"))]
+pub fn hungarian_matrix<C>(cost: &num::matrix::Matrix<C>) -> (C, Vec<Option<usize>>)
+where
+    C: Capacity + num::Number,
+{
+    use num::matrix::MatrixBase;
+
+    let rows: Vec<&[C]> = (0..cost.rows()).map(|i| &cost[i]).collect();
+    hungarian(&rows)
+}
+
+/// n <= m 时的匈牙利算法，返回每行匹配的列
+fn hungarian_inner<C: Capacity>(n: usize, m: usize, a: impl Fn(usize, usize) -> C) -> Vec<usize> {
+    let zero = C::from(0);
+    // 1-based，p[j] 为匹配到第 j 列的行，0 表示未匹配。
+    // 行势能 u 只增不减；列势能只减不增，存其相反数 neg_v 使两者都非负
+    let mut u = vec![zero; n + 1];
+    let mut neg_v = vec![zero; m + 1];
+    let mut p = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv: Vec<Option<C>> = vec![None; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = None::<C>;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                // 约化费用 a - u - v >= 0，先加后减避免无符号类型下溢
+                let cur = a(i0 - 1, j - 1) + neg_v[j] - u[i0];
+                if minv[j].is_none_or(|mv| cur < mv) {
+                    minv[j] = Some(cur);
+                    way[j] = j0;
+                }
+                if delta.is_none_or(|d| minv[j].unwrap() < d) {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            let delta = delta.unwrap();
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    neg_v[j] = neg_v[j] + delta;
+                } else if let Some(mv) = minv[j].as_mut() {
+                    *mv = *mv - delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut col_of = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            col_of[p[j] - 1] = j - 1;
+        }
+    }
+    col_of
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Directed, Undirected};
+
+    /// CLRS 经典示例，最大流为 23
+    fn clrs() -> (Graph<(), i32, Directed>, Vec<NodeIndex>) {
+        let mut g = Graph::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        for (a, b, c) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            g.add_edge(n[a], n[b], c);
+        }
+        (g, n)
+    }
+
+    fn check_flow(g: &Graph<(), i32, Directed>, f: &MaxFlow<i32>, s: NodeIndex, t: NodeIndex) {
+        // 容量约束
+        for e in g.edge_references() {
+            assert!(0 <= f.flow(e.index) && f.flow(e.index) <= *e.weight);
+        }
+        // 流量守恒
+        for u in g.node_indices() {
+            let out: i32 = g
+                .edges_directed(u, crate::graph::Direction::Outgoing)
+                .map(|e| f.flow(e.index))
+                .sum();
+            let inn: i32 = g
+                .edges_directed(u, crate::graph::Direction::Incoming)
+                .map(|e| f.flow(e.index))
+                .sum();
+            if u == s {
+                assert_eq!(out - inn, f.value);
+            } else if u == t {
+                assert_eq!(inn - out, f.value);
+            } else {
+                assert_eq!(inn, out);
+            }
+        }
+        // 割容量等于流值
+        let cut: i32 = g
+            .edge_references()
+            .filter(|e| f.source_side.contains(&e.node[0]) && f.sink_side.contains(&e.node[1]))
+            .map(|e| *e.weight)
+            .sum();
+        assert_eq!(cut, f.value);
+        assert!(f.source_side.contains(&s));
+        assert!(f.sink_side.contains(&t));
+    }
+
+    #[test]
+    fn test_dinic() {
+        let (g, n) = clrs();
+        let f = dinic(&g, n[0], n[5]);
+        assert_eq!(f.value, 23);
+        check_flow(&g, &f, n[0], n[5]);
+    }
+
+    #[test]
+    fn test_dinic_saturated_source() {
+        // 源点出边容量之和超出 u32 范围
+        let mut g: Graph<(), u32, Directed> = Graph::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], u32::MAX);
+        g.add_edge(n[0], n[2], u32::MAX);
+        g.add_edge(n[1], n[3], u32::MAX - 1);
+        g.add_edge(n[2], n[3], 1);
+        assert_eq!(dinic(&g, n[0], n[3]).value, u32::MAX);
+    }
+
+    #[test]
+    fn test_dinic_long_path() {
+        // 增广路长度与节点数相同，不能依赖递归深度
+        let mut g: Graph<(), i32, Directed> = Graph::new();
+        let n: Vec<_> = (0..200_000).map(|_| g.add_node(())).collect();
+        for (i, w) in n.windows(2).enumerate() {
+            g.add_edge(w[0], w[1], 3 + (i % 5) as i32);
+        }
+        let f = dinic(&g, n[0], n[n.len() - 1]);
+        assert_eq!(f.value, 3);
+        assert_eq!(f.source_side, vec![n[0]]);
+    }
+
+    #[test]
+    fn test_push_relabel() {
+        let (g, n) = clrs();
+        let f = push_relabel(&g, n[0], n[5]);
+        assert_eq!(f.value, 23);
+        check_flow(&g, &f, n[0], n[5]);
+    }
+
+    #[test]
+    fn test_max_flow_disconnected() {
+        let mut g: Graph<(), i32, Directed> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 5);
+
+        let f = dinic(&g, a, c);
+        assert_eq!(f.value, 0);
+        assert_eq!(f.source_side, vec![a, b]);
+        assert_eq!(push_relabel(&g, a, c).value, 0);
+    }
+
+    #[test]
+    fn test_max_flow_undirected() {
+        // a - b - c，容量 3 和 2；a - c 容量 1
+        let mut g: Graph<(), u32, Undirected> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 3);
+        g.add_edge(b, c, 2);
+        g.add_edge(a, c, 1);
+
+        assert_eq!(dinic(&g, a, c).value, 3);
+        assert_eq!(push_relabel(&g, c, a).value, 3);
+    }
+
+    #[test]
+    fn test_min_cost_max_flow() {
+        // 边权为 (容量, 费用)
+        let mut g: Graph<(), (i64, i64), Directed> = Graph::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        let e01 = g.add_edge(n[0], n[1], (2, 1));
+        let e02 = g.add_edge(n[0], n[2], (1, 2));
+        let e12 = g.add_edge(n[1], n[2], (1, 1));
+        let e13 = g.add_edge(n[1], n[3], (1, 3));
+        let e23 = g.add_edge(n[2], n[3], (2, 1));
+
+        let f = min_cost_max_flow(&g, n[0], n[3], |&w| w);
+        assert_eq!(f.value, 3);
+        // 0->1->3: 4, 0->1->2->3: 3, 0->2->3: 3
+        assert_eq!(f.cost, 10);
+        let flows: Vec<_> = [e01, e02, e12, e13, e23]
+            .iter()
+            .map(|e| f.edge_flows[e.index()])
+            .collect();
+        assert_eq!(flows, vec![2, 1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_min_cost_negative_cost() {
+        let mut g: Graph<(), (i32, i32), Directed> = Graph::new();
+        let s = g.add_node(());
+        let a = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(s, a, (1, -5));
+        g.add_edge(a, t, (1, 2));
+        g.add_edge(s, t, (1, 0));
+
+        let f = min_cost_max_flow(&g, s, t, |&w| w);
+        assert_eq!((f.value, f.cost), (2, -3));
+    }
+
+    #[test]
+    fn test_hopcroft_karp() {
+        // 左部 0..4，右部 4..8
+        let mut g: Graph<(), i32, Undirected> = Graph::new();
+        let n: Vec<_> = (0..8).map(|_| g.add_node(())).collect();
+        for (a, b) in [(0, 4), (0, 5), (1, 4), (2, 5), (2, 6), (3, 6), (3, 7)] {
+            g.add_edge(n[a], n[b], 1);
+        }
+        let m = hopcroft_karp(&g, &n[..4]);
+        assert_eq!(m.len(), 4);
+
+        let lefts: HashSet<_> = m.iter().map(|&(l, _)| l).collect();
+        let rights: HashSet<_> = m.iter().map(|&(_, r)| r).collect();
+        assert_eq!(lefts.len(), 4);
+        assert_eq!(rights.len(), 4);
+        for (l, r) in m {
+            assert!(g.neighbors(l).any(|(v, _)| v == r));
+        }
+    }
+
+    #[test]
+    fn test_hopcroft_karp_partial() {
+        // 三个左部节点只能连到同一个右部节点
+        let mut g: Graph<(), i32, Directed> = Graph::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        for a in 0..3 {
+            g.add_edge(n[a], n[3], 1);
+        }
+        assert_eq!(hopcroft_karp(&g, &n[..3]).len(), 1);
+    }
+
+    #[test]
+    fn test_hopcroft_karp_long_augmenting_path() {
+        // 左部 l_i 依次连向 r_{i+1}、r_i：第一轮贪心匹配 l_i - r_{i+1}，
+        // 第二轮从 l_{k-1} 出发的增广路经过全部节点
+        let k = 100_000;
+        let mut g: Graph<(), (), Directed> = Graph::new();
+        let left: Vec<_> = (0..k).map(|_| g.add_node(())).collect();
+        let right: Vec<_> = (0..k).map(|_| g.add_node(())).collect();
+        for i in 0..k {
+            // 邻接表按插入的逆序遍历
+            g.add_edge(left[i], right[i], ());
+            if i + 1 < k {
+                g.add_edge(left[i], right[i + 1], ());
+            }
+        }
+        let m = hopcroft_karp(&g, &left);
+        assert_eq!(m.len(), k);
+        let matched: HashSet<_> = m.iter().map(|&(_, r)| r).collect();
+        assert_eq!(matched.len(), k);
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let cost = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let (total, assign) = hungarian(&cost);
+        assert_eq!(total, 5);
+        assert_eq!(assign, vec![Some(1), Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn test_hungarian_rectangular() {
+        // 2 行 3 列
+        let (total, assign) = hungarian(&[vec![10, 1, 7], vec![3, 9, 2]]);
+        assert_eq!((total, assign), (3, vec![Some(1), Some(2)]));
+
+        // 3 行 2 列，有一行不被分配
+        let (total, assign) = hungarian(&[vec![10, 3], vec![1, 9], vec![7, 2]]);
+        assert_eq!((total, assign), (3, vec![None, Some(0), Some(1)]));
+
+        let empty: [[i32; 0]; 0] = [];
+        assert_eq!(hungarian(&empty), (0, vec![]));
+    }
+
+    #[test]
+    fn test_hungarian_unsigned() {
+        let cost: [[u32; 3]; 3] = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        assert_eq!(hungarian(&cost), (5, vec![Some(1), Some(0), Some(2)]));
+
+        let cost: [[usize; 3]; 2] = [[10, 1, 7], [3, 9, 2]];
+        assert_eq!(hungarian(&cost), (3, vec![Some(1), Some(2)]));
+
+        // 需要多次调整势能的输入
+        let cost: [[u8; 4]; 4] = [[9, 2, 7, 8], [6, 4, 3, 7], [5, 8, 1, 8], [7, 6, 9, 4]];
+        assert_eq!(
+            hungarian(&cost),
+            (13, vec![Some(1), Some(0), Some(2), Some(3)])
+        );
+    }
+
+    #[cfg(not(feature = "core"))]
+    #[test]
+    fn test_hungarian_matrix() {
+        let m = num::matrix::Matrix::from([[4i64, 1, 3], [2, 0, 5], [3, 2, 2]]);
+        assert_eq!(hungarian_matrix(&m), (5, vec![Some(1), Some(0), Some(2)]));
+    }
+}
@@ -0,0 +1,278 @@
+//! 图生成器
+//!
+//! 生成常见的确定性图族与带种子的随机图，节点与边权均取 `Default::default()`。
+//! 节点按编号顺序创建，`NodeIndex(i)` 即第 i 个节点；
+//! 对有向图，无向生成器中的每条边 {u, v} 生成为 u → v（u < v）
+use crate::graph::{EdgeType, Graph};
+
+/// SplitMix64 伪随机数生成器，同一种子总是产生相同的序列
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 上的均匀分布
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [0, n) 上的均匀整数
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// 创建含 n 个节点的图，并按 (u, v) 编号添加边
+fn build<N, E, Ty, I>(n: usize, edges: I) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut g = Graph::new();
+    let nodes: Vec<_> = (0..n).map(|_| g.add_node(N::default())).collect();
+    for (u, v) in edges {
+        g.add_edge(nodes[u], nodes[v], E::default());
+    }
+    g
+}
+
+/// 完全图 K_n
+///
+/// 有向图包含所有 n(n - 1) 条有序边，无向图包含 n(n - 1) / 2 条边
+///
+/// 时间复杂度: O(n^2)
+pub fn complete_graph<N, E, Ty>(n: usize) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    let edges = (0..n).flat_map(|u| {
+        let from = if Ty::DIRECTED { 0 } else { u + 1 };
+        (from..n).filter(move |&v| v != u).map(move |v| (u, v))
+    });
+    build(n, edges)
+}
+
+/// 路径 0 - 1 - … - (n - 1)
+///
+/// 时间复杂度: O(n)
+pub fn path_graph<N, E, Ty>(n: usize) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    build(n, (1..n).map(|v| (v - 1, v)))
+}
+
+/// 环 0 - 1 - … - (n - 1) - 0
+///
+/// n < 3 时不存在简单环，退化为 [`path_graph`]
+///
+/// 时间复杂度: O(n)
+pub fn cycle_graph<N, E, Ty>(n: usize) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    if n < 3 {
+        return path_graph(n);
+    }
+    build(n, (0..n).map(|u| (u, (u + 1) % n)))
+}
+
+/// 星图：中心节点 0 与 `leaves` 个叶子 1..=leaves 相连
+///
+/// 时间复杂度: O(leaves)
+pub fn star_graph<N, E, Ty>(leaves: usize) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    build(leaves + 1, (1..=leaves).map(|v| (0, v)))
+}
+
+/// rows × cols 的二维网格，节点 (r, c) 的编号为 `r * cols + c`
+///
+/// 每个节点与右侧、下方的相邻节点相连
+///
+/// 时间复杂度: O(rows * cols)
+pub fn grid_graph<N, E, Ty>(rows: usize, cols: usize) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    let edges = (0..rows).flat_map(|r| {
+        (0..cols).flat_map(move |c| {
+            let u = r * cols + c;
+            let right = (c + 1 < cols).then_some((u, u + 1));
+            let down = (r + 1 < rows).then_some((u, u + cols));
+            right.into_iter().chain(down)
+        })
+    });
+    build(rows * cols, edges)
+}
+
+/// Erdős–Rényi 随机图 G(n, p)
+///
+/// 每对节点独立地以概率 p 相连：有向图考虑所有有序对，无向图考虑所有无序对，不含自环
+///
+/// ## 参数
+/// - `p`: 连边概率，超出 [0, 1] 时按端点截断
+/// - `seed`: 随机种子，相同的参数总是生成相同的图
+///
+/// 时间复杂度: O(n^2)
+pub fn gnp_random_graph<N, E, Ty>(n: usize, p: f64, seed: u64) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    let mut rng = SplitMix64(seed);
+    let mut edges = Vec::new();
+    for u in 0..n {
+        let from = if Ty::DIRECTED { 0 } else { u + 1 };
+        for v in from..n {
+            if v != u && rng.next_f64() < p {
+                edges.push((u, v));
+            }
+        }
+    }
+    build(n, edges)
+}
+
+/// Barabási–Albert 优先连接随机图
+///
+/// 从 m 个孤立节点开始，每个新节点与 m 个不同的已有节点相连，
+/// 选中某节点的概率与其度数成正比。有向图中边由新节点指向已有节点
+///
+/// ## 参数
+/// - `n`: 节点总数
+/// - `m`: 每个新节点的连边数
+/// - `seed`: 随机种子
+///
+/// ## Panics
+/// 不满足 `1 <= m < n` 时 panic
+///
+/// 时间复杂度: O(n * m)
+pub fn barabasi_albert_graph<N, E, Ty>(n: usize, m: usize, seed: u64) -> Graph<N, E, Ty>
+where
+    N: Default,
+    E: Clone + Default,
+    Ty: EdgeType,
+{
+    assert!(m >= 1 && m < n, "Barabási–Albert requires 1 <= m < n");
+    let mut rng = SplitMix64(seed);
+    let mut edges = Vec::with_capacity((n - m) * m);
+    // 每条边的两个端点各出现一次，均匀抽样即按度数加权
+    let mut repeated: Vec<usize> = Vec::with_capacity(2 * (n - m) * m);
+    let mut targets: Vec<usize> = (0..m).collect();
+
+    for source in m..n {
+        for &t in &targets {
+            edges.push((source, t));
+        }
+        repeated.extend(&targets);
+        repeated.extend(std::iter::repeat_n(source, m));
+
+        targets.clear();
+        while targets.len() < m {
+            let t = repeated[rng.below(repeated.len())];
+            if !targets.contains(&t) {
+                targets.push(t);
+            }
+        }
+    }
+    build(n, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Directed, GraphBase, NodeIndex, Undirected, algo, dag};
+
+    type U = Graph<(), u32, Undirected>;
+    type D = Graph<(), u32, Directed>;
+
+    fn pairs<Ty: EdgeType>(g: &Graph<(), u32, Ty>) -> Vec<(usize, usize)> {
+        g.edge_references()
+            .map(|e| (e.node[0].index(), e.node[1].index()))
+            .collect()
+    }
+
+    fn degree<G: GraphBase<Node = NodeIndex>>(g: &G, i: usize) -> usize {
+        g.neighbors(NodeIndex(i)).count()
+    }
+
+    #[test]
+    fn test_deterministic_families() {
+        let k: U = complete_graph(5);
+        assert_eq!((k.node_count(), k.edge_count() / 2), (5, 10));
+        assert!((0..5).all(|i| degree(&k, i) == 4));
+        let k: D = complete_graph(4);
+        assert_eq!(k.edge_count(), 12);
+
+        let p: D = path_graph(4);
+        assert_eq!(dag::toposort(&p).unwrap().len(), 4);
+        assert_eq!(algo::dijkstra(&p, NodeIndex(0), NodeIndex(3)), Some(0));
+
+        let c: D = cycle_graph(5);
+        assert!(dag::is_cyclic_directed(&c));
+        assert_eq!(algo::dfs(&c, NodeIndex(2)).len(), 5);
+        let c: U = cycle_graph(2);
+        assert_eq!(c.edge_count(), 2);
+
+        let s: U = star_graph(6);
+        assert_eq!((s.node_count(), degree(&s, 0), degree(&s, 6)), (7, 6, 1));
+
+        let g: U = grid_graph(3, 4);
+        assert_eq!(g.node_count(), 12);
+        assert_eq!(g.edge_count() / 2, 3 * 3 + 2 * 4);
+        assert_eq!((degree(&g, 0), degree(&g, 5), degree(&g, 11)), (2, 4, 2));
+    }
+
+    #[test]
+    fn test_gnp_random_graph() {
+        let a: D = gnp_random_graph(30, 0.2, 42);
+        let b: D = gnp_random_graph(30, 0.2, 42);
+        assert_eq!(pairs(&a), pairs(&b));
+        let c: D = gnp_random_graph(30, 0.2, 43);
+        assert_ne!(pairs(&a), pairs(&c));
+
+        let empty: U = gnp_random_graph(10, 0.0, 1);
+        assert_eq!(empty.edge_count(), 0);
+        let full: U = gnp_random_graph(10, 1.0, 1);
+        assert_eq!(full.edge_count() / 2, 45);
+    }
+
+    #[test]
+    fn test_barabasi_albert_graph() {
+        let g: U = barabasi_albert_graph(100, 3, 7);
+        assert_eq!(g.node_count(), 100);
+        assert_eq!(g.edge_count() / 2, 97 * 3);
+        assert_eq!(algo::bfs(&g, NodeIndex(0)).len(), 100);
+        // 无重边
+        for u in g.node_indices() {
+            let mut nb: Vec<_> = g.neighbors(u).map(|(v, _)| v).collect();
+            let len = nb.len();
+            nb.sort();
+            nb.dedup();
+            assert_eq!(nb.len(), len);
+        }
+
+        let h: U = barabasi_albert_graph(100, 3, 7);
+        assert_eq!(pairs(&g), pairs(&h));
+    }
+}
@@ -61,6 +61,17 @@ where
     inner: EdgeIter<'a, N, E, Ty, Idx>,
 }
 
+/// 全部边的迭代器
+pub struct EdgeReferences<'a, E, Idx> {
+    iter: std::iter::Enumerate<std::slice::Iter<'a, Edge<E, Idx>>>,
+}
+
+/// 节点索引迭代器
+pub struct NodeIndices<Idx> {
+    range: std::ops::Range<usize>,
+    _boo: PhantomData<Idx>,
+}
+
 impl<N, E, Ty, Idx> Graph<N, E, Ty, Idx>
 where
     Ty: EdgeType,
@@ -204,6 +215,58 @@ where
         }
     }
 
+    /// 节点数量
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 边数量（无向图中每条边计为两条有向边）
+    ///
+    /// 时间复杂度: O(1)
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// 获取节点权重
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_weight(&self, node: NodeIndex<Idx>) -> Option<&N> {
+        self.nodes.get(node.0.into()).map(|n| &n.weight)
+    }
+
+    /// 获取边权重
+    ///
+    /// 时间复杂度: O(1)
+    pub fn edge_weight(&self, e: EdgeIndex<Idx>) -> Option<&E> {
+        self.edges.get(e.0.into()).map(|e| &e.weight)
+    }
+
+    /// 按索引顺序返回所有边（无向图中包含自动补充的反向边）
+    ///
+    /// 时间复杂度: O(1)
+    pub fn edge_references(&self) -> EdgeReferences<'_, E, Idx> {
+        EdgeReferences {
+            iter: self.edges.iter().enumerate(),
+        }
+    }
+
+    /// 判断一条边是否为无向图中由 [`add_edge`](Graph::add_edge) 自动补充的反向边
+    ///
+    /// 时间复杂度: O(1)
+    pub fn is_mirror_edge(&self, e: EdgeIndex<Idx>) -> bool {
+        // 无向图中原边与反向边成对连续存放
+        !Ty::DIRECTED && e.0.into() % 2 == 1
+    }
+
+    /// 返回所有节点索引
+    ///
+    /// 时间复杂度: O(1)
+    pub fn node_indices(&self) -> NodeIndices<Idx> {
+        NodeIndices::new(self.nodes.len())
+    }
+
     /// 返回节点的所有出邻居（对有向图）
     /// 无向图时等价于所有相邻节点
     ///
@@ -219,7 +282,7 @@ impl<N, E, Ty, Idx> GraphBase for Graph<N, E, Ty, Idx>
 where
     Ty: EdgeType,
     Idx: Copy + Eq + Hash + From<usize> + Into<usize> + Ord,
-    E: Copy + Ord + std::ops::Add<Output = E>,
+    E: Clone,
 {
     type Node = NodeIndex<Idx>;
     type EdgeWeight = E;
@@ -229,12 +292,21 @@ where
     where
         Self: 'a;
 
+    type Nodes<'a>
+        = NodeIndices<Idx>
+    where
+        Self: 'a;
+
     fn neighbors(&self, n: Self::Node) -> Self::Neighbors<'_> {
         Neighbors {
             inner: self.edges_directed(n, Direction::Outgoing),
         }
     }
 
+    fn nodes(&self) -> Self::Nodes<'_> {
+        self.node_indices()
+    }
+
     fn node_count(&self) -> usize {
         self.nodes.len()
     }
@@ -278,12 +350,57 @@ impl<'a, N, E, Ty, Idx> Iterator for Neighbors<'a, N, E, Ty, Idx>
 where
     Ty: EdgeType,
     Idx: Copy + PartialEq + From<usize> + Into<usize>,
-    E: Copy,
+    E: Clone,
 {
     type Item = (NodeIndex<Idx>, E);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|edge| (edge.node[1], *edge.weight))
+        self.inner
+            .next()
+            .map(|edge| (edge.node[1], edge.weight.clone()))
+    }
+}
+
+impl<'a, E, Idx> Iterator for EdgeReferences<'a, E, Idx>
+where
+    Idx: Copy + From<usize>,
+{
+    type Item = EdgeReference<'a, E, Idx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(i, edge)| EdgeReference {
+            index: EdgeIndex(Idx::from(i)),
+            weight: &edge.weight,
+            node: edge.node,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<Idx> NodeIndices<Idx> {
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            range: 0..count,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<Idx> Iterator for NodeIndices<Idx>
+where
+    Idx: From<usize>,
+{
+    type Item = NodeIndex<Idx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| NodeIndex(Idx::from(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
     }
 }
 
@@ -383,6 +500,25 @@ mod tests {
         assert_eq!(b_neighbors, vec![a]);
     }
 
+    #[test]
+    fn test_edge_references() {
+        let mut g: Graph<&str, i32, Directed> = Graph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let e0 = g.add_edge(a, b, 1);
+        let e1 = g.add_edge(b, c, 2);
+
+        let edges: Vec<_> = g
+            .edge_references()
+            .map(|e| (e.index, e.node, *e.weight))
+            .collect();
+        assert_eq!(edges, vec![(e0, [a, b], 1), (e1, [b, c], 2)]);
+        assert_eq!(g.edge_weight(e1), Some(&2));
+        assert_eq!(g.node_weight(c), Some(&"c"));
+        assert_eq!(g.node_indices().collect::<Vec<_>>(), vec![a, b, c]);
+    }
+
     #[test]
     fn test_empty_graph() {
         let g: Graph<i32, i32, Directed> = Graph::new();
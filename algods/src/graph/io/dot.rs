@@ -0,0 +1,569 @@
+//! DOT（Graphviz）格式
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    error::AlgodsResult,
+    graph::{
+        EdgeType, Graph, NodeIndex,
+        io::{Attributes, parse_error},
+    },
+};
+
+impl<N, E, Ty, Idx> Graph<N, E, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Clone,
+{
+    /// 导出为 DOT 格式
+    ///
+    /// 节点以索引作为 ID，`node_label` / `edge_label` 的返回值写入 `label` 属性。
+    /// 无向图中每条边只输出一次
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V + E)
+    pub fn to_dot<FN, FE>(&self, mut node_label: FN, mut edge_label: FE) -> String
+    where
+        FN: FnMut(NodeIndex<Idx>, &N) -> String,
+        FE: FnMut(&E) -> String,
+    {
+        let (kind, op) = if Ty::DIRECTED {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut out = format!("{kind} {{\n");
+        for n in self.node_indices() {
+            let label = node_label(n, self.node_weight(n).unwrap());
+            let _ = writeln!(out, "    {} [label={}];", n.index(), quote(&label));
+        }
+        for e in self.edge_references() {
+            if self.is_mirror_edge(e.index) {
+                continue;
+            }
+            let label = edge_label(e.weight);
+            let _ = writeln!(
+                out,
+                "    {} {op} {} [label={}];",
+                e.node[0].index(),
+                e.node[1].index(),
+                quote(&label)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// 从 DOT 格式解析图
+    ///
+    /// 支持的子集：`graph` / `digraph` 头、节点语句、链式边语句（`a -> b -> c`）、
+    /// 属性列表、`graph` / `node` / `edge` 默认属性语句（被忽略）以及注释；不支持子图。
+    /// 节点按首次出现的顺序创建，`node` 接收节点 ID 与其全部属性，`edge` 接收边的属性
+    ///
+    /// ## 返回
+    /// - Ok(graph)：解析成功
+    /// - Err(AlgodsError::Parse)：语法错误，或图类型与 `Ty` 不一致
+    pub fn from_dot<FN, FE>(src: &str, mut node: FN, mut edge: FE) -> AlgodsResult<Self>
+    where
+        FN: FnMut(&str, &Attributes) -> N,
+        FE: FnMut(&Attributes) -> E,
+    {
+        let parsed = DotParser::new(src)?.parse(Ty::DIRECTED)?;
+        let mut g = Self::new();
+        let ids: Vec<_> = parsed
+            .nodes
+            .iter()
+            .map(|(id, attrs)| g.add_node(node(id, attrs)))
+            .collect();
+        for (a, b, attrs) in &parsed.edges {
+            g.add_edge(ids[*a], ids[*b], edge(attrs));
+        }
+        Ok(g)
+    }
+}
+
+/// 给 DOT ID 加引号并转义
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semi,
+    Comma,
+    Eq,
+    EdgeOp(bool), // true = "->"
+}
+
+#[derive(Default)]
+struct ParsedDot {
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(usize, usize, Attributes)>,
+}
+
+struct DotParser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    index: HashMap<String, usize>,
+    out: ParsedDot,
+}
+
+impl DotParser {
+    fn new(src: &str) -> AlgodsResult<Self> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+            pos: 0,
+            index: HashMap::new(),
+            out: ParsedDot::default(),
+        })
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |&(_, l)| l)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, want: Token) -> AlgodsResult<()> {
+        let line = self.line();
+        match self.bump() {
+            Some(t) if t == want => Ok(()),
+            Some(t) => Err(parse_error(line, format!("expected {want:?}, found {t:?}"))),
+            None => Err(parse_error(
+                line,
+                format!("expected {want:?}, found end of input"),
+            )),
+        }
+    }
+
+    fn id(&mut self) -> AlgodsResult<String> {
+        let line = self.line();
+        match self.bump() {
+            Some(Token::Id(s)) => Ok(s),
+            Some(t) => Err(parse_error(
+                line,
+                format!("expected identifier, found {t:?}"),
+            )),
+            None => Err(parse_error(line, "expected identifier, found end of input")),
+        }
+    }
+
+    fn parse(mut self, directed: bool) -> AlgodsResult<ParsedDot> {
+        let line = self.line();
+        let mut kw = self.id()?;
+        if kw.eq_ignore_ascii_case("strict") {
+            kw = self.id()?;
+        }
+        let is_digraph = if kw.eq_ignore_ascii_case("digraph") {
+            true
+        } else if kw.eq_ignore_ascii_case("graph") {
+            false
+        } else {
+            return Err(parse_error(
+                line,
+                format!("expected graph or digraph, found {kw}"),
+            ));
+        };
+        if is_digraph != directed {
+            return Err(parse_error(
+                line,
+                "graph kind does not match the target graph type",
+            ));
+        }
+        if matches!(self.peek(), Some(Token::Id(_))) {
+            self.bump();
+        }
+        self.expect(Token::LBrace)?;
+
+        loop {
+            let line = self.line();
+            match self.peek() {
+                Some(Token::RBrace) => {
+                    self.bump();
+                    break;
+                }
+                Some(Token::Semi) => {
+                    self.bump();
+                }
+                Some(Token::Id(_)) => self.stmt(is_digraph)?,
+                Some(t) => return Err(parse_error(line, format!("unexpected {t:?}"))),
+                None => return Err(parse_error(line, "unexpected end of input, missing '}'")),
+            }
+        }
+        if let Some(t) = self.peek() {
+            let line = self.line();
+            return Err(parse_error(
+                line,
+                format!("unexpected {t:?} after graph body"),
+            ));
+        }
+        Ok(self.out)
+    }
+
+    fn stmt(&mut self, is_digraph: bool) -> AlgodsResult<()> {
+        let line = self.line();
+        let first = self.id()?;
+        if first.eq_ignore_ascii_case("subgraph") {
+            return Err(parse_error(line, "subgraphs are not supported"));
+        }
+        // 默认属性语句
+        if ["graph", "node", "edge"]
+            .iter()
+            .any(|k| first.eq_ignore_ascii_case(k))
+            && self.peek() == Some(&Token::LBracket)
+        {
+            self.attr_list()?;
+            return Ok(());
+        }
+        // ID = ID 形式的图属性
+        if self.peek() == Some(&Token::Eq) {
+            self.bump();
+            self.id()?;
+            return Ok(());
+        }
+
+        let mut chain = vec![self.node_id(first)];
+        while let Some(&Token::EdgeOp(arrow)) = self.peek() {
+            let line = self.line();
+            if arrow != is_digraph {
+                return Err(parse_error(line, "edge operator does not match graph kind"));
+            }
+            self.bump();
+            let id = self.id()?;
+            chain.push(self.node_id(id));
+        }
+
+        let attrs = if self.peek() == Some(&Token::LBracket) {
+            self.attr_list()?
+        } else {
+            Attributes::new()
+        };
+        if chain.len() == 1 {
+            self.out.nodes[chain[0]].1.extend(attrs);
+        } else {
+            for w in chain.windows(2) {
+                self.out.edges.push((w[0], w[1], attrs.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn node_id(&mut self, id: String) -> usize {
+        if let Some(&i) = self.index.get(&id) {
+            return i;
+        }
+        let i = self.out.nodes.len();
+        self.index.insert(id.clone(), i);
+        self.out.nodes.push((id, Attributes::new()));
+        i
+    }
+
+    fn attr_list(&mut self) -> AlgodsResult<Attributes> {
+        let mut attrs = Attributes::new();
+        while self.peek() == Some(&Token::LBracket) {
+            self.bump();
+            loop {
+                match self.peek() {
+                    Some(Token::RBracket) => {
+                        self.bump();
+                        break;
+                    }
+                    Some(Token::Semi | Token::Comma) => {
+                        self.bump();
+                    }
+                    _ => {
+                        let key = self.id()?;
+                        let val = if self.peek() == Some(&Token::Eq) {
+                            self.bump();
+                            self.id()?
+                        } else {
+                            "true".to_string()
+                        };
+                        attrs.insert(key, val);
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// 词法分析，返回记号及其所在行
+fn tokenize(src: &str) -> AlgodsResult<Vec<(Token, usize)>> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            // 预处理行
+            '#' if line_start => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let start = line;
+                i += 2;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(parse_error(start, "unterminated comment")),
+                        Some('*') if chars.get(i + 1) == Some(&'/') => {
+                            i += 2;
+                            break;
+                        }
+                        Some(&ch) => {
+                            if ch == '\n' {
+                                line += 1;
+                            }
+                            i += 1;
+                        }
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        line_start = false;
+
+        let tok = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ';' => Token::Semi,
+            ',' => Token::Comma,
+            '=' => Token::Eq,
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                i += 1;
+                Token::EdgeOp(true)
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                i += 1;
+                Token::EdgeOp(false)
+            }
+            '"' => {
+                let start = line;
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(parse_error(start, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some('"') => s.push('"'),
+                                Some('\\') => s.push('\\'),
+                                Some('n') => s.push('\n'),
+                                Some('\n') => line += 1, // 续行
+                                Some(&other) => {
+                                    s.push('\\');
+                                    s.push(other);
+                                }
+                                None => {}
+                            }
+                            i += 2;
+                            continue;
+                        }
+                        Some(&ch) => {
+                            if ch == '\n' {
+                                line += 1;
+                            }
+                            s.push(ch);
+                        }
+                    }
+                    i += 1;
+                }
+                Token::Id(s)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut s = String::new();
+                while let Some(&ch) = chars.get(i) {
+                    let is_edge_op =
+                        ch == '-' && !s.is_empty() && matches!(chars.get(i + 1), Some('>' | '-'));
+                    if !(ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '-') || is_edge_op
+                    {
+                        break;
+                    }
+                    s.push(ch);
+                    i += 1;
+                }
+                tokens.push((Token::Id(s), line));
+                continue;
+            }
+            other => {
+                return Err(parse_error(line, format!("unexpected character {other:?}")));
+            }
+        };
+        tokens.push((tok, line));
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::AlgodsError,
+        graph::{Directed, Graph, Undirected},
+    };
+
+    #[test]
+    fn test_to_dot_directed() {
+        let mut g: Graph<&str, i32, Directed> = Graph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b \"x\"");
+        g.add_edge(a, b, 5);
+
+        let dot = g.to_dot(|_, w| w.to_string(), |w| w.to_string());
+        assert_eq!(
+            dot,
+            "digraph {\n    0 [label=\"a\"];\n    1 [label=\"b \\\"x\\\"\"];\n    0 -> 1 [label=\"5\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_to_dot_undirected_emits_each_edge_once() {
+        let mut g: Graph<(), i32, Undirected> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, 1);
+
+        let dot = g.to_dot(|n, _| n.index().to_string(), |_| String::new());
+        assert!(dot.starts_with("graph {"));
+        assert_eq!(dot.matches("--").count(), 1);
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let mut g: Graph<String, i32, Directed> = Graph::new();
+        let a = g.add_node("build".to_string());
+        let b = g.add_node("test".to_string());
+        let c = g.add_node("deploy".to_string());
+        g.add_edge(a, b, 1);
+        g.add_edge(b, c, 2);
+
+        let dot = g.to_dot(|_, w| w.clone(), |w| w.to_string());
+        let h: Graph<String, i32, Directed> = Graph::from_dot(
+            &dot,
+            |_, attrs| attrs["label"].clone(),
+            |attrs| attrs["label"].parse().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(h.node_count(), 3);
+        let labels: Vec<_> = h
+            .node_indices()
+            .map(|n| h.node_weight(n).unwrap().clone())
+            .collect();
+        assert_eq!(labels, vec!["build", "test", "deploy"]);
+        let edges: Vec<_> = h
+            .edge_references()
+            .map(|e| (e.node[0].index(), e.node[1].index(), *e.weight))
+            .collect();
+        assert_eq!(edges, vec![(0, 1, 1), (1, 2, 2)]);
+    }
+
+    #[test]
+    fn test_from_dot_subset() {
+        let src = r#"
+            // 构建依赖
+            strict digraph deps {
+                rankdir = LR;
+                node [shape=box];
+                /* 链式边 */
+                fetch -> compile -> link [weight=3];
+                "unit test" [color=red]
+                link -> "unit test";
+            }
+        "#;
+        let g: Graph<String, Option<String>, Directed> = Graph::from_dot(
+            src,
+            |id, attrs| format!("{id}:{}", attrs.get("color").map_or("", |s| s)),
+            |attrs| attrs.get("weight").cloned(),
+        )
+        .unwrap();
+
+        let names: Vec<_> = g
+            .node_indices()
+            .map(|n| g.node_weight(n).unwrap().as_str())
+            .collect();
+        assert_eq!(names, vec!["fetch:", "compile:", "link:", "unit test:red"]);
+        let weights: Vec<_> = g.edge_references().map(|e| e.weight.clone()).collect();
+        assert_eq!(weights, vec![Some("3".into()), Some("3".into()), None]);
+    }
+
+    #[test]
+    fn test_from_dot_errors() {
+        let parse = |s: &str| Graph::<String, (), Directed>::from_dot(s, |id, _| id.into(), |_| ());
+
+        assert!(matches!(
+            parse("graph { a -- b }"),
+            Err(AlgodsError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse("digraph {\n a -- b\n}"),
+            Err(AlgodsError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("digraph {\n a -> \n"),
+            Err(AlgodsError::Parse { .. })
+        ));
+        assert!(matches!(
+            parse("digraph { subgraph x { a } }"),
+            Err(AlgodsError::Parse { .. })
+        ));
+        assert!(matches!(
+            parse("digraph { a [label=\"oops }"),
+            Err(AlgodsError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+//! 空白分隔的边列表格式
+//!
+//! 每行一条边 `source target [weight]`，空行被忽略。位于行首或空白之后的 `#` 开始注释，
+//! 字段中间的 `#`（如 `a#1`）属于字段本身
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use crate::{
+    error::AlgodsResult,
+    graph::{EdgeType, Graph, io::parse_error},
+};
+
+impl<N, E, Ty, Idx> Graph<N, E, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Clone,
+{
+    /// 导出为边列表，每行 `source target weight`，节点以其权重的 `Display` 表示
+    ///
+    /// 孤立节点不会出现在输出中；无向图中每条边只输出一次。
+    /// 节点名或边权中若含有空白字符、或以 `#` 开头，将无法再被 [`from_edge_list`](Graph::from_edge_list) 正确读回
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V + E)
+    pub fn to_edge_list(&self) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        let mut out = String::new();
+        for e in self.edge_references() {
+            if self.is_mirror_edge(e.index) {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "{} {} {}",
+                self.node_weight(e.node[0]).unwrap(),
+                self.node_weight(e.node[1]).unwrap(),
+                e.weight
+            );
+        }
+        out
+    }
+}
+
+impl<E, Ty, Idx> Graph<String, E, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Clone + FromStr + Default,
+{
+    /// 从边列表解析图
+    ///
+    /// 节点以名称作为权重，按首次出现的顺序创建；缺省的边权取 `E::default()`
+    ///
+    /// ## 返回
+    /// - Ok(graph)：解析成功
+    /// - Err(AlgodsError::Parse)：某行字段数不是 2 或 3，或边权无法解析
+    pub fn from_edge_list(src: &str) -> AlgodsResult<Self> {
+        let mut g = Self::new();
+        let mut index = HashMap::new();
+        for (i, raw) in src.lines().enumerate() {
+            // 以 `#` 开头的字段及其后的内容是注释
+            let fields: Vec<&str> = raw
+                .split_whitespace()
+                .take_while(|f| !f.starts_with('#'))
+                .collect();
+            if fields.is_empty() {
+                continue;
+            }
+            if fields.len() > 3 || fields.len() < 2 {
+                return Err(parse_error(
+                    i + 1,
+                    format!(
+                        "expected `source target [weight]`, found {} fields",
+                        fields.len()
+                    ),
+                ));
+            }
+            let weight = match fields.get(2) {
+                Some(w) => w
+                    .parse()
+                    .map_err(|_| parse_error(i + 1, format!("invalid edge weight {w:?}")))?,
+                None => E::default(),
+            };
+            let mut node = |name: &str| {
+                *index
+                    .entry(name.to_string())
+                    .or_insert_with(|| g.add_node(name.to_string()))
+            };
+            let (a, b) = (node(fields[0]), node(fields[1]));
+            g.add_edge(a, b, weight);
+        }
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::AlgodsError,
+        graph::{Directed, Graph, Undirected},
+    };
+
+    #[test]
+    fn test_edge_list_round_trip() {
+        let mut g: Graph<String, f32, Directed> = Graph::new();
+        let a = g.add_node("a".into());
+        let b = g.add_node("b".into());
+        g.add_edge(a, b, 1.5);
+        g.add_edge(b, a, 2.0);
+
+        let text = g.to_edge_list();
+        assert_eq!(text, "a b 1.5\nb a 2\n");
+
+        let h: Graph<String, f32, Directed> = Graph::from_edge_list(&text).unwrap();
+        assert_eq!(h.to_edge_list(), text);
+    }
+
+    #[test]
+    fn test_from_edge_list_comments_and_defaults() {
+        let src = "# fixture\n\n1 2   # 默认权重\n2 3 7\n\t3 1 4\n";
+        let g: Graph<String, i32, Undirected> = Graph::from_edge_list(src).unwrap();
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.to_edge_list(), "1 2 0\n2 3 7\n3 1 4\n");
+    }
+
+    #[test]
+    fn test_hash_inside_fields() {
+        let mut g: Graph<String, String, Directed> = Graph::new();
+        let a = g.add_node("C#".into());
+        let b = g.add_node("F#m".into());
+        g.add_edge(a, b, "w#1".into());
+
+        let text = g.to_edge_list();
+        assert_eq!(text, "C# F#m w#1\n");
+        let h: Graph<String, String, Directed> = Graph::from_edge_list(&text).unwrap();
+        assert_eq!(h.to_edge_list(), text);
+
+        let src = "a#b c#d#e #comment\nx y\t#w 3\n";
+        let h: Graph<String, String, Directed> = Graph::from_edge_list(src).unwrap();
+        assert_eq!(h.to_edge_list(), "a#b c#d#e \nx y \n");
+    }
+
+    #[test]
+    fn test_from_edge_list_errors() {
+        assert_eq!(
+            Graph::<String, i32, Directed>::from_edge_list("a b 1\na\n").err(),
+            Some(AlgodsError::Parse {
+                line: 2,
+                message: "expected `source target [weight]`, found 1 fields".into()
+            })
+        );
+        assert!(matches!(
+            Graph::<String, i32, Directed>::from_edge_list("a b x"),
+            Err(AlgodsError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,472 @@
+//! GraphML 格式
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    error::AlgodsResult,
+    graph::{
+        EdgeType, Graph,
+        io::{Attributes, parse_error},
+    },
+};
+
+impl<N, E, Ty, Idx> Graph<N, E, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Clone,
+{
+    /// 导出为 GraphML 格式
+    ///
+    /// 节点 ID 为 `n{index}`，`node_data` / `edge_data` 的返回值分别写入
+    /// 名为 `label` 和 `weight` 的数据键。无向图中每条边只输出一次
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V + E)
+    pub fn to_graphml<FN, FE>(&self, mut node_data: FN, mut edge_data: FE) -> String
+    where
+        FN: FnMut(&N) -> String,
+        FE: FnMut(&E) -> String,
+    {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"string\"/>\n",
+        );
+        let kind = if Ty::DIRECTED {
+            "directed"
+        } else {
+            "undirected"
+        };
+        let _ = writeln!(out, "  <graph id=\"G\" edgedefault=\"{kind}\">");
+        for n in self.node_indices() {
+            let data = node_data(self.node_weight(n).unwrap());
+            let _ = writeln!(
+                out,
+                "    <node id=\"n{}\"><data key=\"label\">{}</data></node>",
+                n.index(),
+                escape(&data)
+            );
+        }
+        for e in self.edge_references() {
+            if self.is_mirror_edge(e.index) {
+                continue;
+            }
+            let data = edge_data(e.weight);
+            let _ = writeln!(
+                out,
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"weight\">{}</data></edge>",
+                e.node[0].index(),
+                e.node[1].index(),
+                escape(&data)
+            );
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// 从 GraphML 解析图
+    ///
+    /// 读取唯一的 `<graph>` 中的 `<node>` 与 `<edge>`，`<data>` 元素按其键的
+    /// `attr.name`（未声明时使用键 ID）收集为属性。节点按出现顺序创建，
+    /// 边引用的节点可以出现在边之后
+    ///
+    /// ## 返回
+    /// - Ok(graph)：解析成功
+    /// - Err(AlgodsError::Parse)：XML 格式错误、缺少 `<graph>` 或含有多个（嵌套）`<graph>`、
+    ///   引用了不存在的节点，或 `edgedefault` 与 `Ty` 不一致
+    pub fn from_graphml<FN, FE>(src: &str, mut node: FN, mut edge: FE) -> AlgodsResult<Self>
+    where
+        FN: FnMut(&str, &Attributes) -> N,
+        FE: FnMut(&Attributes) -> E,
+    {
+        let mut reader = XmlReader::new(src);
+        let mut key_names: HashMap<String, String> = HashMap::new();
+        let mut nodes: Vec<(String, Attributes)> = Vec::new();
+        let mut edges: Vec<(String, String, Attributes, usize)> = Vec::new();
+        let mut seen_graph = false;
+        // 当前所在的 node/edge 以及 data 键
+        let mut in_node = false;
+        let mut in_edge = false;
+        let mut data_key: Option<String> = None;
+        let mut text = String::new();
+
+        while let Some(ev) = reader.next_event()? {
+            let line = reader.line();
+            match ev {
+                XmlEvent::Start { name, attrs, empty } => match name.as_str() {
+                    "key" => {
+                        if let Some(id) = attrs.get("id") {
+                            let name = attrs.get("attr.name").unwrap_or(id);
+                            key_names.insert(id.clone(), name.clone());
+                        }
+                    }
+                    "graph" => {
+                        if seen_graph {
+                            return Err(parse_error(
+                                line,
+                                "nested or multiple graphs are not supported",
+                            ));
+                        }
+                        seen_graph = true;
+                        let directed = attrs.get("edgedefault").is_none_or(|d| d == "directed");
+                        if directed != Ty::DIRECTED {
+                            return Err(parse_error(
+                                line,
+                                "edgedefault does not match the target graph type",
+                            ));
+                        }
+                    }
+                    "node" => {
+                        let id = attrs
+                            .get("id")
+                            .ok_or_else(|| parse_error(line, "node without id"))?;
+                        nodes.push((id.clone(), Attributes::new()));
+                        in_node = !empty;
+                    }
+                    "edge" => {
+                        let get = |k: &str| {
+                            attrs
+                                .get(k)
+                                .cloned()
+                                .ok_or_else(|| parse_error(line, format!("edge without {k}")))
+                        };
+                        edges.push((get("source")?, get("target")?, Attributes::new(), line));
+                        in_edge = !empty;
+                    }
+                    "data" if (in_node || in_edge) && !empty => {
+                        let key = attrs
+                            .get("key")
+                            .ok_or_else(|| parse_error(line, "data without key"))?;
+                        data_key = Some(key.clone());
+                        text.clear();
+                    }
+                    _ => {}
+                },
+                XmlEvent::Text(t) => {
+                    if data_key.is_some() {
+                        text.push_str(&t);
+                    }
+                }
+                XmlEvent::End { name } => match name.as_str() {
+                    "node" => in_node = false,
+                    "edge" => in_edge = false,
+                    "data" => {
+                        if let Some(key) = data_key.take() {
+                            let name = key_names.get(&key).cloned().unwrap_or(key);
+                            let target = if in_node {
+                                &mut nodes.last_mut().unwrap().1
+                            } else {
+                                &mut edges.last_mut().unwrap().2
+                            };
+                            target.insert(name, std::mem::take(&mut text));
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+        if !seen_graph {
+            return Err(parse_error(reader.line(), "missing <graph> element"));
+        }
+
+        let mut g = Self::new();
+        let mut index = HashMap::new();
+        for (id, attrs) in &nodes {
+            let n = g.add_node(node(id, attrs));
+            index.insert(id.as_str(), n);
+        }
+        for (s, t, attrs, line) in &edges {
+            let lookup = |id: &String| {
+                index
+                    .get(id.as_str())
+                    .copied()
+                    .ok_or_else(|| parse_error(*line, format!("unknown node {id}")))
+            };
+            let (a, b) = (lookup(s)?, lookup(t)?);
+            g.add_edge(a, b, edge(attrs));
+        }
+        Ok(g)
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(s: &str, line: usize) -> AlgodsResult<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(p) = rest.find('&') {
+        out.push_str(&rest[..p]);
+        let end = rest[p..]
+            .find(';')
+            .ok_or_else(|| parse_error(line, "unterminated entity"))?;
+        let entity = &rest[p + 1..p + end];
+        match entity {
+            "lt" => out.push('<'),
+            "gt" => out.push('>'),
+            "amp" => out.push('&'),
+            "quot" => out.push('"'),
+            "apos" => out.push('\''),
+            _ => {
+                let code = entity
+                    .strip_prefix("#x")
+                    .map(|h| u32::from_str_radix(h, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|d| d.parse()))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| parse_error(line, format!("unknown entity &{entity};")))?;
+                out.push(code);
+            }
+        }
+        rest = &rest[p + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+enum XmlEvent {
+    Start {
+        name: String,
+        attrs: HashMap<String, String>,
+        empty: bool,
+    },
+    End {
+        name: String,
+    },
+    Text(String),
+}
+
+/// 只覆盖 GraphML 所需的最小 XML 读取器
+struct XmlReader<'a> {
+    src: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> XmlReader<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.line += self.src[self.pos..self.pos + n].matches('\n').count();
+        self.pos += n;
+    }
+
+    fn skip_past(&mut self, pat: &str) -> AlgodsResult<()> {
+        match self.src[self.pos..].find(pat) {
+            Some(p) => {
+                self.advance(p + pat.len());
+                Ok(())
+            }
+            None => Err(parse_error(self.line(), format!("missing {pat}"))),
+        }
+    }
+
+    fn next_event(&mut self) -> AlgodsResult<Option<XmlEvent>> {
+        loop {
+            let rest = &self.src[self.pos..];
+            if rest.is_empty() {
+                return Ok(None);
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let line = self.line();
+                self.advance(end);
+                let raw = &rest[..end];
+                if raw.trim().is_empty() {
+                    continue;
+                }
+                return Ok(Some(XmlEvent::Text(unescape(raw, line)?)));
+            }
+            if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                let start = self.pos + "<![CDATA[".len();
+                self.skip_past("]]>")?;
+                let text = self.src[start..self.pos - "]]>".len()].to_string();
+                return Ok(Some(XmlEvent::Text(text)));
+            } else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(tag) = rest.strip_prefix("</") {
+                let line = self.line();
+                let end = tag
+                    .find('>')
+                    .ok_or_else(|| parse_error(line, "unterminated tag"))?;
+                let name = tag[..end].trim().to_string();
+                self.advance(2 + end + 1);
+                return Ok(Some(XmlEvent::End { name }));
+            } else {
+                return self.start_tag().map(Some);
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> AlgodsResult<XmlEvent> {
+        let line = self.line();
+        let rest = &self.src[self.pos + 1..];
+        // 查找不在引号内的 '>'
+        let mut quote = None;
+        let mut end = None;
+        for (i, c) in rest.char_indices() {
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), c) if c == q => quote = None,
+                (None, '>') => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let end = end.ok_or_else(|| parse_error(line, "unterminated tag"))?;
+        let mut body = &rest[..end];
+        self.advance(1 + end + 1);
+
+        let empty = body.ends_with('/');
+        if empty {
+            body = &body[..body.len() - 1];
+        }
+        let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+        let name = body[..name_end].to_string();
+        if name.is_empty() {
+            return Err(parse_error(line, "empty tag name"));
+        }
+
+        let mut attrs = HashMap::new();
+        let mut s = body[name_end..].trim_start();
+        while !s.is_empty() {
+            let eq = s
+                .find('=')
+                .ok_or_else(|| parse_error(line, format!("malformed attribute in <{name}>")))?;
+            let key = s[..eq].trim().to_string();
+            let after = s[eq + 1..].trim_start();
+            let q = after
+                .chars()
+                .next()
+                .filter(|&c| c == '"' || c == '\'')
+                .ok_or_else(|| parse_error(line, format!("unquoted attribute {key}")))?;
+            let close = after[1..]
+                .find(q)
+                .ok_or_else(|| parse_error(line, format!("unterminated attribute {key}")))?;
+            attrs.insert(key, unescape(&after[1..1 + close], line)?);
+            s = after[close + 2..].trim_start();
+        }
+        Ok(XmlEvent::Start { name, attrs, empty })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::AlgodsError,
+        graph::{Directed, Graph, Undirected},
+    };
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let mut g: Graph<String, u32, Undirected> = Graph::new();
+        let a = g.add_node("a & b".to_string());
+        let b = g.add_node("<c>".to_string());
+        let c = g.add_node("d".to_string());
+        g.add_edge(a, b, 7);
+        g.add_edge(b, c, 9);
+
+        let xml = g.to_graphml(|w| w.clone(), |w| w.to_string());
+        assert!(xml.contains("edgedefault=\"undirected\""));
+        assert!(xml.contains("a &amp; b"));
+        assert_eq!(xml.matches("<edge ").count(), 2);
+
+        let h: Graph<String, u32, Undirected> = Graph::from_graphml(
+            &xml,
+            |_, attrs| attrs["label"].clone(),
+            |attrs| attrs["weight"].parse().unwrap(),
+        )
+        .unwrap();
+        let labels: Vec<_> = h
+            .node_indices()
+            .map(|n| h.node_weight(n).unwrap().clone())
+            .collect();
+        assert_eq!(labels, vec!["a & b", "<c>", "d"]);
+        // 每条无向边在图中存为两条有向边
+        assert_eq!(h.edge_count(), 4);
+        let mut nbrs: Vec<_> = h.neighbors(b).map(|(v, w)| (v.index(), w)).collect();
+        nbrs.sort();
+        assert_eq!(nbrs, vec![(0, 7), (2, 9)]);
+    }
+
+    #[test]
+    fn test_from_graphml_foreign_document() {
+        let xml = r#"<?xml version="1.0"?>
+<!-- 其他工具导出的文件 -->
+<graphml>
+  <key id="d0" for="node" attr.name="color"/>
+  <graph edgedefault="directed">
+    <node id="x"><data key="d0">red</data></node>
+    <edge source="x" target="y"/>
+    <node id="y"/>
+  </graph>
+</graphml>"#;
+        let g: Graph<String, (), Directed> = Graph::from_graphml(
+            xml,
+            |id, attrs| format!("{id}/{}", attrs.get("color").map_or("-", |s| s)),
+            |_| (),
+        )
+        .unwrap();
+        let names: Vec<_> = g
+            .node_indices()
+            .map(|n| g.node_weight(n).unwrap().as_str())
+            .collect();
+        assert_eq!(names, vec!["x/red", "y/-"]);
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_from_graphml_errors() {
+        let parse = |s: &str| Graph::<(), (), Directed>::from_graphml(s, |_, _| (), |_| ());
+
+        assert!(matches!(
+            parse("<graphml><graph edgedefault=\"undirected\"></graph></graphml>"),
+            Err(AlgodsError::Parse { .. })
+        ));
+        assert!(matches!(
+            parse("<graphml>\n<graph>\n<edge source=\"a\" target=\"b\"/>\n</graph></graphml>"),
+            Err(AlgodsError::Parse { line: 3, .. })
+        ));
+        assert!(matches!(parse("<graphml>"), Err(AlgodsError::Parse { .. })));
+        assert!(matches!(
+            parse("<graphml>\n<graph></graph>\n<graph></graph>\n</graphml>"),
+            Err(AlgodsError::Parse { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse("<graphml><graph><node id=\"a></graph>"),
+            Err(AlgodsError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+//! 与 [`num::matrix::Matrix`] 邻接矩阵的相互转换
+use num::{
+    Number,
+    matrix::{Matrix, MatrixBase},
+};
+
+use crate::graph::{EdgeType, Graph};
+
+impl<N, E, Ty, Idx> Graph<N, E, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    E: Clone,
+{
+    /// 转换为 V×V 邻接矩阵
+    ///
+    /// `m[(i, j)]` 为 i → j 所有边经 `weight` 映射后的和，不存在边时为零。
+    /// 无向图的矩阵是对称的
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V^2 + E)
+    pub fn to_adjacency_matrix<T, F>(&self, mut weight: F) -> Matrix<T>
+    where
+        T: Number,
+        F: FnMut(&E) -> T,
+    {
+        let n = self.node_count();
+        let mut m = Matrix::zero(n, n);
+        for e in self.edge_references() {
+            if self.is_mirror_edge(e.index) {
+                continue;
+            }
+            let (i, j) = (e.node[0].index(), e.node[1].index());
+            let w = weight(e.weight);
+            m[(i, j)] += w;
+            if !Ty::DIRECTED && i != j {
+                m[(j, i)] += w;
+            }
+        }
+        m
+    }
+}
+
+impl<T, Ty, Idx> Graph<(), T, Ty, Idx>
+where
+    Ty: EdgeType,
+    Idx: Copy + PartialEq + From<usize> + Into<usize>,
+    T: Number,
+{
+    /// 从邻接矩阵构建图，每个非零元素 `m[(i, j)]` 生成一条权重为该值的边 i → j
+    ///
+    /// 无向图只读取上三角（含对角线）
+    ///
+    /// ## Panics
+    /// 矩阵不是方阵时 panic
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(V^2)
+    pub fn from_adjacency_matrix(m: &Matrix<T>) -> Self {
+        assert!(m.is_square(), "adjacency matrix must be square");
+        let n = m.rows();
+        let mut g = Self::new();
+        let nodes: Vec<_> = (0..n).map(|_| g.add_node(())).collect();
+        for i in 0..n {
+            let start = if Ty::DIRECTED { 0 } else { i };
+            for j in start..n {
+                let w = m[(i, j)];
+                if !w.is_zero() {
+                    g.add_edge(nodes[i], nodes[j], w);
+                }
+            }
+        }
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{
+        Number,
+        matrix::{Matrix, MatrixBase},
+    };
+
+    use crate::graph::{Directed, Graph, Undirected};
+
+    fn rows<T: Number>(m: &Matrix<T>) -> Vec<Vec<T>> {
+        (0..m.rows()).map(|i| m[i].to_vec()).collect()
+    }
+
+    #[test]
+    fn test_to_adjacency_matrix() {
+        let mut g: Graph<(), i32, Directed> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 2);
+        g.add_edge(a, b, 3); // 重边求和
+        g.add_edge(c, a, 1);
+
+        let m = g.to_adjacency_matrix(|&w| w);
+        assert_eq!(rows(&m), vec![vec![0, 5, 0], vec![0, 0, 0], vec![1, 0, 0]]);
+
+        let mut u: Graph<(), i32, Undirected> = Graph::new();
+        let a = u.add_node(());
+        let b = u.add_node(());
+        u.add_edge(a, b, 4);
+        u.add_edge(b, b, 1);
+        assert_eq!(
+            rows(&u.to_adjacency_matrix(|&w| w as f64)),
+            vec![vec![0.0, 4.0], vec![4.0, 1.0]]
+        );
+    }
+
+    #[test]
+    fn test_from_adjacency_matrix() {
+        let m = Matrix::from([[0, 1, 0], [0, 0, 2], [3, 0, 0]]);
+        let g: Graph<(), i32, Directed> = Graph::from_adjacency_matrix(&m);
+        assert_eq!(g.node_count(), 3);
+        let edges: Vec<_> = g
+            .edge_references()
+            .map(|e| (e.node[0].index(), e.node[1].index(), *e.weight))
+            .collect();
+        assert_eq!(edges, vec![(0, 1, 1), (1, 2, 2), (2, 0, 3)]);
+        assert_eq!(rows(&g.to_adjacency_matrix(|&w| w)), rows(&m));
+
+        let sym = Matrix::from([[0, 7], [7, 0]]);
+        let u: Graph<(), i32, Undirected> = Graph::from_adjacency_matrix(&sym);
+        assert_eq!(u.edge_count(), 2);
+        assert_eq!(rows(&u.to_adjacency_matrix(|&w| w)), rows(&sym));
+    }
+}
@@ -0,0 +1,26 @@
+//! 图的导入与导出
+//!
+//! 以 [`Graph`](crate::graph::Graph) 方法的形式提供以下格式的读写：
+//! - DOT（Graphviz）：[`to_dot`](crate::graph::Graph::to_dot) / [`from_dot`](crate::graph::Graph::from_dot)
+//! - GraphML：[`to_graphml`](crate::graph::Graph::to_graphml) / [`from_graphml`](crate::graph::Graph::from_graphml)
+//! - 空白分隔的边列表：[`to_edge_list`](crate::graph::Graph::to_edge_list) / [`from_edge_list`](crate::graph::Graph::from_edge_list)
+//! - 邻接矩阵：`to_adjacency_matrix` / `from_adjacency_matrix`（需要 `num::matrix`）
+use std::collections::HashMap;
+
+use crate::error::AlgodsError;
+
+mod dot;
+mod edge_list;
+mod graphml;
+#[cfg(not(feature = "core"))]
+mod matrix;
+
+/// 节点或边上的属性（属性名 → 属性值）
+pub type Attributes = HashMap<String, String>;
+
+fn parse_error(line: usize, message: impl Into<String>) -> AlgodsError {
+    AlgodsError::Parse {
+        line,
+        message: message.into(),
+    }
+}
@@ -0,0 +1,450 @@
+//! 基于 VF2 的图同构与子图同构
+//!
+//! 图只通过 [`GraphBase`] 访问，因此有向、无向图及 [`CsrGraph`](crate::graph::CsrGraph)
+//! 均可使用。节点匹配闭包接收两侧的节点句柄，可捕获图以比较节点权重；
+//! 边匹配闭包接收两侧的边权，重边按邻居迭代顺序逐一比较
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::GraphBase;
+
+/// VF2 使用的下标化邻接结构
+struct Vf2Graph<N, E> {
+    nodes: Vec<N>,
+    /// 去重后的出邻居
+    out: Vec<Vec<usize>>,
+    /// 去重后的入邻居
+    inn: Vec<Vec<usize>>,
+    /// (u, v) 上所有边的权重
+    edges: HashMap<(usize, usize), Vec<E>>,
+}
+
+impl<N: Copy + Eq + std::hash::Hash, E> Vf2Graph<N, E> {
+    fn new<G: GraphBase<Node = N, EdgeWeight = E>>(g: &G) -> Self {
+        let nodes: Vec<N> = g.nodes().collect();
+        let index: HashMap<N, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let n = nodes.len();
+        let mut out = vec![Vec::new(); n];
+        let mut inn = vec![Vec::new(); n];
+        let mut edges: HashMap<(usize, usize), Vec<E>> = HashMap::new();
+        for (u, &node) in nodes.iter().enumerate() {
+            for (v, w) in g.neighbors(node) {
+                let v = index[&v];
+                let ws = edges.entry((u, v)).or_default();
+                if ws.is_empty() {
+                    out[u].push(v);
+                    inn[v].push(u);
+                }
+                ws.push(w);
+            }
+        }
+        Self {
+            nodes,
+            out,
+            inn,
+            edges,
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.values().map(Vec::len).sum()
+    }
+
+    /// 排序后的 (出度, 入度) 序列，同构的必要条件
+    fn degree_sequence(&self) -> Vec<(usize, usize)> {
+        let mut d: Vec<_> = (0..self.nodes.len())
+            .map(|u| (self.out[u].len(), self.inn[u].len()))
+            .collect();
+        d.sort_unstable();
+        d
+    }
+
+    /// 连通性优先的匹配顺序：每个分量从度数最大的节点开始 BFS，
+    /// 使后续节点尽量与已匹配节点相邻，从而缩小候选集
+    fn match_order(&self) -> Vec<usize> {
+        let n = self.nodes.len();
+        let degree = |u: usize| self.out[u].len() + self.inn[u].len();
+        let mut by_degree: Vec<usize> = (0..n).collect();
+        by_degree.sort_by_key(|&u| std::cmp::Reverse(degree(u)));
+
+        let mut seen = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        for root in by_degree {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let start = order.len();
+            order.push(root);
+            let mut i = start;
+            while i < order.len() {
+                let u = order[i];
+                i += 1;
+                for &v in self.out[u].iter().chain(&self.inn[u]) {
+                    if !seen[v] {
+                        seen[v] = true;
+                        order.push(v);
+                    }
+                }
+            }
+        }
+        order
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Isomorphism,
+    /// 导出子图同构：pattern 与 target 的一个导出子图同构
+    Subgraph,
+}
+
+struct Vf2<'a, N0, E0, N1, E1, NM, EM> {
+    g0: &'a Vf2Graph<N0, E0>,
+    g1: &'a Vf2Graph<N1, E1>,
+    core0: Vec<Option<usize>>,
+    core1: Vec<Option<usize>>,
+    order: Vec<usize>,
+    mode: Mode,
+    node_match: NM,
+    edge_match: EM,
+}
+
+impl<N0, E0, N1, E1, NM, EM> Vf2<'_, N0, E0, N1, E1, NM, EM>
+where
+    N0: Copy,
+    N1: Copy,
+    NM: FnMut(N0, N1) -> bool,
+    EM: FnMut(&E0, &E1) -> bool,
+{
+    /// 比较 pattern 边 (a, b) 与 target 边 (x, y) 的重数与权重
+    fn edges_match(&mut self, (a, b): (usize, usize), (x, y): (usize, usize)) -> bool {
+        match (self.g0.edges.get(&(a, b)), self.g1.edges.get(&(x, y))) {
+            (Some(e0), Some(e1)) => {
+                e0.len() == e1.len() && e0.iter().zip(e1).all(|(w0, w1)| (self.edge_match)(w0, w1))
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn feasible(&mut self, u: usize, v: usize) -> bool {
+        let (g0, g1) = (self.g0, self.g1);
+        let degrees_ok = match self.mode {
+            Mode::Isomorphism => {
+                g0.out[u].len() == g1.out[v].len() && g0.inn[u].len() == g1.inn[v].len()
+            }
+            Mode::Subgraph => {
+                g0.out[u].len() <= g1.out[v].len() && g0.inn[u].len() <= g1.inn[v].len()
+            }
+        };
+        if !degrees_ok || !(self.node_match)(g0.nodes[u], g1.nodes[v]) {
+            return false;
+        }
+
+        // pattern 中与已匹配节点（含 u 自身的自环）相连的边必须在 target 中存在且匹配
+        for &u2 in &g0.out[u] {
+            let v2 = if u2 == u { Some(v) } else { self.core0[u2] };
+            if let Some(v2) = v2
+                && !self.edges_match((u, u2), (v, v2))
+            {
+                return false;
+            }
+        }
+        for &u2 in &g0.inn[u] {
+            if u2 != u
+                && let Some(v2) = self.core0[u2]
+                && !self.edges_match((u2, u), (v2, v))
+            {
+                return false;
+            }
+        }
+
+        // 反之，target 中与已匹配节点相连的边也必须在 pattern 中存在（导出子图）
+        let mapped = |v2: usize| if v2 == v { Some(u) } else { self.core1[v2] };
+        g1.out[v]
+            .iter()
+            .filter_map(|&v2| mapped(v2))
+            .all(|u2| g0.edges.contains_key(&(u, u2)))
+            && g1.inn[v]
+                .iter()
+                .filter_map(|&v2| mapped(v2))
+                .all(|u2| g0.edges.contains_key(&(u2, u)))
+    }
+
+    /// 候选 target 节点：若 u 与某已匹配节点 p 相邻，只需考虑 p 的像的对应邻居
+    fn candidates(&self, u: usize) -> Vec<usize> {
+        let (g0, g1) = (self.g0, self.g1);
+        let unmapped = |v: &usize| self.core1[*v].is_none();
+        if let Some(p) = g0.inn[u].iter().find_map(|&p| self.core0[p]) {
+            return g1.out[p].iter().copied().filter(unmapped).collect();
+        }
+        if let Some(p) = g0.out[u].iter().find_map(|&p| self.core0[p]) {
+            return g1.inn[p].iter().copied().filter(unmapped).collect();
+        }
+        (0..g1.nodes.len()).filter(unmapped).collect()
+    }
+
+    /// 深度优先搜索所有匹配；`on_match` 返回 false 时停止
+    fn search<F: FnMut(&[Option<usize>]) -> bool>(
+        &mut self,
+        depth: usize,
+        on_match: &mut F,
+    ) -> bool {
+        if depth == self.order.len() {
+            return on_match(&self.core0);
+        }
+        let u = self.order[depth];
+        for v in self.candidates(u) {
+            if !self.feasible(u, v) {
+                continue;
+            }
+            self.core0[u] = Some(v);
+            self.core1[v] = Some(u);
+            let go_on = self.search(depth + 1, on_match);
+            self.core0[u] = None;
+            self.core1[v] = None;
+            if !go_on {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 运行 VF2，对每个找到的匹配调用 `on_match(pattern 节点, target 节点)` 映射
+fn run_vf2<G0, G1, NM, EM, F>(
+    g0: &G0,
+    g1: &G1,
+    mode: Mode,
+    node_match: NM,
+    edge_match: EM,
+    mut on_match: F,
+) where
+    G0: GraphBase,
+    G1: GraphBase,
+    NM: FnMut(G0::Node, G1::Node) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+    F: FnMut(HashMap<G0::Node, G1::Node>) -> bool,
+{
+    let (a, b) = (Vf2Graph::new(g0), Vf2Graph::new(g1));
+    let possible = match mode {
+        Mode::Isomorphism => {
+            a.nodes.len() == b.nodes.len()
+                && a.edge_count() == b.edge_count()
+                && a.degree_sequence() == b.degree_sequence()
+        }
+        Mode::Subgraph => a.nodes.len() <= b.nodes.len(),
+    };
+    if !possible {
+        return;
+    }
+
+    let mut vf2 = Vf2 {
+        g0: &a,
+        g1: &b,
+        core0: vec![None; a.nodes.len()],
+        core1: vec![None; b.nodes.len()],
+        order: a.match_order(),
+        mode,
+        node_match,
+        edge_match,
+    };
+    vf2.search(0, &mut |core: &[Option<usize>]| {
+        let mapping = core
+            .iter()
+            .enumerate()
+            .map(|(u, v)| (a.nodes[u], b.nodes[v.unwrap()]))
+            .collect();
+        on_match(mapping)
+    });
+}
+
+/// 判断两图是否同构（只比较结构）
+///
+/// ## Notes
+/// 最坏时间复杂度为指数级，常见图上借助度数与连通性剪枝通常很快
+pub fn is_isomorphic<G0: GraphBase, G1: GraphBase>(g0: &G0, g1: &G1) -> bool {
+    is_isomorphic_matching(g0, g1, |_, _| true, |_, _| true)
+}
+
+/// 判断两图是否在给定的节点、边匹配条件下同构
+///
+/// ## 参数
+/// - `node_match(n0, n1)`: 节点 n0 能否映射到 n1
+/// - `edge_match(w0, w1)`: 对应边的权重是否匹配
+pub fn is_isomorphic_matching<G0, G1, NM, EM>(
+    g0: &G0,
+    g1: &G1,
+    node_match: NM,
+    edge_match: EM,
+) -> bool
+where
+    G0: GraphBase,
+    G1: GraphBase,
+    NM: FnMut(G0::Node, G1::Node) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let mut found = false;
+    run_vf2(g0, g1, Mode::Isomorphism, node_match, edge_match, |_| {
+        found = true;
+        false
+    });
+    found
+}
+
+/// 枚举 `pattern` 到 `target` 的所有导出子图同构
+///
+/// 每个结果是 pattern 节点到 target 节点的单射，且两个被映射节点之间
+/// 在 pattern 中有边当且仅当在 target 中有边（重数相同、边权匹配）
+///
+/// ## 参数
+/// - `node_match(n0, n1)`: pattern 节点 n0 能否映射到 target 节点 n1
+/// - `edge_match(w0, w1)`: 对应边的权重是否匹配
+///
+/// ## 返回
+/// 所有映射；同一节点集合的不同自同构映射会分别出现
+pub fn subgraph_isomorphisms<G0, G1, NM, EM>(
+    pattern: &G0,
+    target: &G1,
+    node_match: NM,
+    edge_match: EM,
+) -> Vec<HashMap<G0::Node, G1::Node>>
+where
+    G0: GraphBase,
+    G1: GraphBase,
+    NM: FnMut(G0::Node, G1::Node) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let mut out = Vec::new();
+    run_vf2(
+        pattern,
+        target,
+        Mode::Subgraph,
+        node_match,
+        edge_match,
+        |m| {
+            out.push(m);
+            true
+        },
+    );
+    out
+}
+
+/// 子图同构在 target 中覆盖的不同节点集合数（忽略自同构），便于统计模式出现次数
+pub fn count_subgraph_occurrences<G0: GraphBase, G1: GraphBase>(pattern: &G0, target: &G1) -> usize
+where
+    G1::Node: Ord,
+{
+    let mut seen = HashSet::new();
+    run_vf2(
+        pattern,
+        target,
+        Mode::Subgraph,
+        |_, _| true,
+        |_, _| true,
+        |m| {
+            let mut nodes: Vec<G1::Node> = m.into_values().collect();
+            nodes.sort();
+            seen.insert(nodes);
+            true
+        },
+    );
+    seen.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        CsrGraph, Directed, Graph, NodeIndex, Undirected,
+        generators::{complete_graph, cycle_graph, path_graph, star_graph},
+    };
+
+    type U = Graph<(), u32, Undirected>;
+    type D = Graph<(), u32, Directed>;
+
+    #[test]
+    fn test_is_isomorphic() {
+        // 同一个环的两种编号
+        let a: CsrGraph<(), u32, Undirected> =
+            CsrGraph::from_edges([(0, 1, 0), (1, 2, 0), (2, 3, 0), (3, 0, 0)]);
+        let b: CsrGraph<(), u32, Undirected> =
+            CsrGraph::from_edges([(0, 2, 0), (2, 1, 0), (1, 3, 0), (3, 0, 0)]);
+        assert!(is_isomorphic(&a, &b));
+        assert!(is_isomorphic(&a, &cycle_graph::<(), u32, Undirected>(4)));
+
+        // 度数序列相同但不同构：C6 与两个 C3
+        let c6: U = cycle_graph(6);
+        let two_c3: CsrGraph<(), u32, Undirected> = CsrGraph::from_edges([
+            (0, 1, 0),
+            (1, 2, 0),
+            (2, 0, 0),
+            (3, 4, 0),
+            (4, 5, 0),
+            (5, 3, 0),
+        ]);
+        assert!(!is_isomorphic(&c6, &two_c3));
+
+        // 有向：方向不同的路径不同构
+        let p: D = path_graph(3);
+        let q: CsrGraph<(), u32> = CsrGraph::from_edges([(1, 0, 0), (1, 2, 0)]);
+        assert!(!is_isomorphic(&p, &q));
+        assert!(is_isomorphic(&p, &p));
+    }
+
+    #[test]
+    fn test_is_isomorphic_matching() {
+        let mut g: Graph<&str, u32, Directed> = Graph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b, 5);
+        let mut h: Graph<&str, u32, Directed> = Graph::new();
+        let x = h.add_node("b");
+        let y = h.add_node("a");
+        h.add_edge(y, x, 5);
+
+        let same_label = |n0: NodeIndex, n1: NodeIndex| g.node_weight(n0) == h.node_weight(n1);
+        assert!(is_isomorphic_matching(&g, &h, same_label, |w0, w1| w0 == w1));
+        assert!(!is_isomorphic_matching(&g, &h, same_label, |w0, w1| w0 != w1));
+
+        // 标签颠倒后结构同构但标签不匹配
+        let mut k: Graph<&str, u32, Directed> = Graph::new();
+        let p = k.add_node("b");
+        let q = k.add_node("a");
+        k.add_edge(p, q, 5);
+        assert!(is_isomorphic(&g, &k));
+        assert!(!is_isomorphic_matching(
+            &g,
+            &k,
+            |n0, n1| g.node_weight(n0) == k.node_weight(n1),
+            |_, _| true
+        ));
+    }
+
+    #[test]
+    fn test_subgraph_isomorphisms() {
+        // K4 中的三角形：4 个节点集合 × 6 个自同构
+        let tri: U = complete_graph(3);
+        let k4: U = complete_graph(4);
+        assert_eq!(
+            subgraph_isomorphisms(&tri, &k4, |_, _| true, |_, _| true).len(),
+            24
+        );
+        assert_eq!(count_subgraph_occurrences(&tri, &k4), 4);
+
+        // 导出子图：C4 中没有三角形，但有 4 条导出的 3 节点路径
+        let c4: U = cycle_graph(4);
+        assert_eq!(count_subgraph_occurrences(&tri, &c4), 0);
+        let p3: U = path_graph(3);
+        assert_eq!(count_subgraph_occurrences(&p3, &c4), 4);
+        // K4 中任意 3 个节点都构成三角形，因此不存在导出的 P3
+        assert_eq!(count_subgraph_occurrences(&p3, &k4), 0);
+
+        // 有向星图中匹配中心节点
+        let s: D = star_graph(3);
+        let edge: D = path_graph(2);
+        let found = subgraph_isomorphisms(&edge, &s, |_, _| true, |_, _| true);
+        assert_eq!(found.len(), 3);
+        assert!(found.iter().all(|m| m[&NodeIndex(0)] == NodeIndex(0)));
+    }
+}
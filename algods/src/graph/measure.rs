@@ -0,0 +1,78 @@
+//! 边权度量
+//!
+//! 最短路、最长路等算法只要求边权实现 [`Measure`]：可加、有零元、可比较。
+//! 浮点数只有偏序，[`Measure::measure_cmp`] 将 NaN 视为比任何值都大的代价，
+//! 从而在堆中给出确定的全序
+use std::{cmp::Ordering, ops::Add};
+
+use num::Zero;
+
+/// 可用作路径长度的度量
+///
+/// 对所有 `Zero + Clone + PartialOrd + Add` 类型自动实现，
+/// 包括整数、`f32` / `f64`、`Frac`、`BigInteger` 等
+pub trait Measure: Zero + Clone + PartialOrd + Add<Output = Self> {
+    /// 全序比较：可比较时与 `partial_cmp` 一致，NaN 大于所有非 NaN 值，NaN 之间相等
+    ///
+    /// 不命名为 `total_cmp`，否则对 `f32` / `f64` 的方法调用会解析到语义不同的固有方法
+    #[allow(clippy::eq_op)]
+    fn measure_cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or_else(|| {
+            // 只有 NaN 不等于自身
+            let (a_nan, b_nan) = (self != self, other != other);
+            a_nan.cmp(&b_nan)
+        })
+    }
+}
+
+impl<T> Measure for T where T: Zero + Clone + PartialOrd + Add<Output = T> {}
+
+/// 以 [`Measure::measure_cmp`] 实现 [`Ord`] 的包装，可直接放入 `BinaryHeap` 等容器
+#[derive(Debug, Clone, Copy)]
+pub struct TotalOrd<T>(pub T);
+
+impl<T: Measure> PartialEq for TotalOrd<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Measure> Eq for TotalOrd<T> {}
+
+impl<T: Measure> PartialOrd for TotalOrd<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Measure> Ord for TotalOrd<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.measure_cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_cmp_nan() {
+        let nan = f64::NAN;
+        assert_eq!(Measure::measure_cmp(&1.0, &2.0), Ordering::Less);
+        assert_eq!(
+            Measure::measure_cmp(&nan, &f64::INFINITY),
+            Ordering::Greater
+        );
+        assert_eq!(Measure::measure_cmp(&f64::INFINITY, &-nan), Ordering::Less);
+        assert_eq!(Measure::measure_cmp(&nan, &nan), Ordering::Equal);
+        // 方法调用语法同样使用 Measure 的语义：负号 NaN 也大于所有值
+        assert_eq!((-nan).measure_cmp(&1.0), Ordering::Greater);
+        assert_eq!((-nan).total_cmp(&1.0), Ordering::Less);
+
+        let mut v: Vec<_> = [3.0, nan, -1.0, 0.5].into_iter().map(TotalOrd).collect();
+        v.sort();
+        let v: Vec<_> = v.into_iter().map(|x| x.0).collect();
+        assert_eq!(&v[..3], &[-1.0, 0.5, 3.0]);
+        assert!(v[3].is_nan());
+    }
+}
@@ -3,23 +3,47 @@
 use std::hash::Hash;
 pub mod algo;
 mod base;
+pub mod centrality;
+pub mod clique;
+pub mod coloring;
+pub mod csr;
+pub mod dag;
+pub mod flow;
+pub mod generators;
 #[allow(clippy::module_inception)]
 pub mod graph;
+pub mod io;
+pub mod isomorphism;
+pub mod measure;
+pub mod visit;
+
+pub use base::{Directed, Direction, EdgeIndex, EdgeType, NodeIndex, Undirected};
+pub use csr::CsrGraph;
+pub use graph::Graph;
+pub use measure::{Measure, TotalOrd};
 
 pub trait GraphBase {
     /// 节点句柄类型
     type Node: Copy + Eq + Hash + Ord;
-    /// 边权类型
-    type EdgeWeight: Copy + Ord + std::ops::Add<Output = Self::EdgeWeight>;
+    /// 边权类型，最短路等算法要求其实现 [`Measure`]
+    type EdgeWeight: Clone;
 
     /// 邻居迭代器 (neighbor, weight)
     type Neighbors<'a>: Iterator<Item = (Self::Node, Self::EdgeWeight)>
     where
         Self: 'a;
 
+    /// 节点迭代器
+    type Nodes<'a>: Iterator<Item = Self::Node>
+    where
+        Self: 'a;
+
     /// 返回某节点的出邻居
     fn neighbors(&self, n: Self::Node) -> Self::Neighbors<'_>;
 
+    /// 返回图中所有节点
+    fn nodes(&self) -> Self::Nodes<'_>;
+
     /// 节点总数
     fn node_count(&self) -> usize;
 
@@ -0,0 +1,418 @@
+//! 基于访问者的图遍历框架
+//!
+//! [`depth_first_search`] 在遍历过程中向访问者回调 [`DfsEvent`]，
+//! 访问者通过返回 [`Control`] 提前终止或剪枝；
+//! [`Dfs`] / [`Bfs`] 是不持有图引用的惰性遍历器，两步之间可以修改图
+use std::collections::{HashSet, VecDeque};
+
+use crate::graph::GraphBase;
+
+/// 发现 / 完成时间戳，在每次 Discover 与 Finish 时递增
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time(pub usize);
+
+/// 深度优先搜索过程中产生的事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent<N> {
+    /// 首次访问节点
+    Discover(N, Time),
+    /// 搜索树边 (u, v)，v 随后被发现
+    TreeEdge(N, N),
+    /// 指向当前搜索路径上节点的边（有向图中意味着存在环）
+    BackEdge(N, N),
+    /// 指向已完成节点的边（有向图中的前向边或横叉边）
+    CrossEdge(N, N),
+    /// 节点的所有出边都已处理完毕
+    Finish(N, Time),
+}
+
+/// 访问者对遍历的控制指令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<B> {
+    /// 正常继续
+    Continue,
+    /// 剪枝：在 Discover 时返回则不再展开该节点的出边，在 TreeEdge 时返回则不沿该边前进。
+    /// 其它事件上等同于 Continue
+    Prune,
+    /// 立即终止遍历并返回该值
+    Break(B),
+}
+
+impl<B> Control<B> {
+    /// 若为 Break 则返回其中的值
+    pub fn break_value(self) -> Option<B> {
+        match self {
+            Control::Break(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// 访问者的返回类型，使得不关心控制流的访问者可以直接返回 `()`
+pub trait ControlFlow {
+    /// 表示「继续」的值
+    fn continuing() -> Self;
+
+    /// 是否应终止遍历
+    fn should_break(&self) -> bool;
+
+    /// 是否应剪枝
+    fn should_prune(&self) -> bool;
+}
+
+impl ControlFlow for () {
+    fn continuing() {}
+
+    fn should_break(&self) -> bool {
+        false
+    }
+
+    fn should_prune(&self) -> bool {
+        false
+    }
+}
+
+impl<B> ControlFlow for Control<B> {
+    fn continuing() -> Self {
+        Control::Continue
+    }
+
+    fn should_break(&self) -> bool {
+        matches!(self, Control::Break(_))
+    }
+
+    fn should_prune(&self) -> bool {
+        matches!(self, Control::Prune)
+    }
+}
+
+/// 若访问者要求终止则立即返回
+macro_rules! try_control {
+    ($e:expr) => {{
+        let c = $e;
+        if c.should_break() {
+            return c;
+        }
+        c
+    }};
+}
+
+/// 从 `starts` 中的每个未访问节点依次开始深度优先搜索，并向 `visitor` 报告事件
+///
+/// 每个可达节点恰好产生一次 Discover 与一次 Finish（被 Break 终止时除外），
+/// 每条被检查的边产生一次 TreeEdge / BackEdge / CrossEdge。
+/// 无向图中，树边的反向边会被报告为 BackEdge
+///
+/// ## 参数
+/// - `g`: 图
+/// - `starts`: 起点序列
+/// - `visitor`: 事件回调，返回 `()` 或 [`Control`]
+///
+/// ## 返回
+/// 访问者返回 Break 时返回该值，否则返回 `C::continuing()`
+///
+/// ## Notes
+/// 迭代实现，不受调用栈深度限制；
+/// 时间复杂度: O(V + E)
+pub fn depth_first_search<G, I, F, C>(g: &G, starts: I, mut visitor: F) -> C
+where
+    G: GraphBase,
+    I: IntoIterator<Item = G::Node>,
+    F: FnMut(DfsEvent<G::Node>) -> C,
+    C: ControlFlow,
+{
+    let mut discovered = HashSet::with_capacity(g.node_count());
+    let mut finished = HashSet::with_capacity(g.node_count());
+    let mut time = 0;
+    let mut stack: Vec<(G::Node, Option<G::Neighbors<'_>>)> = Vec::new();
+
+    for s in starts {
+        if !discovered.insert(s) {
+            continue;
+        }
+        let c = try_control!(visitor(DfsEvent::Discover(s, Time(time))));
+        time += 1;
+        stack.push((s, (!c.should_prune()).then(|| g.neighbors(s))));
+
+        while let Some((u, it)) = stack.last_mut() {
+            let u = *u;
+            match it.as_mut().and_then(Iterator::next) {
+                Some((v, _)) => {
+                    if !discovered.contains(&v) {
+                        let c = try_control!(visitor(DfsEvent::TreeEdge(u, v)));
+                        if c.should_prune() {
+                            continue;
+                        }
+                        discovered.insert(v);
+                        let c = try_control!(visitor(DfsEvent::Discover(v, Time(time))));
+                        time += 1;
+                        stack.push((v, (!c.should_prune()).then(|| g.neighbors(v))));
+                    } else if !finished.contains(&v) {
+                        try_control!(visitor(DfsEvent::BackEdge(u, v)));
+                    } else {
+                        try_control!(visitor(DfsEvent::CrossEdge(u, v)));
+                    }
+                }
+                None => {
+                    stack.pop();
+                    finished.insert(u);
+                    try_control!(visitor(DfsEvent::Finish(u, Time(time))));
+                    time += 1;
+                }
+            }
+        }
+    }
+    C::continuing()
+}
+
+/// 惰性深度优先（先序）遍历器
+///
+/// 不持有图的引用，每一步通过 [`next`](Dfs::next) 传入图，因此两步之间可以修改图。
+/// 节点在出栈时才被标记为已访问，新加入的边会在之后的步骤中生效
+#[derive(Debug, Clone)]
+pub struct Dfs<N> {
+    stack: Vec<N>,
+    discovered: HashSet<N>,
+}
+
+impl<N: Copy + Eq + std::hash::Hash> Dfs<N> {
+    /// 从 `start` 开始的遍历器
+    pub fn new<G: GraphBase<Node = N>>(g: &G, start: N) -> Self {
+        let mut dfs = Self::empty(g);
+        dfs.stack.push(start);
+        dfs
+    }
+
+    /// 没有起点的遍历器，需配合 [`move_to`](Dfs::move_to) 使用
+    pub fn empty<G: GraphBase<Node = N>>(g: &G) -> Self {
+        Self {
+            stack: Vec::new(),
+            discovered: HashSet::with_capacity(g.node_count()),
+        }
+    }
+
+    /// 清空栈并从 `start` 继续遍历，已访问的节点保持不变
+    pub fn move_to(&mut self, start: N) {
+        self.stack.clear();
+        self.stack.push(start);
+    }
+
+    /// 清空全部状态
+    pub fn reset(&mut self) {
+        self.stack.clear();
+        self.discovered.clear();
+    }
+
+    /// 节点是否已被访问
+    pub fn is_discovered(&self, n: N) -> bool {
+        self.discovered.contains(&n)
+    }
+
+    /// 返回下一个节点，遍历结束时返回 None
+    ///
+    /// 均摊时间复杂度: O(deg)
+    pub fn next<G: GraphBase<Node = N>>(&mut self, g: &G) -> Option<N> {
+        while let Some(u) = self.stack.pop() {
+            if !self.discovered.insert(u) {
+                continue;
+            }
+            // 逆序入栈，使第一个邻居最先被访问
+            let base = self.stack.len();
+            self.stack.extend(
+                g.neighbors(u)
+                    .map(|(v, _)| v)
+                    .filter(|v| !self.discovered.contains(v)),
+            );
+            self.stack[base..].reverse();
+            return Some(u);
+        }
+        None
+    }
+}
+
+/// 惰性广度优先遍历器
+///
+/// 与 [`Dfs`] 相同，不持有图的引用；节点在入队时即被标记为已访问
+#[derive(Debug, Clone)]
+pub struct Bfs<N> {
+    queue: VecDeque<N>,
+    discovered: HashSet<N>,
+}
+
+impl<N: Copy + Eq + std::hash::Hash> Bfs<N> {
+    /// 从 `start` 开始的遍历器
+    pub fn new<G: GraphBase<Node = N>>(g: &G, start: N) -> Self {
+        let mut discovered = HashSet::with_capacity(g.node_count());
+        discovered.insert(start);
+        Self {
+            queue: VecDeque::from([start]),
+            discovered,
+        }
+    }
+
+    /// 节点是否已被访问（入队）
+    pub fn is_discovered(&self, n: N) -> bool {
+        self.discovered.contains(&n)
+    }
+
+    /// 返回下一个节点，遍历结束时返回 None
+    ///
+    /// 均摊时间复杂度: O(deg)
+    pub fn next<G: GraphBase<Node = N>>(&mut self, g: &G) -> Option<N> {
+        let u = self.queue.pop_front()?;
+        for (v, _) in g.neighbors(u) {
+            if self.discovered.insert(v) {
+                self.queue.push_back(v);
+            }
+        }
+        Some(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{CsrGraph, Directed, Graph, NodeIndex, Undirected, algo};
+
+    fn n(i: usize) -> NodeIndex {
+        NodeIndex(i)
+    }
+
+    #[test]
+    fn test_dfs_events() {
+        // 0 -> 1 -> 2 -> 0, 0 -> 2, 3 -> 2
+        let g: CsrGraph<(), u32> =
+            CsrGraph::from_edges([(0, 1, 1), (0, 2, 1), (1, 2, 1), (2, 0, 1), (3, 2, 1)]);
+        let mut events = Vec::new();
+        depth_first_search(&g, [n(0), n(3)], |e| events.push(e));
+
+        use DfsEvent::*;
+        assert_eq!(
+            events,
+            vec![
+                Discover(n(0), Time(0)),
+                TreeEdge(n(0), n(1)),
+                Discover(n(1), Time(1)),
+                TreeEdge(n(1), n(2)),
+                Discover(n(2), Time(2)),
+                BackEdge(n(2), n(0)),
+                Finish(n(2), Time(3)),
+                Finish(n(1), Time(4)),
+                CrossEdge(n(0), n(2)),
+                Finish(n(0), Time(5)),
+                Discover(n(3), Time(6)),
+                CrossEdge(n(3), n(2)),
+                Finish(n(3), Time(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dfs_break_and_prune() {
+        let g: CsrGraph<(), u32> =
+            CsrGraph::from_edges([(0, 1, 1), (1, 2, 1), (0, 3, 1), (3, 4, 1)]);
+
+        // 查找路径：在 TreeEdge 上记录前驱，发现目标时终止
+        let mut pred = [None; 5];
+        let found = depth_first_search(&g, [n(0)], |e| match e {
+            DfsEvent::TreeEdge(u, v) => {
+                pred[v.index()] = Some(u);
+                Control::Continue
+            }
+            DfsEvent::Discover(v, _) if v == n(4) => Control::Break(v),
+            _ => Control::Continue,
+        });
+        assert_eq!(found.break_value(), Some(n(4)));
+        assert_eq!(pred[4], Some(n(3)));
+        assert_eq!(pred[3], Some(n(0)));
+
+        // 在 1 处剪枝，2 不会被发现
+        let mut seen = Vec::new();
+        let res: Control<()> = depth_first_search(&g, [n(0)], |e| match e {
+            DfsEvent::Discover(v, _) => {
+                seen.push(v.index());
+                if v == n(1) {
+                    Control::Prune
+                } else {
+                    Control::Continue
+                }
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(res, Control::Continue);
+        assert_eq!(seen, vec![0, 1, 3, 4]);
+
+        // 在树边上剪枝
+        let mut seen = Vec::new();
+        depth_first_search(&g, [n(0)], |e| match e {
+            DfsEvent::TreeEdge(_, v) if v == n(3) => Control::<()>::Prune,
+            DfsEvent::Discover(v, _) => {
+                seen.push(v.index());
+                Control::Continue
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(seen, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_undirected_back_edges() {
+        let mut g: Graph<(), u32, Undirected> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, 1);
+        let mut back = 0;
+        depth_first_search(&g, [a], |e| {
+            if let DfsEvent::BackEdge(..) = e {
+                back += 1;
+            }
+        });
+        assert_eq!(back, 1);
+    }
+
+    #[test]
+    fn test_walkers_match_algo() {
+        let mut g: Graph<(), u32, Directed> = Graph::new();
+        let v: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        for (a, b) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 0)] {
+            g.add_edge(v[a], v[b], 1);
+        }
+
+        let mut dfs = Dfs::new(&g, v[0]);
+        let order: Vec<_> = std::iter::from_fn(|| dfs.next(&g)).collect();
+        assert_eq!(order, algo::dfs(&g, v[0]));
+
+        let mut bfs = Bfs::new(&g, v[0]);
+        let order: Vec<_> = std::iter::from_fn(|| bfs.next(&g)).collect();
+        assert_eq!(order, algo::bfs(&g, v[0]));
+    }
+
+    #[test]
+    fn test_walker_mutation_between_steps() {
+        let mut g: Graph<(), u32, Directed> = Graph::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, 1);
+
+        // 遍历器不借用图，两步之间可以加点加边
+        let mut dfs = Dfs::new(&g, a);
+        assert_eq!(dfs.next(&g), Some(a));
+        let c = g.add_node(());
+        g.add_edge(b, c, 1);
+        assert_eq!(dfs.next(&g), Some(b));
+        assert_eq!(dfs.next(&g), Some(c));
+        assert_eq!(dfs.next(&g), None);
+
+        let d = g.add_node(());
+        dfs.move_to(d);
+        assert_eq!(dfs.next(&g), Some(d));
+        assert!(dfs.is_discovered(a));
+
+        let mut bfs = Bfs::new(&g, a);
+        assert_eq!(bfs.next(&g), Some(a));
+        g.add_edge(b, d, 1);
+        assert_eq!(bfs.next(&g), Some(b));
+        let rest: Vec<_> = std::iter::from_fn(|| bfs.next(&g)).collect();
+        assert_eq!(rest, vec![d, c]);
+    }
+}
@@ -0,0 +1,183 @@
+//! 子树聚合与换根 DP
+use std::collections::HashMap;
+
+use crate::hierarchy::{hierarchy::Hierarchy, tree::NodeId};
+
+/// 自底向上折叠每棵子树
+///
+/// 按后序遍历整棵树，对每个节点调用 `f(value, child_results)`，
+/// 其中 `child_results` 为其子节点（按子节点顺序）的折叠结果
+///
+/// ## 参数
+/// * `tree` - 待折叠的树
+/// * `f` - 合并函数，参数为节点值与各子节点的结果
+///
+/// ## 返回
+/// 节点到其子树折叠结果的映射
+///
+/// ## Notes
+/// 时间复杂度: O(n)
+pub fn fold_subtrees<T, R, F>(tree: &T, mut f: F) -> HashMap<NodeId, R>
+where
+    T: Hierarchy,
+    F: FnMut(&T::Value, &[&R]) -> R,
+{
+    let order: Vec<NodeId> = tree.post_order_iter(tree.root()).unwrap().collect();
+    let pos: HashMap<NodeId, usize> = order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let mut results: Vec<R> = Vec::with_capacity(order.len());
+    for &n in &order {
+        let r = {
+            let args: Vec<&R> = tree
+                .children_unchecked(n)
+                .map(|c| &results[pos[&c]])
+                .collect();
+            f(tree.value(n).unwrap(), &args)
+        };
+        results.push(r);
+    }
+    order.into_iter().zip(results).collect()
+}
+
+/// 换根 DP：对每个节点，计算以它为根时整棵树的折叠结果
+///
+/// 以 v 为根时，v 的邻居（子节点与原父节点）各自构成一棵子树。
+/// 子树 u 的结果为 `finish(value(u), acc)`，acc 是 u 的各个子树结果经 `merge` 合并后的值
+/// （无子树时为 `identity`）。换根后的父方向子树同样按此规则计算
+///
+/// ## 参数
+/// * `tree` - 待计算的树
+/// * `identity` - `merge` 的单位元
+/// * `merge` - 合并两个子树结果，须满足结合律与交换律
+/// * `finish` - 由节点值和其子树合并结果得到以该节点为根的结果
+///
+/// ## 返回
+/// 节点到“以该节点为根时的折叠结果”的映射
+///
+/// ## Notes
+/// 时间复杂度: O(n)，`merge` 与 `finish` 各调用 O(n) 次
+pub fn reroot<T, R, M, F>(tree: &T, identity: R, mut merge: M, mut finish: F) -> HashMap<NodeId, R>
+where
+    T: Hierarchy,
+    R: Clone,
+    M: FnMut(&R, &R) -> R,
+    F: FnMut(&T::Value, &R) -> R,
+{
+    let order: Vec<NodeId> = tree.dfs_iter(tree.root()).unwrap().collect();
+    let pos: HashMap<NodeId, usize> = order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let kids: Vec<Vec<usize>> = order
+        .iter()
+        .map(|&n| tree.children_unchecked(n).map(|c| pos[&c]).collect())
+        .collect();
+    let value = |i: usize| tree.value(order[i]).unwrap();
+
+    // down[v]：原树中以 v 为根的子树结果
+    let n = order.len();
+    let mut down: Vec<Option<R>> = vec![None; n];
+    for v in (0..n).rev() {
+        let mut acc = identity.clone();
+        for &c in &kids[v] {
+            acc = merge(&acc, down[c].as_ref().unwrap());
+        }
+        down[v] = Some(finish(value(v), &acc));
+    }
+
+    // up[c]：以 c 为根时，原父节点方向的子树结果
+    let mut up: Vec<Option<R>> = vec![None; n];
+    let mut answer: Vec<Option<R>> = vec![None; n];
+    for v in 0..n {
+        let k = kids[v].len();
+        // prefix[i]：父方向结果与前 i 个子节点结果的合并
+        let mut prefix = Vec::with_capacity(k + 1);
+        prefix.push(up[v].clone().unwrap_or_else(|| identity.clone()));
+        for &c in &kids[v] {
+            let next = merge(&prefix[prefix.len() - 1], down[c].as_ref().unwrap());
+            prefix.push(next);
+        }
+        let mut suffix = identity.clone();
+        for i in (0..k).rev() {
+            let c = kids[v][i];
+            up[c] = Some(finish(value(v), &merge(&prefix[i], &suffix)));
+            suffix = merge(down[c].as_ref().unwrap(), &suffix);
+        }
+        answer[v] = Some(finish(value(v), &prefix[k]));
+    }
+
+    order
+        .into_iter()
+        .zip(answer.into_iter().map(Option::unwrap))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::{LcrsTree, Tree, VecTree};
+
+    #[test]
+    fn cost_rollup() {
+        // 部门成本汇总
+        let mut t = VecTree::with_root(("company", 10));
+        let r = t.root();
+        let eng = t.add_child(r, ("eng", 100)).unwrap();
+        let ops = t.add_child(r, ("ops", 40)).unwrap();
+        let web = t.add_child(eng, ("web", 30)).unwrap();
+        let infra = t.add_child(eng, ("infra", 20)).unwrap();
+
+        let totals = fold_subtrees(&t, |&(_, cost), kids: &[&u32]| {
+            cost + kids.iter().copied().sum::<u32>()
+        });
+        assert_eq!(totals[&r], 200);
+        assert_eq!(totals[&eng], 150);
+        assert_eq!((totals[&ops], totals[&web], totals[&infra]), (40, 30, 20));
+
+        // 子节点结果按子节点顺序传入
+        let outline = fold_subtrees(&t, |&(name, _), kids: &[&String]| {
+            if kids.is_empty() {
+                name.to_string()
+            } else {
+                let inner: Vec<&str> = kids.iter().map(|s| s.as_str()).collect();
+                format!("{name}({})", inner.join(","))
+            }
+        });
+        assert_eq!(outline[&r], "company(eng(web,infra),ops)");
+    }
+
+    #[test]
+    fn reroot_matches_naive() {
+        // 随机形状的树
+        let mut t = LcrsTree::with_root(());
+        let mut nodes = vec![t.root()];
+        let mut x = 7u64;
+        for _ in 1..60 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let p = nodes[(x >> 33) as usize % nodes.len()];
+            nodes.push(t.add_child(p, ()).unwrap());
+        }
+
+        // (子树节点数, 子树内所有节点到子树根的距离之和)
+        let sums = reroot(
+            &t,
+            (0usize, 0usize),
+            |a, b| (a.0 + b.0, a.1 + b.1),
+            |_, acc| (acc.0 + 1, acc.1 + acc.0),
+        );
+        // 离心率：到最远节点的距离
+        let ecc = reroot(&t, -1i64, |a, b| *a.max(b), |_, acc| acc + 1);
+
+        for &v in &nodes {
+            let dists: Vec<usize> = nodes.iter().map(|&u| t.distance(v, u).unwrap()).collect();
+            assert_eq!(sums[&v], (nodes.len(), dists.iter().sum()));
+            assert_eq!(ecc[&v], *dists.iter().max().unwrap() as i64);
+        }
+    }
+
+    #[test]
+    fn reroot_single_node() {
+        let t = VecTree::with_root(5);
+        let res = reroot(&t, 0, |a, b| a + b, |v, acc| v + acc);
+        assert_eq!(res[&t.root()], 5);
+        assert_eq!(fold_subtrees(&t, |v, _: &[&i32]| *v)[&t.root()], 5);
+    }
+}
@@ -32,13 +32,13 @@ pub trait Hierarchy: Tree {
     /// - Err(_)：节点无效
     #[inline]
     fn is_leaf(&self, node: NodeId) -> AlgodsResult<bool> {
-        Ok(self.children(node)?.is_empty())
+        Ok(self.children(node)?.next().is_none())
     }
 
     /// 节点的出度（直接子节点数量）
     #[inline]
     fn degree(&self, node: NodeId) -> AlgodsResult<usize> {
-        Ok(self.children(node)?.len())
+        Ok(self.children(node)?.count())
     }
 
     /// 节点深度（根节点深度为 0）
@@ -55,6 +55,95 @@ pub trait Hierarchy: Tree {
         Ok(d)
     }
 
+    /// 判断 `ancestor` 是否为 `node` 的真祖先（不包含自身）
+    ///
+    /// ## 返回
+    /// - Ok(true)：`ancestor` 位于 `node` 到根的路径上且不等于 `node`
+    /// - Ok(false)：否则
+    /// - Err(_)：任一节点无效
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(depth(node))
+    fn is_ancestor_of(&self, ancestor: NodeId, node: NodeId) -> AlgodsResult<bool> {
+        if !self.contains(ancestor) {
+            return Err(AlgodsError::InvalidNodeId);
+        }
+        let mut curr = self.parent(node)?;
+        while let Some(p) = curr {
+            if p == ancestor {
+                return Ok(true);
+            }
+            curr = self.parent_unchecked(p);
+        }
+        Ok(false)
+    }
+
+    /// 最近公共祖先
+    ///
+    /// 节点是其自身的祖先，因此 `lca(a, a) == a`，且若 a 是 b 的祖先则 `lca(a, b) == a`
+    ///
+    /// ## 返回
+    /// - Ok(lca)：a 与 b 的最近公共祖先
+    /// - Err(_)：任一节点无效
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(depth(a) + depth(b))；大量查询请使用 [`LcaIndex`](crate::hierarchy::LcaIndex)
+    fn lca(&self, mut a: NodeId, mut b: NodeId) -> AlgodsResult<NodeId> {
+        let (mut da, mut db) = (self.depth(a)?, self.depth(b)?);
+        while da > db {
+            a = self.parent_unchecked(a).unwrap();
+            da -= 1;
+        }
+        while db > da {
+            b = self.parent_unchecked(b).unwrap();
+            db -= 1;
+        }
+        while a != b {
+            a = self.parent_unchecked(a).unwrap();
+            b = self.parent_unchecked(b).unwrap();
+        }
+        Ok(a)
+    }
+
+    /// 两节点之间的距离（路径上的边数）
+    ///
+    /// ## 返回
+    /// - Ok(dist)：depth(a) + depth(b) - 2 * depth(lca)
+    /// - Err(_)：任一节点无效
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(depth(a) + depth(b))
+    fn distance(&self, a: NodeId, b: NodeId) -> AlgodsResult<usize> {
+        let l = self.lca(a, b)?;
+        Ok(self.depth(a)? + self.depth(b)? - 2 * self.depth(l)?)
+    }
+
+    /// 从 a 到 b 的唯一路径：a 向上到最近公共祖先，再向下到 b
+    ///
+    /// ## 返回
+    /// - Ok(path)：首元素为 a、末元素为 b 的节点序列
+    /// - Err(_)：任一节点无效
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(depth(a) + depth(b))
+    fn path(&self, a: NodeId, b: NodeId) -> AlgodsResult<Vec<NodeId>> {
+        let l = self.lca(a, b)?;
+        let mut up = vec![a];
+        let mut curr = a;
+        while curr != l {
+            curr = self.parent_unchecked(curr).unwrap();
+            up.push(curr);
+        }
+        let mut down = Vec::new();
+        let mut curr = b;
+        while curr != l {
+            down.push(curr);
+            curr = self.parent_unchecked(curr).unwrap();
+        }
+        up.extend(down.into_iter().rev());
+        Ok(up)
+    }
+
     /// 迭代器：从父节点开始向上遍历祖先（不包含自身）
     fn ancestors_iter(&self, node: NodeId) -> AlgodsResult<AncestorsIter<'_, Self>>
     where
@@ -109,6 +198,122 @@ pub trait Hierarchy: Tree {
         queue.push_back(start);
         Ok(BfsIter { tree: self, queue })
     }
+
+    /// 后序遍历迭代器：先按顺序访问所有子节点，再访问节点自身
+    ///
+    /// 适用于表达式树求值、子树聚合等需要自底向上处理的场景
+    ///
+    /// ## 参数
+    /// * `start` - 遍历起点
+    ///
+    /// ## 返回
+    /// - Ok(PostOrderIter)：合法节点起点
+    /// - Err(_)：起点无效
+    fn post_order_iter(&self, start: NodeId) -> AlgodsResult<PostOrderIter<'_, Self>>
+    where
+        Self: Sized,
+    {
+        if !self.contains(start) {
+            return Err(AlgodsError::InvalidNodeId);
+        }
+        Ok(PostOrderIter {
+            tree: self,
+            stack: vec![(start, self.children_unchecked(start))],
+        })
+    }
+
+    /// 按层遍历迭代器，产出 `(depth, NodeId)`，深度相对于起点（起点深度为 0）
+    ///
+    /// 节点按深度非递减的顺序产出，同层节点按从左到右的顺序
+    ///
+    /// ## 参数
+    /// * `start` - 遍历起点
+    ///
+    /// ## 返回
+    /// - Ok(LevelOrderIter)：合法节点起点
+    /// - Err(_)：起点无效
+    fn level_order_iter(&self, start: NodeId) -> AlgodsResult<LevelOrderIter<'_, Self>>
+    where
+        Self: Sized,
+    {
+        if !self.contains(start) {
+            return Err(AlgodsError::InvalidNodeId);
+        }
+        let mut queue = VecDeque::new();
+        queue.push_back((0, start));
+        Ok(LevelOrderIter { tree: self, queue })
+    }
+
+    /// 子树中叶子节点的迭代器（先序顺序）
+    ///
+    /// 起点本身是叶子时只产出起点
+    fn leaves_iter(&self, start: NodeId) -> AlgodsResult<LeavesIter<'_, Self>>
+    where
+        Self: Sized,
+    {
+        Ok(LeavesIter {
+            inner: self.dfs_iter(start)?,
+        })
+    }
+
+    /// 子树中所有后代节点的迭代器（先序顺序，不包含自身）
+    fn descendants_iter(&self, node: NodeId) -> AlgodsResult<DescendantsIter<'_, Self>>
+    where
+        Self: Sized,
+    {
+        let mut inner = self.dfs_iter(node)?;
+        inner.next();
+        Ok(DescendantsIter { inner })
+    }
+
+    /// 兄弟节点迭代器（按父节点中的顺序，不包含自身；根节点没有兄弟）
+    fn siblings_iter(&self, node: NodeId) -> AlgodsResult<SiblingsIter<'_, Self>> {
+        let iter = self.parent(node)?.map(|p| self.children_unchecked(p));
+        Ok(SiblingsIter { iter, node })
+    }
+
+    /// 先序遍历子树并逐个提供节点值的可变引用，可剪枝
+    ///
+    /// ## 参数
+    /// * `start` - 遍历起点
+    /// * `visit` - 访问函数，参数为 (节点, 相对起点的深度, 值的可变引用)；
+    ///   返回 false 时跳过该节点的子树
+    ///
+    /// ## 返回
+    /// - Ok(())：遍历完成
+    /// - Err(_)：起点无效
+    fn traverse_mut<F>(&mut self, start: NodeId, mut visit: F) -> AlgodsResult<()>
+    where
+        Self: Sized,
+        F: FnMut(NodeId, usize, &mut Self::Value) -> bool,
+    {
+        if !self.contains(start) {
+            return Err(AlgodsError::InvalidNodeId);
+        }
+        let mut stack = vec![(start, 0)];
+        while let Some((n, depth)) = stack.pop() {
+            if visit(n, depth, self.value_mut(n)?) {
+                stack.extend(self.children_unchecked(n).rev().map(|c| (c, depth + 1)));
+            }
+        }
+        Ok(())
+    }
+
+    /// 先序遍历子树，对每个节点值调用 `f`
+    ///
+    /// ## 参数
+    /// * `start` - 遍历起点
+    /// * `f` - 访问函数，参数为 (节点, 值的可变引用)
+    fn for_each_mut<F>(&mut self, start: NodeId, mut f: F) -> AlgodsResult<()>
+    where
+        Self: Sized,
+        F: FnMut(NodeId, &mut Self::Value),
+    {
+        self.traverse_mut(start, |n, _, v| {
+            f(n, v);
+            true
+        })
+    }
 }
 
 /// 祖先迭代器
@@ -138,9 +343,7 @@ impl<'a, T: Tree> Iterator for DfsIter<'a, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let n = self.stack.pop()?;
-        for &c in self.tree.children_unchecked(n).iter().rev() {
-            self.stack.push(c);
-        }
+        self.stack.extend(self.tree.children_unchecked(n).rev());
         Some(n)
     }
 }
@@ -156,12 +359,236 @@ impl<'a, T: Tree> Iterator for BfsIter<'a, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let n = self.queue.pop_front()?;
-        for &c in self.tree.children_unchecked(n) {
-            self.queue.push_back(c);
-        }
+        self.queue.extend(self.tree.children_unchecked(n));
         Some(n)
     }
 }
 
+/// 后序遍历迭代器
+pub struct PostOrderIter<'a, T: Tree + ?Sized> {
+    tree: &'a T,
+    /// (节点, 尚未访问的子节点)
+    stack: Vec<(NodeId, T::Children<'a>)>,
+}
+
+impl<'a, T: Tree> Iterator for PostOrderIter<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (n, children) = self.stack.last_mut()?;
+            let n = *n;
+            match children.next() {
+                Some(c) => {
+                    let grandchildren = self.tree.children_unchecked(c);
+                    self.stack.push((c, grandchildren));
+                }
+                None => {
+                    self.stack.pop();
+                    return Some(n);
+                }
+            }
+        }
+    }
+}
+
+/// 按层遍历迭代器
+pub struct LevelOrderIter<'a, T: Tree + ?Sized> {
+    tree: &'a T,
+    queue: VecDeque<(usize, NodeId)>,
+}
+
+impl<'a, T: Tree> Iterator for LevelOrderIter<'a, T> {
+    type Item = (usize, NodeId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, n) = self.queue.pop_front()?;
+        self.queue
+            .extend(self.tree.children_unchecked(n).map(|c| (depth + 1, c)));
+        Some((depth, n))
+    }
+}
+
+/// 叶子节点迭代器
+pub struct LeavesIter<'a, T: Tree + ?Sized> {
+    inner: DfsIter<'a, T>,
+}
+
+impl<'a, T: Tree> Iterator for LeavesIter<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.inner.tree;
+        self.inner
+            .by_ref()
+            .find(|&n| tree.children_unchecked(n).next().is_none())
+    }
+}
+
+/// 后代节点迭代器
+pub struct DescendantsIter<'a, T: Tree + ?Sized> {
+    inner: DfsIter<'a, T>,
+}
+
+impl<'a, T: Tree> Iterator for DescendantsIter<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// 兄弟节点迭代器
+pub struct SiblingsIter<'a, T: Tree + ?Sized + 'a> {
+    /// 父节点的子节点，根节点为 None
+    iter: Option<T::Children<'a>>,
+    node: NodeId,
+}
+
+impl<'a, T: Tree + ?Sized> Iterator for SiblingsIter<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node;
+        self.iter.as_mut()?.find(|&c| c != node)
+    }
+}
+
 // 自动实现 Hierarchy trait 给所有 Tree
 impl<T: Tree> Hierarchy for T {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::hierarchy::{LcrsTree, VecTree};
+
+    /// ```text
+    ///         r
+    ///       / | \
+    ///      a  b  c
+    ///     / \     \
+    ///    d   e     f
+    /// ```
+    fn sample<T: Tree<Value = char>>(mut t: T) -> (T, Vec<NodeId>) {
+        let r = t.root();
+        let a = t.add_child(r, 'a').unwrap();
+        let b = t.add_child(r, 'b').unwrap();
+        let c = t.add_child(r, 'c').unwrap();
+        let d = t.add_child(a, 'd').unwrap();
+        let e = t.add_child(a, 'e').unwrap();
+        let f = t.add_child(c, 'f').unwrap();
+        (t, vec![r, a, b, c, d, e, f])
+    }
+
+    fn names<T: Tree<Value = char>>(t: &T, it: impl Iterator<Item = NodeId>) -> String {
+        it.map(|n| *t.value(n).unwrap()).collect()
+    }
+
+    fn check_iterators<T: Tree<Value = char>>(t: T) {
+        let (t, n) = sample(t);
+        let (r, a, b, c) = (n[0], n[1], n[2], n[3]);
+
+        assert_eq!(names(&t, t.post_order_iter(r).unwrap()), "deabfcr");
+        assert_eq!(names(&t, t.post_order_iter(a).unwrap()), "dea");
+        assert_eq!(names(&t, t.leaves_iter(r).unwrap()), "debf");
+        assert_eq!(names(&t, t.leaves_iter(b).unwrap()), "b");
+        assert_eq!(names(&t, t.descendants_iter(r).unwrap()), "adebcf");
+        assert_eq!(names(&t, t.descendants_iter(b).unwrap()), "");
+        assert_eq!(names(&t, t.siblings_iter(b).unwrap()), "ac");
+        assert_eq!(names(&t, t.siblings_iter(r).unwrap()), "");
+
+        let levels: Vec<(usize, char)> = t
+            .level_order_iter(r)
+            .unwrap()
+            .map(|(d, n)| (d, *t.value(n).unwrap()))
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                (0, 'r'),
+                (1, 'a'),
+                (1, 'b'),
+                (1, 'c'),
+                (2, 'd'),
+                (2, 'e'),
+                (2, 'f')
+            ]
+        );
+        let from_c: Vec<_> = t.level_order_iter(c).unwrap().map(|(d, _)| d).collect();
+        assert_eq!(from_c, [0, 1]);
+    }
+
+    #[test]
+    fn iterators_on_both_layouts() {
+        check_iterators(VecTree::with_root('r'));
+        check_iterators(LcrsTree::with_root('r'));
+    }
+
+    #[test]
+    fn iterators_reject_invalid_nodes() {
+        let (mut t, n) = sample(VecTree::with_root('r'));
+        t.remove_subtree(n[1]).unwrap();
+        assert!(t.post_order_iter(n[4]).is_err());
+        assert!(t.level_order_iter(n[1]).is_err());
+        assert!(t.leaves_iter(n[1]).is_err());
+        assert!(t.descendants_iter(n[1]).is_err());
+        assert!(t.siblings_iter(n[1]).is_err());
+        assert!(t.for_each_mut(n[1], |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn post_order_evaluates_expression() {
+        // (1 + 2) * (10 - 4)
+        #[derive(Clone, Copy)]
+        enum Expr {
+            Num(i64),
+            Add,
+            Sub,
+            Mul,
+        }
+        let mut t = VecTree::with_root(Expr::Mul);
+        let r = t.root();
+        let add = t.add_child(r, Expr::Add).unwrap();
+        let sub = t.add_child(r, Expr::Sub).unwrap();
+        t.add_child(add, Expr::Num(1)).unwrap();
+        t.add_child(add, Expr::Num(2)).unwrap();
+        t.add_child(sub, Expr::Num(10)).unwrap();
+        t.add_child(sub, Expr::Num(4)).unwrap();
+
+        let mut results: HashMap<NodeId, i64> = HashMap::new();
+        for n in t.post_order_iter(r).unwrap() {
+            let args: Vec<i64> = t.children(n).unwrap().map(|c| results[&c]).collect();
+            let v = match *t.value(n).unwrap() {
+                Expr::Num(x) => x,
+                Expr::Add => args.iter().sum(),
+                Expr::Sub => args[0] - args[1],
+                Expr::Mul => args.iter().product(),
+            };
+            results.insert(n, v);
+        }
+        assert_eq!(results[&r], 18);
+    }
+
+    #[test]
+    fn mutable_traversal() {
+        let (mut t, n) = sample(LcrsTree::with_root('r'));
+        let (r, a, c) = (n[0], n[1], n[3]);
+
+        t.for_each_mut(a, |_, v| *v = v.to_ascii_uppercase())
+            .unwrap();
+        assert_eq!(names(&t, t.dfs_iter(r).unwrap()), "rADEbcf");
+
+        // 剪掉 c 的子树，并按深度改写其余节点
+        let mut visited = Vec::new();
+        t.traverse_mut(r, |node, depth, v| {
+            visited.push(node);
+            *v = char::from_digit(depth as u32, 10).unwrap();
+            node != c
+        })
+        .unwrap();
+        assert_eq!(visited.len(), 6);
+        assert_eq!(names(&t, t.dfs_iter(r).unwrap()), "012211f");
+    }
+}
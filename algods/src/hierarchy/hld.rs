@@ -0,0 +1,402 @@
+use std::ops::Range;
+
+use crate::{
+    error::{AlgodsError, AlgodsResult},
+    hierarchy::{
+        hierarchy::Hierarchy,
+        tree::{NodeId, Tree},
+    },
+};
+
+/// 重链剖分（Heavy-Light Decomposition）
+///
+/// 每个节点与其子树最大的子节点（重儿子）位于同一条重链上。节点按“先走重儿子”的先序编号，
+/// 使得每条重链、每棵子树在编号上都是连续区间，任意路径可拆分为 O(log n) 个区间，
+/// 配合区间数据结构即可支持路径查询，见 [`PathAggregate`]。
+///
+/// 与 [`LcaIndex`](crate::hierarchy::LcaIndex) 相同，查询时需传入同一棵树，
+/// 树结构在构建之后被修改时返回 [`AlgodsError::StaleIndex`]
+pub struct HeavyLightDecomposition {
+    /// arena 下标 → 紧凑编号
+    slots: Vec<Option<usize>>,
+    /// 紧凑编号 → NodeId
+    nodes: Vec<NodeId>,
+    /// 父节点（根的父节点为自身）
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    /// 所在重链的顶端
+    head: Vec<usize>,
+    /// 剖分后的位置
+    pos: Vec<usize>,
+    /// 构建时树的结构版本号
+    version: u64,
+}
+
+impl HeavyLightDecomposition {
+    /// 为整棵树构建剖分
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n)
+    pub fn new<T: Hierarchy>(tree: &T) -> Self {
+        let mut hld = Self {
+            slots: Vec::new(),
+            nodes: Vec::with_capacity(tree.size()),
+            parent: Vec::with_capacity(tree.size()),
+            depth: Vec::with_capacity(tree.size()),
+            size: Vec::new(),
+            head: Vec::new(),
+            pos: Vec::new(),
+            version: tree.version(),
+        };
+
+        // 先序编号，记录父节点与深度
+        for n in tree.dfs_iter(tree.root()).unwrap() {
+            let id = hld.nodes.len();
+            if hld.slots.len() <= n.index {
+                hld.slots.resize(n.index + 1, None);
+            }
+            hld.slots[n.index] = Some(id);
+            hld.nodes.push(n);
+            let p = tree
+                .parent_unchecked(n)
+                .map_or(id, |p| hld.slots[p.index].unwrap());
+            hld.parent.push(p);
+            hld.depth.push(if p == id { 0 } else { hld.depth[p] + 1 });
+        }
+
+        let n = hld.nodes.len();
+        hld.size = vec![1; n];
+        for v in (1..n).rev() {
+            let p = hld.parent[v];
+            hld.size[p] += hld.size[v];
+        }
+
+        // 先走重儿子的先序遍历：轻儿子先入栈，重儿子最后入栈、最先出栈
+        hld.head = vec![0; n];
+        hld.pos = vec![0; n];
+        let mut next = 0;
+        let mut stack = vec![0];
+        while let Some(v) = stack.pop() {
+            hld.pos[v] = next;
+            next += 1;
+            let kids: Vec<usize> = tree
+                .children_unchecked(hld.nodes[v])
+                .map(|c| hld.slots[c.index].unwrap())
+                .collect();
+            let heavy = kids.iter().copied().max_by_key(|&c| hld.size[c]);
+            for &c in kids.iter().rev() {
+                if Some(c) != heavy {
+                    hld.head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if let Some(h) = heavy {
+                hld.head[h] = hld.head[v];
+                stack.push(h);
+            }
+        }
+        hld
+    }
+
+    /// 将 NodeId 映射为紧凑编号：树结构须与构建时一致，节点须在树中存活且属于剖分
+    fn slot<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<usize> {
+        if self.is_stale(tree) {
+            return Err(AlgodsError::StaleIndex);
+        }
+        match self.slots.get(node.index) {
+            Some(&Some(id)) if self.nodes[id] == node && tree.contains(node) => Ok(id),
+            _ => Err(AlgodsError::InvalidNodeId),
+        }
+    }
+
+    /// 树结构在构建剖分之后是否被修改过
+    ///
+    /// 时间复杂度: O(1)
+    pub fn is_stale<T: Tree>(&self, tree: &T) -> bool {
+        tree.version() != self.version
+    }
+
+    /// 剖分中的节点数
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 剖分是否为空（根节点总在其中，因此恒为 false）
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 节点在剖分序列中的位置，取值范围 `0..len()`
+    pub fn position<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<usize> {
+        Ok(self.pos[self.slot(tree, node)?])
+    }
+
+    /// 节点所在重链的顶端节点
+    pub fn head<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<NodeId> {
+        Ok(self.nodes[self.head[self.slot(tree, node)?]])
+    }
+
+    /// 子树在剖分序列中对应的区间
+    ///
+    /// 时间复杂度: O(1)
+    pub fn subtree_range<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<Range<usize>> {
+        let v = self.slot(tree, node)?;
+        Ok(self.pos[v]..self.pos[v] + self.size[v])
+    }
+
+    /// 将 a 到 b 的路径（包含两端）拆分为剖分序列上的若干区间
+    ///
+    /// 区间互不相交，顺序不保证与路径方向一致
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn path_segments<T: Tree>(
+        &self,
+        tree: &T,
+        a: NodeId,
+        b: NodeId,
+    ) -> AlgodsResult<Vec<Range<usize>>> {
+        let (a, b) = (self.slot(tree, a)?, self.slot(tree, b)?);
+        let mut out = Vec::new();
+        self.walk(a, b, |r| out.push(r));
+        Ok(out)
+    }
+
+    /// 沿重链向上合并，直到两端位于同一条重链；返回 LCA 的紧凑编号
+    fn walk<F: FnMut(Range<usize>)>(&self, mut a: usize, mut b: usize, mut emit: F) -> usize {
+        while self.head[a] != self.head[b] {
+            if self.depth[self.head[a]] < self.depth[self.head[b]] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            emit(self.pos[self.head[a]]..self.pos[a] + 1);
+            a = self.parent[self.head[a]];
+        }
+        let (lo, hi) = if self.pos[a] <= self.pos[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        emit(self.pos[lo]..self.pos[hi] + 1);
+        lo
+    }
+
+    /// 最近公共祖先
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn lca<T: Tree>(&self, tree: &T, a: NodeId, b: NodeId) -> AlgodsResult<NodeId> {
+        let (a, b) = (self.slot(tree, a)?, self.slot(tree, b)?);
+        Ok(self.nodes[self.walk(a, b, |_| {})])
+    }
+}
+
+/// 基于重链剖分的路径 / 子树聚合
+///
+/// 为每个节点保存一个值 R，支持单点修改、路径聚合与子树聚合。
+/// 路径区间的合并顺序与路径方向无关，因此 `op` 须满足结合律与交换律（如求和、最大值）
+pub struct PathAggregate<R, F> {
+    hld: HeavyLightDecomposition,
+    identity: R,
+    op: F,
+    /// 自底向上的线段树，叶子位于 `n..2n`，按剖分位置排列
+    data: Vec<R>,
+}
+
+impl<R: Clone, F: Fn(&R, &R) -> R> PathAggregate<R, F> {
+    /// 构建聚合结构
+    ///
+    /// ## 参数
+    /// * `tree` - 树
+    /// * `identity` - `op` 的单位元
+    /// * `op` - 满足结合律与交换律的合并函数
+    /// * `init` - 由节点值得到初始的聚合值
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n)
+    pub fn new<T, I>(tree: &T, identity: R, op: F, mut init: I) -> Self
+    where
+        T: Hierarchy,
+        I: FnMut(&T::Value) -> R,
+    {
+        let hld = HeavyLightDecomposition::new(tree);
+        let n = hld.len();
+        let mut data = vec![identity.clone(); 2 * n];
+        for (v, &node) in hld.nodes.iter().enumerate() {
+            data[n + hld.pos[v]] = init(tree.value(node).unwrap());
+        }
+        for i in (1..n).rev() {
+            data[i] = op(&data[2 * i], &data[2 * i + 1]);
+        }
+        Self {
+            hld,
+            identity,
+            op,
+            data,
+        }
+    }
+
+    /// 底层的重链剖分
+    pub fn decomposition(&self) -> &HeavyLightDecomposition {
+        &self.hld
+    }
+
+    /// 节点当前的聚合值
+    pub fn get<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<&R> {
+        let n = self.hld.len();
+        Ok(&self.data[n + self.hld.position(tree, node)?])
+    }
+
+    /// 修改节点的聚合值
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn set<T: Tree>(&mut self, tree: &T, node: NodeId, value: R) -> AlgodsResult<()> {
+        let n = self.hld.len();
+        let mut i = n + self.hld.position(tree, node)?;
+        self.data[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.data[i] = (self.op)(&self.data[2 * i], &self.data[2 * i + 1]);
+        }
+        Ok(())
+    }
+
+    /// 剖分序列区间 [range.start, range.end) 上的聚合
+    fn query(&self, range: Range<usize>) -> R {
+        let n = self.hld.len();
+        let (mut l, mut r) = (range.start + n, range.end + n);
+        let mut acc = self.identity.clone();
+        while l < r {
+            if l & 1 == 1 {
+                acc = (self.op)(&acc, &self.data[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                acc = (self.op)(&acc, &self.data[r]);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        acc
+    }
+
+    /// a 到 b 路径（包含两端）上所有节点值的聚合
+    ///
+    /// 时间复杂度: O(log^2 n)
+    pub fn path<T: Tree>(&self, tree: &T, a: NodeId, b: NodeId) -> AlgodsResult<R> {
+        let (a, b) = (self.hld.slot(tree, a)?, self.hld.slot(tree, b)?);
+        let mut acc = self.identity.clone();
+        self.hld
+            .walk(a, b, |r| acc = (self.op)(&acc, &self.query(r)));
+        Ok(acc)
+    }
+
+    /// 以 node 为根的子树中所有节点值的聚合
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn subtree<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<R> {
+        Ok(self.query(self.hld.subtree_range(tree, node)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::VecTree;
+
+    fn random_tree(n: usize, seed: u64) -> (VecTree<u64>, Vec<NodeId>) {
+        let mut t = VecTree::with_root(seed % 100);
+        let mut nodes = vec![t.root()];
+        let mut x = seed;
+        for _ in 1..n {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let p = nodes[(x >> 33) as usize % nodes.len()];
+            nodes.push(t.add_child(p, (x >> 40) % 100).unwrap());
+        }
+        (t, nodes)
+    }
+
+    #[test]
+    fn decomposition_structure() {
+        let (t, nodes) = random_tree(200, 3);
+        let hld = HeavyLightDecomposition::new(&t);
+        assert_eq!(hld.len(), 200);
+
+        let mut seen = [false; 200];
+        for &v in &nodes {
+            let p = hld.position(&t, v).unwrap();
+            assert!(!std::mem::replace(&mut seen[p], true));
+
+            // 子树区间恰好覆盖所有后代
+            let range = hld.subtree_range(&t, v).unwrap();
+            assert_eq!(range.len(), t.dfs_iter(v).unwrap().count());
+            for d in t.dfs_iter(v).unwrap() {
+                assert!(range.contains(&hld.position(&t, d).unwrap()));
+            }
+        }
+
+        for &a in nodes.iter().step_by(7) {
+            for &b in nodes.iter().step_by(5) {
+                assert_eq!(hld.lca(&t, a, b).unwrap(), t.lca(a, b).unwrap());
+                let segs = hld.path_segments(&t, a, b).unwrap();
+                let covered: usize = segs.iter().map(|r| r.len()).sum();
+                assert_eq!(covered, t.distance(a, b).unwrap() + 1);
+                // 路径区间数为 O(log n)
+                assert!(segs.len() <= 2 * 8 + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn path_and_subtree_queries() {
+        let (t, nodes) = random_tree(150, 11);
+        let mut sum = PathAggregate::new(&t, 0u64, |a, b| a + b, |&v| v);
+        let max = PathAggregate::new(&t, 0u64, |a, b| *a.max(b), |&v| v);
+
+        for &a in nodes.iter().step_by(3) {
+            for &b in nodes.iter().step_by(4) {
+                let path = t.path(a, b).unwrap();
+                let values = path.iter().map(|&n| *t.value(n).unwrap());
+                assert_eq!(sum.path(&t, a, b).unwrap(), values.clone().sum::<u64>());
+                assert_eq!(max.path(&t, a, b).unwrap(), values.max().unwrap());
+            }
+        }
+
+        let total: u64 = nodes.iter().map(|&n| *t.value(n).unwrap()).sum();
+        assert_eq!(sum.subtree(&t, t.root()).unwrap(), total);
+
+        let leaf = *nodes.last().unwrap();
+        let old = *sum.get(&t, leaf).unwrap();
+        sum.set(&t, leaf, old + 1000).unwrap();
+        assert_eq!(sum.subtree(&t, t.root()).unwrap(), total + 1000);
+        assert_eq!(sum.path(&t, leaf, leaf).unwrap(), old + 1000);
+    }
+
+    #[test]
+    fn rejects_stale_nodes() {
+        let mut t = VecTree::with_root(1);
+        let r = t.root();
+        let a = t.add_child(r, 2).unwrap();
+        let b = t.add_child(a, 3).unwrap();
+        let agg = PathAggregate::new(&t, 0, |a, b| a + b, |&v| v);
+        assert_eq!(agg.path(&t, r, b).unwrap(), 6);
+
+        t.remove_subtree(b).unwrap();
+        assert_eq!(agg.path(&t, r, b), Err(AlgodsError::StaleIndex));
+        let c = t.add_child(a, 4).unwrap();
+        assert_eq!(
+            agg.decomposition().lca(&t, c, r),
+            Err(AlgodsError::StaleIndex)
+        );
+        assert!(agg.decomposition().is_stale(&t));
+
+        // 只移动子树时同样会被检测到
+        let agg = PathAggregate::new(&t, 0, |a, b| a + b, |&v| v);
+        assert_eq!(agg.path(&t, c, r).unwrap(), 7);
+        let d = t.add_child(r, 8).unwrap();
+        let agg = PathAggregate::new(&t, 0, |a, b| a + b, |&v| v);
+        t.move_subtree(c, d, 0).unwrap();
+        assert_eq!(agg.subtree(&t, d), Err(AlgodsError::StaleIndex));
+    }
+}
@@ -0,0 +1,447 @@
+//! 树的文本表示与序列化
+//!
+//! - [`Display`]：`tree(1)` 风格的 Unicode 框线绘制
+//! - 缩进大纲：[`to_outline`](VecTree::to_outline) / [`from_outline`](VecTree::from_outline)
+//! - 父节点数组：[`to_parent_array`](VecTree::to_parent_array) / [`from_parent_array`](VecTree::from_parent_array)
+//! - 嵌套结构：[`NestedTree`]，启用 `serde` feature 后可经任意 serde 格式读写
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use crate::{
+    error::{AlgodsError, AlgodsResult},
+    hierarchy::{
+        hierarchy::Hierarchy,
+        lcrs_tree::LcrsTree,
+        tree::{NodeId, Tree},
+        vec_tree::VecTree,
+    },
+};
+
+fn parse_error(line: usize, message: impl Into<String>) -> AlgodsError {
+    AlgodsError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+/// 以框线字符绘制整棵树，根节点独占首行，末尾不含换行
+///
+/// 节点值中的换行不做处理
+fn write_tree<T>(tree: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: Tree,
+    T::Value: Display,
+{
+    let root = tree.root();
+    write!(f, "{}", tree.value(root).unwrap())?;
+
+    // (节点, 继承的前缀, 是否为最后一个兄弟)
+    let push_children = |stack: &mut Vec<(NodeId, String, bool)>, n: NodeId, prefix: String| {
+        // 逆序入栈，首个入栈的即最后一个兄弟
+        for (i, c) in tree.children_unchecked(n).rev().enumerate() {
+            stack.push((c, prefix.clone(), i == 0));
+        }
+    };
+    let mut stack = Vec::new();
+    push_children(&mut stack, root, String::new());
+    while let Some((n, prefix, last)) = stack.pop() {
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        write!(f, "\n{prefix}{branch}{}", tree.value(n).unwrap())?;
+        push_children(&mut stack, n, prefix + indent);
+    }
+    Ok(())
+}
+
+impl<T: Display> Display for VecTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(self, f)
+    }
+}
+
+impl<T: Display> Display for LcrsTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(self, f)
+    }
+}
+
+/// 嵌套表示的树，便于快照比较与序列化
+///
+/// 启用 `serde` feature 后实现 `Serialize` / `Deserialize`，
+/// 叶子节点序列化时省略空的 `children` 字段
+///
+/// ## Notes
+/// 构造、比较与序列化均为递归实现，极深的树可能耗尽调用栈
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NestedTree<T> {
+    /// 节点值
+    pub value: T,
+    /// 按顺序排列的子树
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub children: Vec<NestedTree<T>>,
+}
+
+impl<T> NestedTree<T> {
+    /// 创建带子树的节点
+    pub fn new(value: T, children: Vec<NestedTree<T>>) -> Self {
+        Self { value, children }
+    }
+
+    /// 创建叶子节点
+    pub fn leaf(value: T) -> Self {
+        Self::new(value, Vec::new())
+    }
+}
+
+impl<T> From<NestedTree<T>> for VecTree<T> {
+    fn from(nested: NestedTree<T>) -> Self {
+        VecTree::from_nested(nested)
+    }
+}
+
+impl<T> VecTree<T> {
+    /// 导出为缩进大纲：每个节点一行，每层缩进两个空格
+    ///
+    /// 节点值首尾的空白在 [`from_outline`](VecTree::from_outline) 中会被去除，
+    /// 含换行的值无法被正确读回
+    ///
+    /// 时间复杂度: O(n)
+    pub fn to_outline(&self) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::new();
+        let mut stack = vec![(self.root(), 0)];
+        while let Some((n, depth)) = stack.pop() {
+            let _ = writeln!(
+                out,
+                "{:indent$}{}",
+                "",
+                self.value(n).unwrap(),
+                indent = 2 * depth
+            );
+            stack.extend(self.children_unchecked(n).rev().map(|c| (c, depth + 1)));
+        }
+        out
+    }
+
+    /// 从缩进大纲构建树
+    ///
+    /// 第一行非空内容为根节点，之后每行为一个节点，以空格缩进表示层级。
+    /// 缩进单位取第一个缩进行的空格数，之后的缩进必须是它的整数倍，且每次至多加深一层；
+    /// 空行被忽略
+    ///
+    /// ## 返回
+    /// - Ok(VecTree)：解析成功
+    /// - Err(AlgodsError::Parse)：缩进非法、出现多个根、节点值无法解析或输入为空
+    ///
+    /// 时间复杂度: O(n)
+    pub fn from_outline(src: &str) -> AlgodsResult<Self>
+    where
+        T: FromStr,
+    {
+        let mut tree: Option<Self> = None;
+        // path[d]：当前路径上深度为 d 的节点
+        let mut path: Vec<NodeId> = Vec::new();
+        let mut unit = None;
+
+        for (i, raw) in src.lines().enumerate() {
+            let line = i + 1;
+            let text = raw.trim_start_matches(' ');
+            if text.trim().is_empty() {
+                continue;
+            }
+            if text.starts_with('\t') {
+                return Err(parse_error(line, "tabs are not allowed in indentation"));
+            }
+
+            let indent = raw.len() - text.len();
+            let depth = match indent {
+                0 => 0,
+                _ => {
+                    let unit = *unit.get_or_insert(indent);
+                    if indent % unit != 0 {
+                        return Err(parse_error(
+                            line,
+                            format!("indentation of {indent} is not a multiple of {unit}"),
+                        ));
+                    }
+                    indent / unit
+                }
+            };
+
+            let text = text.trim_end();
+            let value = text
+                .parse()
+                .map_err(|_| parse_error(line, format!("invalid value {text:?}")))?;
+
+            match tree.as_mut() {
+                None if depth > 0 => {
+                    return Err(parse_error(line, "the root must not be indented"));
+                }
+                None => {
+                    let t = VecTree::with_root(value);
+                    path.push(t.root());
+                    tree = Some(t);
+                }
+                Some(_) if depth == 0 => {
+                    return Err(parse_error(line, "multiple root entries"));
+                }
+                Some(_) if depth > path.len() => {
+                    return Err(parse_error(line, "indentation skips a level"));
+                }
+                Some(t) => {
+                    path.truncate(depth);
+                    let id = t.add_child(path[depth - 1], value)?;
+                    path.push(id);
+                }
+            }
+        }
+        tree.ok_or_else(|| parse_error(0, "empty outline"))
+    }
+
+    /// 导出为 `(parent_index, value)` 数组
+    ///
+    /// 节点按先序排列，`parent_index` 为父节点在数组中的下标，根节点为 None
+    ///
+    /// 时间复杂度: O(n)
+    pub fn to_parent_array(&self) -> Vec<(Option<usize>, T)>
+    where
+        T: Clone,
+    {
+        let mut index = HashMap::with_capacity(self.size());
+        self.dfs_iter(self.root())
+            .unwrap()
+            .enumerate()
+            .map(|(i, n)| {
+                index.insert(n, i);
+                let parent = self.parent_unchecked(n).map(|p| index[&p]);
+                (parent, self.value(n).unwrap().clone())
+            })
+            .collect()
+    }
+
+    /// 从 `(parent_index, value)` 数组构建树
+    ///
+    /// 数组元素可以任意顺序排列，兄弟节点按其在数组中的先后排序
+    ///
+    /// ## 返回
+    /// - Ok(VecTree)：构建成功
+    /// - Err(AlgodsError::MissingRoot)：数组为空或没有 parent 为 None 的元素
+    /// - Err(AlgodsError::MultipleRoots)：有多个 parent 为 None 的元素
+    /// - Err(AlgodsError::IndexOutOfBounds)：parent 下标越界
+    /// - Err(AlgodsError::WouldCreateCycle)：存在无法从根到达的元素（父指针成环）
+    ///
+    /// 时间复杂度: O(n)
+    pub fn from_parent_array<I>(items: I) -> AlgodsResult<Self>
+    where
+        I: IntoIterator<Item = (Option<usize>, T)>,
+    {
+        let (parents, values): (Vec<Option<usize>>, Vec<T>) = items.into_iter().unzip();
+        let len = parents.len();
+        let mut values: Vec<Option<T>> = values.into_iter().map(Some).collect();
+
+        let mut root = None;
+        let mut children = vec![Vec::new(); len];
+        for (i, &p) in parents.iter().enumerate() {
+            match p {
+                None if root.is_some() => return Err(AlgodsError::MultipleRoots),
+                None => root = Some(i),
+                Some(p) if p >= len => return Err(AlgodsError::IndexOutOfBounds { index: p, len }),
+                Some(p) => children[p].push(i),
+            }
+        }
+        let root = root.ok_or(AlgodsError::MissingRoot)?;
+
+        let mut tree = VecTree::with_root(values[root].take().unwrap());
+        let mut stack: Vec<(usize, NodeId)> = children[root]
+            .iter()
+            .rev()
+            .map(|&c| (c, tree.root()))
+            .collect();
+        while let Some((i, parent)) = stack.pop() {
+            let id = tree.add_child(parent, values[i].take().unwrap())?;
+            stack.extend(children[i].iter().rev().map(|&c| (c, id)));
+        }
+        if tree.size() != len {
+            return Err(AlgodsError::WouldCreateCycle);
+        }
+        Ok(tree)
+    }
+
+    /// 导出为嵌套表示
+    ///
+    /// 时间复杂度: O(n)
+    pub fn to_nested(&self) -> NestedTree<T>
+    where
+        T: Clone,
+    {
+        // 后序遍历时，节点的子树恰好是栈顶的若干项
+        let mut built: Vec<NestedTree<T>> = Vec::new();
+        for n in self.post_order_iter(self.root()).unwrap() {
+            let k = self.children_unchecked(n).count();
+            let children = built.split_off(built.len() - k);
+            built.push(NestedTree::new(self.value(n).unwrap().clone(), children));
+        }
+        built.pop().unwrap()
+    }
+
+    /// 从嵌套表示构建树，子节点顺序保持不变
+    ///
+    /// 时间复杂度: O(n)
+    pub fn from_nested(nested: NestedTree<T>) -> Self {
+        let NestedTree { value, children } = nested;
+        let mut tree = VecTree::with_root(value);
+        let mut stack: Vec<(NestedTree<T>, NodeId)> = children
+            .into_iter()
+            .rev()
+            .map(|c| (c, tree.root()))
+            .collect();
+        while let Some((NestedTree { value, children }, parent)) = stack.pop() {
+            let id = tree.add_child(parent, value).unwrap();
+            stack.extend(children.into_iter().rev().map(|c| (c, id)));
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> VecTree<String> {
+        VecTree::from_outline(
+            "
+src
+  graph
+    algo.rs
+    mod.rs
+  hierarchy
+    io.rs
+  lib.rs
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn box_drawing_display() {
+        let expected = "\
+src
+├── graph
+│   ├── algo.rs
+│   └── mod.rs
+├── hierarchy
+│   └── io.rs
+└── lib.rs";
+        assert_eq!(sample().to_string(), expected);
+        assert_eq!(VecTree::with_root(1).to_string(), "1");
+
+        let mut t = LcrsTree::with_root("a");
+        let b = t.add_child(t.root(), "b").unwrap();
+        t.add_child(b, "c").unwrap();
+        assert_eq!(t.to_string(), "a\n└── b\n    └── c");
+    }
+
+    #[test]
+    fn outline_round_trip() {
+        let t = sample();
+        let text = t.to_outline();
+        assert!(text.starts_with("src\n  graph\n    algo.rs\n"));
+        let back: VecTree<String> = VecTree::from_outline(&text).unwrap();
+        assert_eq!(back.to_nested(), t.to_nested());
+
+        // 其他缩进单位
+        let t: VecTree<u32> = VecTree::from_outline("1\n    2\n        3\n    4").unwrap();
+        assert_eq!(t.to_outline(), "1\n  2\n    3\n  4\n");
+    }
+
+    #[test]
+    fn outline_errors() {
+        let err = |src: &str| match VecTree::<i32>::from_outline(src) {
+            Err(AlgodsError::Parse { line, .. }) => line,
+            other => panic!("expected parse error, got {:?}", other.map(|t| t.size())),
+        };
+        assert_eq!(err(""), 0);
+        assert_eq!(err("  1"), 1);
+        assert_eq!(err("1\n2"), 2);
+        assert_eq!(err("1\n  2\n   3"), 3);
+        assert_eq!(err("1\n  2\n      3"), 3);
+        assert_eq!(err("1\n\n  x"), 3);
+        assert_eq!(err("1\n\t2"), 2);
+    }
+
+    #[test]
+    fn parent_array_round_trip() {
+        let t = sample();
+        let array = t.to_parent_array();
+        assert_eq!(array[0], (None, "src".to_string()));
+        assert_eq!(array[2], (Some(1), "algo.rs".to_string()));
+        assert_eq!(array[6], (Some(0), "lib.rs".to_string()));
+        let back = VecTree::from_parent_array(array).unwrap();
+        assert_eq!(back.to_nested(), t.to_nested());
+
+        // 任意顺序的数组
+        let t = VecTree::from_parent_array([(Some(2), 'c'), (Some(2), 'd'), (None, 'r')]).unwrap();
+        assert_eq!(t.to_string(), "r\n├── c\n└── d");
+    }
+
+    #[test]
+    fn parent_array_errors() {
+        let build = |items: Vec<(Option<usize>, i32)>| VecTree::from_parent_array(items).err();
+        assert_eq!(build(vec![]), Some(AlgodsError::MissingRoot));
+        assert_eq!(
+            build(vec![(Some(1), 0), (Some(0), 1)]),
+            Some(AlgodsError::MissingRoot)
+        );
+        assert_eq!(
+            build(vec![(None, 0), (None, 1)]),
+            Some(AlgodsError::MultipleRoots)
+        );
+        assert_eq!(
+            build(vec![(None, 0), (Some(5), 1)]),
+            Some(AlgodsError::IndexOutOfBounds { index: 5, len: 2 })
+        );
+        assert_eq!(
+            build(vec![(None, 0), (Some(2), 1), (Some(1), 2)]),
+            Some(AlgodsError::WouldCreateCycle)
+        );
+    }
+
+    #[test]
+    fn nested_round_trip() {
+        let nested = NestedTree::new(
+            "ceo",
+            vec![
+                NestedTree::new("cto", vec![NestedTree::leaf("dev")]),
+                NestedTree::leaf("cfo"),
+            ],
+        );
+        let t = VecTree::from(nested.clone());
+        assert_eq!(t.size(), 4);
+        assert_eq!(t.to_string(), "ceo\n├── cto\n│   └── dev\n└── cfo");
+        assert_eq!(t.to_nested(), nested);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn nested_serde_round_trip() {
+        let t = sample();
+        let json = serde_json::to_string(&t.to_nested()).unwrap();
+        assert!(json.starts_with(r#"{"value":"src","children":[{"value":"graph""#));
+        assert!(json.contains(r#"{"value":"lib.rs"}"#));
+
+        let nested: NestedTree<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(VecTree::from(nested).to_outline(), t.to_outline());
+    }
+}
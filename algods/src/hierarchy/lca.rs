@@ -0,0 +1,346 @@
+use crate::{
+    error::{AlgodsError, AlgodsResult},
+    hierarchy::tree::{NodeId, Tree},
+};
+
+/// 预处理的最近公共祖先索引（倍增）
+///
+/// 索引反映的是构建时的树结构，并记录了树的 [`version`](Tree::version)。查询时需传入同一棵树：
+/// 树结构在构建之后被修改（增删、移动节点或调整子节点顺序）时返回 [`AlgodsError::StaleIndex`]，
+/// 应重新构建，可用 [`is_stale`](LcaIndex::is_stale) 检查；不属于索引的节点返回 [`AlgodsError::InvalidNodeId`]
+pub struct LcaIndex {
+    /// arena 下标 → 紧凑编号
+    slots: Vec<Option<usize>>,
+    /// 紧凑编号 → NodeId
+    nodes: Vec<NodeId>,
+    depth: Vec<usize>,
+    /// DFS 进入 / 离开时间，用于 O(1) 祖先判断
+    tin: Vec<usize>,
+    tout: Vec<usize>,
+    /// up[k][v] 为 v 的第 2^k 个祖先（根的祖先为自身）
+    up: Vec<Vec<usize>>,
+    /// 构建时树的结构版本号
+    version: u64,
+}
+
+impl LcaIndex {
+    /// 为整棵树构建索引
+    ///
+    /// ## Notes
+    /// 时间复杂度: O(n log n)，空间复杂度: O(n log n)
+    pub fn new<T: Tree>(tree: &T) -> Self {
+        let root = tree.root();
+        let mut index = Self {
+            slots: Vec::new(),
+            nodes: Vec::with_capacity(tree.size()),
+            depth: Vec::with_capacity(tree.size()),
+            tin: Vec::with_capacity(tree.size()),
+            tout: Vec::with_capacity(tree.size()),
+            up: Vec::new(),
+            version: tree.version(),
+        };
+        let mut parent = Vec::with_capacity(tree.size());
+
+        // 迭代 DFS：(紧凑编号, 尚未访问的子节点)
+        let mut timer = 0;
+        let mut stack = vec![(index.push(root, 0, timer), tree.children_unchecked(root))];
+        parent.push(0);
+        while let Some((v, children)) = stack.last_mut() {
+            let v = *v;
+            if let Some(c) = children.next() {
+                timer += 1;
+                let cv = index.push(c, index.depth[v] + 1, timer);
+                parent.push(v);
+                stack.push((cv, tree.children_unchecked(c)));
+            } else {
+                timer += 1;
+                index.tout[v] = timer;
+                stack.pop();
+            }
+        }
+
+        let n = index.nodes.len();
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        index.up.push(parent);
+        for k in 1..levels {
+            let prev = &index.up[k - 1];
+            let next: Vec<usize> = (0..n).map(|v| prev[prev[v]]).collect();
+            index.up.push(next);
+        }
+        index
+    }
+
+    fn push(&mut self, node: NodeId, depth: usize, time: usize) -> usize {
+        let id = self.nodes.len();
+        if self.slots.len() <= node.index {
+            self.slots.resize(node.index + 1, None);
+        }
+        self.slots[node.index] = Some(id);
+        self.nodes.push(node);
+        self.depth.push(depth);
+        self.tin.push(time);
+        self.tout.push(time);
+        id
+    }
+
+    /// 将 NodeId 映射为紧凑编号：树结构须与构建时一致，节点须在树中存活且属于索引
+    fn slot<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<usize> {
+        if self.is_stale(tree) {
+            return Err(AlgodsError::StaleIndex);
+        }
+        match self.slots.get(node.index) {
+            Some(&Some(id)) if self.nodes[id] == node && tree.contains(node) => Ok(id),
+            _ => Err(AlgodsError::InvalidNodeId),
+        }
+    }
+
+    /// 索引中的节点数
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 索引是否为空（根节点总在索引中，因此恒为 false）
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 树结构在构建索引之后是否被修改过
+    ///
+    /// 时间复杂度: O(1)
+    pub fn is_stale<T: Tree>(&self, tree: &T) -> bool {
+        tree.version() != self.version
+    }
+
+    /// 节点深度
+    ///
+    /// 时间复杂度: O(1)
+    pub fn depth<T: Tree>(&self, tree: &T, node: NodeId) -> AlgodsResult<usize> {
+        Ok(self.depth[self.slot(tree, node)?])
+    }
+
+    /// 判断 `ancestor` 是否为 `node` 的真祖先（不包含自身）
+    ///
+    /// 时间复杂度: O(1)
+    pub fn is_ancestor_of<T: Tree>(
+        &self,
+        tree: &T,
+        ancestor: NodeId,
+        node: NodeId,
+    ) -> AlgodsResult<bool> {
+        let (a, v) = (self.slot(tree, ancestor)?, self.slot(tree, node)?);
+        Ok(a != v && self.covers(a, v))
+    }
+
+    /// a 是否为 v 的祖先（包含自身）
+    #[inline]
+    fn covers(&self, a: usize, v: usize) -> bool {
+        self.tin[a] <= self.tin[v] && self.tout[v] <= self.tout[a]
+    }
+
+    /// 最近公共祖先
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn lca<T: Tree>(&self, tree: &T, a: NodeId, b: NodeId) -> AlgodsResult<NodeId> {
+        let (a, b) = (self.slot(tree, a)?, self.slot(tree, b)?);
+        Ok(self.nodes[self.lca_slot(a, b)])
+    }
+
+    fn lca_slot(&self, mut a: usize, b: usize) -> usize {
+        if self.covers(a, b) {
+            return a;
+        }
+        if self.covers(b, a) {
+            return b;
+        }
+        for k in (0..self.up.len()).rev() {
+            let p = self.up[k][a];
+            if !self.covers(p, b) {
+                a = p;
+            }
+        }
+        self.up[0][a]
+    }
+
+    /// 两节点之间的距离（边数）
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn distance<T: Tree>(&self, tree: &T, a: NodeId, b: NodeId) -> AlgodsResult<usize> {
+        let (a, b) = (self.slot(tree, a)?, self.slot(tree, b)?);
+        let l = self.lca_slot(a, b);
+        Ok(self.depth[a] + self.depth[b] - 2 * self.depth[l])
+    }
+
+    /// 节点的第 k 个祖先（k = 0 时为自身）
+    ///
+    /// ## 返回
+    /// - Ok(Some(ancestor))：存在第 k 个祖先
+    /// - Ok(None)：k 超过节点深度
+    /// - Err(_)：节点无效
+    ///
+    /// 时间复杂度: O(log n)
+    pub fn kth_ancestor<T: Tree>(
+        &self,
+        tree: &T,
+        node: NodeId,
+        k: usize,
+    ) -> AlgodsResult<Option<NodeId>> {
+        let mut v = self.slot(tree, node)?;
+        if k > self.depth[v] {
+            return Ok(None);
+        }
+        for (bit, up) in self.up.iter().enumerate() {
+            if k >> bit & 1 == 1 {
+                v = up[v];
+            }
+        }
+        Ok(Some(self.nodes[v]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hierarchy::{Hierarchy, VecTree};
+
+    /// ```text
+    ///         r
+    ///       / | \
+    ///      a  b  c
+    ///     / \     \
+    ///    d   e     f
+    ///    |
+    ///    g
+    /// ```
+    fn sample() -> (VecTree<char>, Vec<NodeId>) {
+        let mut t = VecTree::with_root('r');
+        let r = t.root();
+        let a = t.add_child(r, 'a').unwrap();
+        let b = t.add_child(r, 'b').unwrap();
+        let c = t.add_child(r, 'c').unwrap();
+        let d = t.add_child(a, 'd').unwrap();
+        let e = t.add_child(a, 'e').unwrap();
+        let f = t.add_child(c, 'f').unwrap();
+        let g = t.add_child(d, 'g').unwrap();
+        (t, vec![r, a, b, c, d, e, f, g])
+    }
+
+    #[test]
+    fn hierarchy_lca_and_path() {
+        let (t, n) = sample();
+        let [r, a, b, _c, d, e, f, g] = n[..] else {
+            unreachable!()
+        };
+
+        assert!(t.is_ancestor_of(r, g).unwrap());
+        assert!(t.is_ancestor_of(a, g).unwrap());
+        assert!(!t.is_ancestor_of(g, g).unwrap());
+        assert!(!t.is_ancestor_of(b, g).unwrap());
+
+        assert_eq!(t.lca(g, e).unwrap(), a);
+        assert_eq!(t.lca(g, f).unwrap(), r);
+        assert_eq!(t.lca(a, g).unwrap(), a);
+        assert_eq!(t.lca(d, d).unwrap(), d);
+
+        assert_eq!(t.distance(g, e).unwrap(), 3);
+        assert_eq!(t.distance(g, f).unwrap(), 5);
+        assert_eq!(t.distance(b, b).unwrap(), 0);
+
+        let names = |p: Vec<NodeId>| p.iter().map(|&x| *t.value(x).unwrap()).collect::<String>();
+        assert_eq!(names(t.path(g, f).unwrap()), "gdarcf");
+        assert_eq!(names(t.path(a, g).unwrap()), "adg");
+        assert_eq!(names(t.path(e, e).unwrap()), "e");
+    }
+
+    #[test]
+    fn index_matches_naive() {
+        let (t, n) = sample();
+        let idx = LcaIndex::new(&t);
+        assert_eq!(idx.len(), 8);
+        for &x in &n {
+            assert_eq!(idx.depth(&t, x).unwrap(), t.depth(x).unwrap());
+            for &y in &n {
+                assert_eq!(idx.lca(&t, x, y).unwrap(), t.lca(x, y).unwrap());
+                assert_eq!(idx.distance(&t, x, y).unwrap(), t.distance(x, y).unwrap());
+                assert_eq!(
+                    idx.is_ancestor_of(&t, x, y).unwrap(),
+                    t.is_ancestor_of(x, y).unwrap()
+                );
+            }
+        }
+        let (r, a, g) = (n[0], n[1], n[7]);
+        assert_eq!(idx.kth_ancestor(&t, g, 2).unwrap(), Some(a));
+        assert_eq!(idx.kth_ancestor(&t, g, 3).unwrap(), Some(r));
+        assert_eq!(idx.kth_ancestor(&t, g, 4).unwrap(), None);
+    }
+
+    #[test]
+    fn index_on_deep_chain() {
+        let mut t = VecTree::with_root(0);
+        let mut chain = vec![t.root()];
+        for i in 1..1000 {
+            let c = t.add_child(chain[i - 1], i).unwrap();
+            chain.push(c);
+        }
+        let side = t.add_child(chain[500], 0).unwrap();
+        let idx = LcaIndex::new(&t);
+        assert_eq!(idx.lca(&t, chain[999], side).unwrap(), chain[500]);
+        assert_eq!(idx.distance(&t, chain[999], side).unwrap(), 499 + 1);
+        assert_eq!(
+            idx.kth_ancestor(&t, chain[999], 999).unwrap(),
+            Some(chain[0])
+        );
+    }
+
+    #[test]
+    fn index_detects_stale_nodes() {
+        let (mut t, n) = sample();
+        let idx = LcaIndex::new(&t);
+        assert!(!idx.is_stale(&t));
+
+        let (a, d, g) = (n[1], n[4], n[7]);
+        t.remove_subtree(d).unwrap();
+        assert!(idx.is_stale(&t));
+        // 树结构变化后所有查询都会被拒绝
+        let fresh = t.add_child(a, 'x').unwrap();
+        assert_eq!(idx.lca(&t, fresh, a), Err(AlgodsError::StaleIndex));
+        assert_eq!(idx.lca(&t, g, a), Err(AlgodsError::StaleIndex));
+        assert_eq!(idx.lca(&t, n[5], n[6]), Err(AlgodsError::StaleIndex));
+
+        let rebuilt = LcaIndex::new(&t);
+        assert!(!rebuilt.is_stale(&t));
+        assert_eq!(rebuilt.lca(&t, g, a), Err(AlgodsError::InvalidNodeId));
+        assert_eq!(rebuilt.lca(&t, fresh, a).unwrap(), a);
+    }
+
+    #[test]
+    fn index_detects_reshaping() {
+        let (mut t, n) = sample();
+        let [r, a, b, c, ..] = n[..] else {
+            unreachable!()
+        };
+
+        // 只移动而不增删节点
+        let idx = LcaIndex::new(&t);
+        t.move_subtree(c, b, 0).unwrap();
+        assert!(idx.is_stale(&t));
+        assert_eq!(idx.lca(&t, c, b), Err(AlgodsError::StaleIndex));
+        let idx = LcaIndex::new(&t);
+        assert_eq!(idx.lca(&t, c, b).unwrap(), b);
+
+        // 调整兄弟顺序
+        t.swap_children(r, 0, 1).unwrap();
+        assert!(idx.is_stale(&t));
+        let idx = LcaIndex::new(&t);
+        t.sort_children_by(r, |x, y| x.cmp(y)).unwrap();
+        assert!(idx.is_stale(&t));
+        let idx = LcaIndex::new(&t);
+        t.insert_before(a, 'z').unwrap();
+        assert!(idx.is_stale(&t));
+
+        // 修改节点值不影响索引
+        let idx = LcaIndex::new(&t);
+        *t.value_mut(a).unwrap() = 'A';
+        assert!(!idx.is_stale(&t));
+        assert_eq!(idx.lca(&t, c, a).unwrap(), r);
+    }
+}
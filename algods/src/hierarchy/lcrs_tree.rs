@@ -0,0 +1,382 @@
+use crate::{
+    error::{AlgodsError, AlgodsResult},
+    hierarchy::tree::{NodeId, Tree},
+};
+
+/// LCRS 树节点
+struct LcrsNode<T> {
+    /// 节点值（已删除节点为 None）
+    value: Option<T>,
+    parent: Option<NodeId>,
+    first_child: Option<usize>,
+    last_child: Option<usize>,
+    prev_sibling: Option<usize>,
+    next_sibling: Option<usize>,
+    generation: u32,
+}
+
+/// 左孩子右兄弟（LCRS）表示的有根树
+///
+/// 每个节点只保存首个子节点与相邻兄弟的链接，子节点以双向链表组织，
+/// 因此添加子节点与删除子树时摘除节点均为 O(1)，适用于写多、扇出较大的场景。
+/// 与 [`VecTree`](crate::hierarchy::VecTree) 一样，节点 ID 带有世代，删除后的 ID 不会误用新节点。
+///
+/// [`Tree::children`] 直接沿兄弟链表迭代，不额外保存子节点列表
+pub struct LcrsTree<T> {
+    nodes: Vec<LcrsNode<T>>,
+    /// 当前存活节点数量
+    alive_count: usize,
+    /// 结构版本号，见 [`Tree::version`]
+    version: u64,
+}
+
+impl<T> LcrsTree<T> {
+    /// 创建一棵带根节点的树
+    ///
+    /// # 参数
+    /// * `value` - 根节点的值
+    ///
+    /// # 返回
+    /// 新创建的 LcrsTree 实例，根节点已经存在
+    pub fn with_root(value: T) -> Self {
+        let mut tree = Self {
+            nodes: Vec::new(),
+            alive_count: 0,
+            version: 0,
+        };
+        tree.alloc_node(value, None);
+        tree
+    }
+
+    fn alloc_node(&mut self, value: T, parent: Option<NodeId>) -> NodeId {
+        let index = self.nodes.len();
+        let id = NodeId {
+            index,
+            generation: 0,
+        };
+        let prev_sibling = parent.and_then(|p| self.nodes[p.index].last_child);
+        self.nodes.push(LcrsNode {
+            value: Some(value),
+            parent,
+            first_child: None,
+            last_child: None,
+            prev_sibling,
+            next_sibling: None,
+            generation: 0,
+        });
+        self.alive_count += 1;
+        self.version += 1;
+
+        if let Some(p) = parent {
+            match prev_sibling {
+                Some(prev) => self.nodes[prev].next_sibling = Some(index),
+                None => self.nodes[p.index].first_child = Some(index),
+            }
+            self.nodes[p.index].last_child = Some(index);
+        }
+        id
+    }
+
+    fn check_alive(&self, node: NodeId) -> AlgodsResult<()> {
+        if self.contains(node) {
+            Ok(())
+        } else {
+            Err(AlgodsError::InvalidNodeId)
+        }
+    }
+
+    /// 下标对应的当前 NodeId
+    #[inline]
+    fn id(&self, index: usize) -> NodeId {
+        NodeId {
+            index,
+            generation: self.nodes[index].generation,
+        }
+    }
+
+    /// 获取节点的第一个子节点
+    ///
+    /// # 返回
+    /// - Ok(Some(child))：存在子节点
+    /// - Ok(None)：节点是叶子
+    /// - Err(_)：节点无效或已被删除
+    pub fn first_child(&self, node: NodeId) -> AlgodsResult<Option<NodeId>> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index].first_child.map(|i| self.id(i)))
+    }
+
+    /// 获取节点的最后一个子节点
+    pub fn last_child(&self, node: NodeId) -> AlgodsResult<Option<NodeId>> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index].last_child.map(|i| self.id(i)))
+    }
+
+    /// 获取节点的下一个兄弟节点（根节点总是返回 None）
+    pub fn next_sibling(&self, node: NodeId) -> AlgodsResult<Option<NodeId>> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index].next_sibling.map(|i| self.id(i)))
+    }
+
+    /// 获取节点的上一个兄弟节点（根节点总是返回 None）
+    pub fn prev_sibling(&self, node: NodeId) -> AlgodsResult<Option<NodeId>> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index].prev_sibling.map(|i| self.id(i)))
+    }
+
+    fn children_of(&self, index: usize) -> LcrsChildren<'_, T> {
+        let node = &self.nodes[index];
+        LcrsChildren {
+            tree: self,
+            front: node.first_child,
+            back: node.last_child,
+        }
+    }
+
+    /// 将节点从父节点的兄弟链表中摘下
+    fn unlink(&mut self, index: usize) {
+        let node = &self.nodes[index];
+        let (parent, prev, next) = (
+            node.parent.unwrap().index,
+            node.prev_sibling,
+            node.next_sibling,
+        );
+        match prev {
+            Some(p) => self.nodes[p].next_sibling = next,
+            None => self.nodes[parent].first_child = next,
+        }
+        match next {
+            Some(n) => self.nodes[n].prev_sibling = prev,
+            None => self.nodes[parent].last_child = prev,
+        }
+    }
+
+    fn remove_inner(&mut self, root: usize) {
+        let mut stack = vec![root];
+        while let Some(idx) = stack.pop() {
+            let mut cur = self.nodes[idx].first_child;
+            while let Some(c) = cur {
+                stack.push(c);
+                cur = self.nodes[c].next_sibling;
+            }
+            let node = &mut self.nodes[idx];
+            node.value = None;
+            node.parent = None;
+            node.first_child = None;
+            node.last_child = None;
+            node.prev_sibling = None;
+            node.next_sibling = None;
+            node.generation += 1;
+            self.alive_count -= 1;
+        }
+        self.version += 1;
+    }
+}
+
+impl<T> Tree for LcrsTree<T> {
+    type Value = T;
+    type Children<'a>
+        = LcrsChildren<'a, T>
+    where
+        Self: 'a;
+
+    fn root(&self) -> NodeId {
+        self.id(0)
+    }
+
+    fn contains(&self, node: NodeId) -> bool {
+        node.index < self.nodes.len() && self.nodes[node.index].generation == node.generation
+    }
+
+    fn parent(&self, node: NodeId) -> AlgodsResult<Option<NodeId>> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index].parent)
+    }
+
+    fn children(&self, node: NodeId) -> AlgodsResult<Self::Children<'_>> {
+        self.check_alive(node)?;
+        Ok(self.children_of(node.index))
+    }
+
+    fn value(&self, node: NodeId) -> AlgodsResult<&Self::Value> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index]
+            .value
+            .as_ref()
+            .expect("alive node has a value"))
+    }
+
+    fn value_mut(&mut self, node: NodeId) -> AlgodsResult<&mut Self::Value> {
+        self.check_alive(node)?;
+        Ok(self.nodes[node.index]
+            .value
+            .as_mut()
+            .expect("alive node has a value"))
+    }
+
+    fn add_child(&mut self, parent: NodeId, value: T) -> AlgodsResult<NodeId> {
+        self.check_alive(parent)?;
+        Ok(self.alloc_node(value, Some(parent)))
+    }
+
+    fn remove_subtree(&mut self, node: NodeId) -> AlgodsResult<()> {
+        self.check_alive(node)?;
+        if node.index == 0 {
+            return Err(AlgodsError::CannotRemoveRoot);
+        }
+
+        self.unlink(node.index);
+        self.remove_inner(node.index);
+        Ok(())
+    }
+
+    fn size(&self) -> usize {
+        self.alive_count
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn parent_unchecked(&self, node: NodeId) -> Option<NodeId> {
+        unsafe { self.nodes.get_unchecked(node.index).parent }
+    }
+
+    fn children_unchecked(&self, node: NodeId) -> Self::Children<'_> {
+        self.children_of(node.index)
+    }
+}
+
+/// 沿兄弟链表迭代 [`LcrsTree`] 节点的直接子节点
+pub struct LcrsChildren<'a, T> {
+    tree: &'a LcrsTree<T>,
+    /// 两端尚未产出的子节点，相遇后均为 None
+    front: Option<usize>,
+    back: Option<usize>,
+}
+
+impl<T> Iterator for LcrsChildren<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.front?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = self.tree.nodes[c].next_sibling;
+        }
+        Some(self.tree.id(c))
+    }
+}
+
+impl<T> DoubleEndedIterator for LcrsChildren<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.back?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = self.tree.nodes[c].prev_sibling;
+        }
+        Some(self.tree.id(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hierarchy::{hierarchy::Hierarchy, lca::LcaIndex};
+
+    use super::*;
+
+    #[test]
+    fn create_tree_with_root() {
+        let tree = LcrsTree::with_root(42);
+        let root = tree.root();
+
+        assert!(tree.contains(root));
+        assert_eq!(*tree.value(root).unwrap(), 42);
+        assert!(tree.parent(root).unwrap().is_none());
+        assert_eq!(tree.children(root).unwrap().next(), None);
+        assert_eq!(tree.first_child(root).unwrap(), None);
+        assert_eq!(tree.size(), 1);
+    }
+
+    #[test]
+    fn sibling_links() {
+        let mut tree = LcrsTree::with_root("root");
+        let root = tree.root();
+        let a = tree.add_child(root, "a").unwrap();
+        let b = tree.add_child(root, "b").unwrap();
+        let c = tree.add_child(root, "c").unwrap();
+
+        assert!(tree.children(root).unwrap().eq([a, b, c]));
+        assert!(tree.children(root).unwrap().rev().eq([c, b, a]));
+        let mut it = tree.children(root).unwrap();
+        assert_eq!((it.next(), it.next_back()), (Some(a), Some(c)));
+        assert_eq!((it.next_back(), it.next()), (Some(b), None));
+        assert_eq!(tree.first_child(root).unwrap(), Some(a));
+        assert_eq!(tree.last_child(root).unwrap(), Some(c));
+        assert_eq!(tree.next_sibling(a).unwrap(), Some(b));
+        assert_eq!(tree.prev_sibling(c).unwrap(), Some(b));
+        assert_eq!(tree.next_sibling(c).unwrap(), None);
+        assert_eq!(tree.next_sibling(root).unwrap(), None);
+
+        // 删除中间节点后兄弟链表应更新
+        tree.remove_subtree(b).unwrap();
+        assert!(tree.children(root).unwrap().eq([a, c]));
+        assert_eq!(tree.next_sibling(a).unwrap(), Some(c));
+        assert_eq!(tree.prev_sibling(c).unwrap(), Some(a));
+
+        tree.remove_subtree(c).unwrap();
+        let d = tree.add_child(root, "d").unwrap();
+        assert!(tree.children(root).unwrap().eq([a, d]));
+        assert_eq!(tree.last_child(root).unwrap(), Some(d));
+    }
+
+    #[test]
+    fn remove_subtree_invalidates_nodes() {
+        let mut tree = LcrsTree::with_root(0);
+        let root = tree.root();
+
+        let a = tree.add_child(root, 1).unwrap();
+        let b = tree.add_child(a, 2).unwrap();
+        let c = tree.add_child(b, 3).unwrap();
+
+        tree.remove_subtree(a).unwrap();
+
+        assert!(!tree.contains(a));
+        assert!(!tree.contains(b));
+        assert!(!tree.contains(c));
+        assert_eq!(tree.value(c), Err(AlgodsError::InvalidNodeId));
+        assert_eq!(tree.children(root).unwrap().next(), None);
+        assert_eq!(tree.size(), 1);
+        assert_eq!(
+            tree.remove_subtree(root),
+            Err(AlgodsError::CannotRemoveRoot)
+        );
+    }
+
+    #[test]
+    fn hierarchy_algorithms() {
+        let mut tree = LcrsTree::with_root(0);
+        let r = tree.root();
+        let a = tree.add_child(r, 1).unwrap();
+        let b = tree.add_child(r, 2).unwrap();
+        let c = tree.add_child(a, 3).unwrap();
+        let d = tree.add_child(b, 4).unwrap();
+
+        let values =
+            |it: Vec<NodeId>| -> Vec<i32> { it.iter().map(|&n| *tree.value(n).unwrap()).collect() };
+        assert_eq!(values(tree.dfs_iter(r).unwrap().collect()), [0, 1, 3, 2, 4]);
+        assert_eq!(values(tree.bfs_iter(r).unwrap().collect()), [0, 1, 2, 3, 4]);
+        assert_eq!(tree.depth(d).unwrap(), 2);
+        assert_eq!(tree.lca(c, d).unwrap(), r);
+        assert_eq!(tree.path(c, d).unwrap(), [c, a, r, b, d]);
+
+        let idx = LcaIndex::new(&tree);
+        assert_eq!(idx.distance(&tree, c, d).unwrap(), 4);
+
+        *tree.value_mut(c).unwrap() = 30;
+        assert_eq!(*tree.value(c).unwrap(), 30);
+    }
+}
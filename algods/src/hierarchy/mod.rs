@@ -1,8 +1,18 @@
+pub mod fold;
 #[allow(clippy::module_inception)]
 pub mod hierarchy;
+pub mod hld;
+pub mod io;
+pub mod lca;
+pub mod lcrs_tree;
 pub mod tree;
 pub mod vec_tree;
 
+pub use fold::*;
 pub use hierarchy::*;
+pub use hld::*;
+pub use io::*;
+pub use lca::*;
+pub use lcrs_tree::*;
 pub use tree::*;
 pub use vec_tree::*;
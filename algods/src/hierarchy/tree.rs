@@ -21,6 +21,11 @@ impl fmt::Debug for NodeId {
 pub trait Tree {
     type Value;
 
+    /// 子节点迭代器，按兄弟顺序产出直接子节点，可反向迭代
+    type Children<'a>: DoubleEndedIterator<Item = NodeId> + 'a
+    where
+        Self: 'a;
+
     /// 获取根节点 ID
     ///
     /// ## 返回
@@ -47,15 +52,15 @@ pub trait Tree {
     /// - Err(_)：节点无效或已被删除
     fn parent(&self, node: NodeId) -> AlgodsResult<Option<NodeId>>;
 
-    /// 获取节点的直接子节点列表
+    /// 获取节点的直接子节点
     ///
     /// ## 参数
     /// * `node` - 待查询节点
     ///
     /// ## 返回
-    /// - Ok(&[NodeId])：节点的所有直接子节点  
+    /// - Ok(iter)：按兄弟顺序迭代节点的所有直接子节点  
     /// - Err(_)：节点无效或已被删除
-    fn children(&self, node: NodeId) -> AlgodsResult<&[NodeId]>;
+    fn children(&self, node: NodeId) -> AlgodsResult<Self::Children<'_>>;
 
     /// 不可变访问节点值
     ///
@@ -101,6 +106,12 @@ pub trait Tree {
     /// 获取当前存活节点数（不含已删除节点）
     fn size(&self) -> usize;
 
+    /// 树结构的版本号
+    ///
+    /// 每次增删节点、移动子树或调整子节点顺序后递增，修改节点值不影响版本号。
+    /// 预处理索引（如 [`LcaIndex`](crate::hierarchy::LcaIndex)）据此判断自身是否过期
+    fn version(&self) -> u64;
+
     /// 获取节点父节点（unchecked，节点必须处于存活状态）
     ///
     /// ## Safety
@@ -113,5 +124,5 @@ pub trait Tree {
     /// ## Safety
     /// 调用者必须保证节点仍然存活，否则行为未定义
     #[doc(hidden)]
-    fn children_unchecked(&self, node: NodeId) -> &[NodeId];
+    fn children_unchecked(&self, node: NodeId) -> Self::Children<'_>;
 }
@@ -1,3 +1,5 @@
+use std::{cmp::Ordering, iter::Copied, slice};
+
 use smallvec::SmallVec;
 
 use crate::{
@@ -12,8 +14,8 @@ const INLINE_CHILDREN: usize = 4;
 /// 适用于读多写少场景。底层使用 Vec 存储节点值和关系，
 /// 子节点使用 SmallVec 内联存储以减少小树的堆分配。
 pub struct VecTree<T> {
-    /// 节点值数组
-    values: Vec<T>,
+    /// 节点值数组（已删除节点的值为 None）
+    values: Vec<Option<T>>,
     /// 父节点数组（根节点为 None）
     parents: Vec<Option<NodeId>>,
     /// 子节点数组，每个节点存储自己的直接子节点
@@ -22,6 +24,8 @@ pub struct VecTree<T> {
     generations: Vec<u32>,
     /// 当前存活节点数量
     alive_count: usize,
+    /// 结构版本号，见 [`Tree::version`]
+    version: u64,
 }
 
 impl<T> VecTree<T> {
@@ -39,6 +43,7 @@ impl<T> VecTree<T> {
             children: Vec::new(),
             generations: Vec::new(),
             alive_count: 0,
+            version: 0,
         };
         tree.alloc_node(value, None);
         tree
@@ -50,11 +55,12 @@ impl<T> VecTree<T> {
             index: self.values.len(),
             generation: 0,
         };
-        self.values.push(value);
+        self.values.push(Some(value));
         self.parents.push(parent);
         self.children.push(SmallVec::new());
         self.generations.push(0);
         self.alive_count += 1;
+        self.version += 1;
 
         if let Some(p) = parent {
             self.children[p.index].push(id);
@@ -79,14 +85,215 @@ impl<T> VecTree<T> {
             }
             self.children[idx].clear();
             self.parents[idx] = None;
+            self.values[idx] = None;
             self.generations[idx] += 1;
             self.alive_count -= 1;
         }
+        self.version += 1;
+    }
+
+    /// 将节点从其父节点的子节点列表中摘下（不改变节点自身的父指针）
+    ///
+    /// # 返回
+    /// 节点原先在兄弟中的位置
+    fn unlink(&mut self, node: NodeId) -> usize {
+        let parent = self.parents[node.index].unwrap();
+        let siblings = &mut self.children[parent.index];
+        let pos = siblings.iter().position(|&c| c == node).unwrap();
+        siblings.remove(pos);
+        pos
+    }
+
+    /// `node` 是否位于以 `root` 为根的子树中（包含 root 自身）
+    fn in_subtree(&self, root: NodeId, mut node: NodeId) -> bool {
+        loop {
+            if node == root {
+                return true;
+            }
+            match self.parents[node.index] {
+                Some(p) => node = p,
+                None => return false,
+            }
+        }
+    }
+
+    fn check_position(position: usize, len: usize) -> AlgodsResult<()> {
+        if position <= len {
+            Ok(())
+        } else {
+            Err(AlgodsError::IndexOutOfBounds {
+                index: position,
+                len,
+            })
+        }
+    }
+
+    /// 在 `parent` 的第 `position` 个子节点位置插入新节点
+    ///
+    /// # 参数
+    /// * `parent` - 父节点
+    /// * `position` - 插入位置，取值范围 `0..=children.len()`，等于长度时追加到末尾
+    /// * `value` - 新节点的值
+    ///
+    /// # 返回
+    /// 新节点的 NodeId；位置越界时返回 `IndexOutOfBounds`
+    pub fn insert_child_at(
+        &mut self,
+        parent: NodeId,
+        position: usize,
+        value: T,
+    ) -> AlgodsResult<NodeId> {
+        self.check_alive(parent)?;
+        Self::check_position(position, self.children[parent.index].len())?;
+        let id = self.alloc_node(value, None);
+        self.parents[id.index] = Some(parent);
+        self.children[parent.index].insert(position, id);
+        Ok(id)
+    }
+
+    /// 在兄弟节点 `sibling` 之前插入新节点
+    ///
+    /// # 返回
+    /// 新节点的 NodeId；`sibling` 为根节点时返回 `RootHasNoSiblings`
+    pub fn insert_before(&mut self, sibling: NodeId, value: T) -> AlgodsResult<NodeId> {
+        let (parent, pos) = self.sibling_slot(sibling)?;
+        self.insert_child_at(parent, pos, value)
+    }
+
+    /// 在兄弟节点 `sibling` 之后插入新节点
+    ///
+    /// # 返回
+    /// 新节点的 NodeId；`sibling` 为根节点时返回 `RootHasNoSiblings`
+    pub fn insert_after(&mut self, sibling: NodeId, value: T) -> AlgodsResult<NodeId> {
+        let (parent, pos) = self.sibling_slot(sibling)?;
+        self.insert_child_at(parent, pos + 1, value)
+    }
+
+    /// 节点的父节点及其在兄弟中的位置
+    fn sibling_slot(&self, node: NodeId) -> AlgodsResult<(NodeId, usize)> {
+        self.check_alive(node)?;
+        let parent = self.parents[node.index].ok_or(AlgodsError::RootHasNoSiblings)?;
+        let pos = self.children[parent.index]
+            .iter()
+            .position(|&c| c == node)
+            .unwrap();
+        Ok((parent, pos))
+    }
+
+    /// 将以 `node` 为根的子树移动为 `new_parent` 的第 `position` 个子节点
+    ///
+    /// 子树内所有节点的 NodeId 保持有效
+    ///
+    /// # 参数
+    /// * `node` - 待移动子树的根
+    /// * `new_parent` - 新的父节点，可以是原父节点（即在兄弟间调整顺序）
+    /// * `position` - 在新父节点子节点列表中的位置，按移出 `node` 之后的列表计算
+    ///
+    /// # 返回
+    /// - `WouldCreateCycle`：`new_parent` 是 `node` 自身或其后代（移动根节点总属于此情况）
+    /// - `IndexOutOfBounds`：位置越界，此时树不会被修改
+    ///
+    /// 时间复杂度: O(depth + children)
+    pub fn move_subtree(
+        &mut self,
+        node: NodeId,
+        new_parent: NodeId,
+        position: usize,
+    ) -> AlgodsResult<()> {
+        self.check_alive(node)?;
+        self.check_alive(new_parent)?;
+        if self.in_subtree(node, new_parent) {
+            return Err(AlgodsError::WouldCreateCycle);
+        }
+
+        let old_parent = self.parents[node.index].unwrap();
+        let len = self.children[new_parent.index].len() - usize::from(old_parent == new_parent);
+        Self::check_position(position, len)?;
+
+        self.unlink(node);
+        self.parents[node.index] = Some(new_parent);
+        self.children[new_parent.index].insert(position, node);
+        self.version += 1;
+        Ok(())
+    }
+
+    /// 交换 `parent` 的第 i 个与第 j 个子节点
+    ///
+    /// # 返回
+    /// 任一位置越界时返回 `IndexOutOfBounds`
+    pub fn swap_children(&mut self, parent: NodeId, i: usize, j: usize) -> AlgodsResult<()> {
+        self.check_alive(parent)?;
+        let children = &mut self.children[parent.index];
+        let len = children.len();
+        if let Some(index) = [i, j].into_iter().find(|&x| x >= len) {
+            return Err(AlgodsError::IndexOutOfBounds { index, len });
+        }
+        children.swap(i, j);
+        self.version += 1;
+        Ok(())
+    }
+
+    /// 按节点值对 `parent` 的直接子节点稳定排序
+    ///
+    /// # 参数
+    /// * `parent` - 父节点
+    /// * `compare` - 子节点值的比较函数
+    ///
+    /// 时间复杂度: O(k log k)，k 为子节点数
+    pub fn sort_children_by<F>(&mut self, parent: NodeId, mut compare: F) -> AlgodsResult<()>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.check_alive(parent)?;
+        let values = &self.values;
+        self.children[parent.index].sort_by(|a, b| {
+            compare(
+                values[a.index].as_ref().unwrap(),
+                values[b.index].as_ref().unwrap(),
+            )
+        });
+        self.version += 1;
+        Ok(())
+    }
+
+    /// 将以 `node` 为根的子树从树中摘下，作为一棵新的 VecTree 返回
+    ///
+    /// 子树中的节点值被移动到新树中，子节点顺序保持不变；
+    /// 原树中这些节点的 NodeId 随之失效，新树使用新的 NodeId
+    ///
+    /// # 返回
+    /// 新树；`node` 为根节点时返回 `CannotRemoveRoot`
+    ///
+    /// 时间复杂度: O(子树大小)
+    pub fn detach(&mut self, node: NodeId) -> AlgodsResult<VecTree<T>> {
+        self.check_alive(node)?;
+        if node.index == 0 {
+            return Err(AlgodsError::CannotRemoveRoot);
+        }
+        self.unlink(node);
+
+        let mut tree = VecTree::with_root(self.values[node.index].take().unwrap());
+        let mut stack: Vec<(NodeId, NodeId)> = self.children[node.index]
+            .iter()
+            .rev()
+            .map(|&c| (c, tree.root()))
+            .collect();
+        while let Some((old, parent)) = stack.pop() {
+            let id = tree.alloc_node(self.values[old.index].take().unwrap(), Some(parent));
+            stack.extend(self.children[old.index].iter().rev().map(|&c| (c, id)));
+        }
+
+        self.remove_inner(node);
+        Ok(tree)
     }
 }
 
 impl<T> Tree for VecTree<T> {
     type Value = T;
+    type Children<'a>
+        = Copied<slice::Iter<'a, NodeId>>
+    where
+        Self: 'a;
 
     fn root(&self) -> NodeId {
         NodeId {
@@ -104,19 +311,23 @@ impl<T> Tree for VecTree<T> {
         Ok(self.parents[node.index])
     }
 
-    fn children(&self, node: NodeId) -> AlgodsResult<&[NodeId]> {
+    fn children(&self, node: NodeId) -> AlgodsResult<Self::Children<'_>> {
         self.check_alive(node)?;
-        Ok(&self.children[node.index])
+        Ok(self.children[node.index].iter().copied())
     }
 
     fn value(&self, node: NodeId) -> AlgodsResult<&Self::Value> {
         self.check_alive(node)?;
-        Ok(&self.values[node.index])
+        Ok(self.values[node.index]
+            .as_ref()
+            .expect("alive node has a value"))
     }
 
     fn value_mut(&mut self, node: NodeId) -> AlgodsResult<&mut Self::Value> {
         self.check_alive(node)?;
-        Ok(&mut self.values[node.index])
+        Ok(self.values[node.index]
+            .as_mut()
+            .expect("alive node has a value"))
     }
 
     fn add_child(&mut self, parent: NodeId, value: T) -> AlgodsResult<NodeId> {
@@ -130,9 +341,7 @@ impl<T> Tree for VecTree<T> {
             return Err(AlgodsError::CannotRemoveRoot);
         }
 
-        let parent = self.parents[node.index].unwrap();
-        self.children[parent.index].retain(|&mut c| c != node);
-
+        self.unlink(node);
         self.remove_inner(node);
         Ok(())
     }
@@ -141,12 +350,16 @@ impl<T> Tree for VecTree<T> {
         self.alive_count
     }
 
+    fn version(&self) -> u64 {
+        self.version
+    }
+
     fn parent_unchecked(&self, node: NodeId) -> Option<NodeId> {
         unsafe { *self.parents.get_unchecked(node.index) }
     }
 
-    fn children_unchecked(&self, node: NodeId) -> &[NodeId] {
-        unsafe { self.children.get_unchecked(node.index) }
+    fn children_unchecked(&self, node: NodeId) -> Self::Children<'_> {
+        unsafe { self.children.get_unchecked(node.index).iter().copied() }
     }
 }
 
@@ -164,7 +377,7 @@ mod tests {
         assert!(tree.contains(root));
         assert_eq!(*tree.value(root).unwrap(), 42);
         assert!(tree.parent(root).unwrap().is_none());
-        assert!(tree.children(root).unwrap().is_empty());
+        assert_eq!(tree.children(root).unwrap().next(), None);
         assert_eq!(tree.size(), 1);
     }
 
@@ -179,7 +392,7 @@ mod tests {
         assert_eq!(tree.parent(a).unwrap(), Some(root));
         assert_eq!(tree.parent(b).unwrap(), Some(root));
 
-        let children = tree.children(root).unwrap();
+        let children: Vec<_> = tree.children(root).unwrap().collect();
         assert_eq!(children.len(), 2);
         assert!(children.contains(&a));
         assert!(children.contains(&b));
@@ -203,7 +416,7 @@ mod tests {
 
         // root 仍然存在
         assert!(tree.contains(root));
-        assert!(tree.children(root).unwrap().is_empty());
+        assert_eq!(tree.children(root).unwrap().next(), None);
         assert_eq!(tree.size(), 1);
     }
 
@@ -274,4 +487,131 @@ mod tests {
         let res = tree.remove_subtree(root);
         assert!(matches!(res, Err(AlgodsError::CannotRemoveRoot)));
     }
+
+    fn names(tree: &VecTree<&'static str>, node: NodeId) -> Vec<&'static str> {
+        tree.children(node)
+            .unwrap()
+            .map(|c| *tree.value(c).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn insert_at_positions() {
+        let mut tree = VecTree::with_root("root");
+        let r = tree.root();
+        let b = tree.add_child(r, "b").unwrap();
+        tree.insert_child_at(r, 0, "a").unwrap();
+        tree.insert_after(b, "c").unwrap();
+        tree.insert_before(b, "ab").unwrap();
+        tree.insert_child_at(r, 4, "d").unwrap();
+        assert_eq!(names(&tree, r), ["a", "ab", "b", "c", "d"]);
+        assert_eq!(tree.size(), 6);
+
+        assert_eq!(
+            tree.insert_child_at(r, 6, "x"),
+            Err(AlgodsError::IndexOutOfBounds { index: 6, len: 5 })
+        );
+        assert_eq!(
+            tree.insert_after(r, "x"),
+            Err(AlgodsError::RootHasNoSiblings)
+        );
+        assert_eq!(tree.size(), 6);
+    }
+
+    #[test]
+    fn move_subtree_keeps_ids() {
+        // root ─┬─ docs ── a.txt
+        //       └─ src ─── lib
+        let mut tree = VecTree::with_root("root");
+        let r = tree.root();
+        let docs = tree.add_child(r, "docs").unwrap();
+        let a = tree.add_child(docs, "a.txt").unwrap();
+        let src = tree.add_child(r, "src").unwrap();
+        let lib = tree.add_child(src, "lib").unwrap();
+
+        tree.move_subtree(docs, lib, 0).unwrap();
+        assert_eq!(tree.parent(docs).unwrap(), Some(lib));
+        assert_eq!(names(&tree, r), ["src"]);
+        assert_eq!(tree.depth(a).unwrap(), 4);
+        assert_eq!(tree.size(), 5);
+
+        // 在同一父节点下调整顺序
+        let x = tree.add_child(r, "x").unwrap();
+        tree.move_subtree(x, r, 0).unwrap();
+        assert_eq!(names(&tree, r), ["x", "src"]);
+        assert_eq!(
+            tree.move_subtree(x, r, 2),
+            Err(AlgodsError::IndexOutOfBounds { index: 2, len: 1 })
+        );
+        assert_eq!(names(&tree, r), ["x", "src"]);
+    }
+
+    #[test]
+    fn move_subtree_rejects_cycles() {
+        let mut tree = VecTree::with_root(0);
+        let r = tree.root();
+        let a = tree.add_child(r, 1).unwrap();
+        let b = tree.add_child(a, 2).unwrap();
+
+        assert_eq!(
+            tree.move_subtree(a, b, 0),
+            Err(AlgodsError::WouldCreateCycle)
+        );
+        assert_eq!(
+            tree.move_subtree(a, a, 0),
+            Err(AlgodsError::WouldCreateCycle)
+        );
+        assert_eq!(
+            tree.move_subtree(r, b, 0),
+            Err(AlgodsError::WouldCreateCycle)
+        );
+        assert_eq!(tree.parent(b).unwrap(), Some(a));
+    }
+
+    #[test]
+    fn swap_and_sort_children() {
+        let mut tree = VecTree::with_root("root");
+        let r = tree.root();
+        for name in ["carol", "alice", "bob"] {
+            tree.add_child(r, name).unwrap();
+        }
+        tree.swap_children(r, 0, 2).unwrap();
+        assert_eq!(names(&tree, r), ["bob", "alice", "carol"]);
+        assert_eq!(
+            tree.swap_children(r, 0, 3),
+            Err(AlgodsError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+
+        tree.sort_children_by(r, |a, b| a.cmp(b)).unwrap();
+        assert_eq!(names(&tree, r), ["alice", "bob", "carol"]);
+        tree.sort_children_by(r, |a, b| b.len().cmp(&a.len()))
+            .unwrap();
+        assert_eq!(names(&tree, r), ["alice", "carol", "bob"]);
+    }
+
+    #[test]
+    fn detach_into_new_tree() {
+        let mut tree = VecTree::with_root("ceo".to_string());
+        let r = tree.root();
+        let cto = tree.add_child(r, "cto".to_string()).unwrap();
+        let dev1 = tree.add_child(cto, "dev1".to_string()).unwrap();
+        let _dev2 = tree.add_child(cto, "dev2".to_string()).unwrap();
+        let _lead = tree.add_child(dev1, "lead".to_string()).unwrap();
+        let cfo = tree.add_child(r, "cfo".to_string()).unwrap();
+
+        let team = tree.detach(cto).unwrap();
+        assert_eq!(tree.size(), 2);
+        assert!(!tree.contains(cto) && !tree.contains(dev1));
+        assert!(tree.children(r).unwrap().eq([cfo]));
+
+        assert_eq!(team.size(), 4);
+        let order: Vec<_> = team
+            .dfs_iter(team.root())
+            .unwrap()
+            .map(|n| team.value(n).unwrap().as_str())
+            .collect();
+        assert_eq!(order, ["cto", "dev1", "lead", "dev2"]);
+
+        assert_eq!(tree.detach(r).err(), Some(AlgodsError::CannotRemoveRoot));
+    }
 }